@@ -0,0 +1,28 @@
+use std::net::SocketAddrV4;
+use std::time::Duration;
+
+use eipscanne_rs::discovery;
+
+const DISCOVERY_WINDOW: Duration = Duration::from_secs(2);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // ========= Broadcast the ListIdentity request ============
+    println!("BROADCASTING ListIdentity");
+    let discovered_devices = discovery::discover(DISCOVERY_WINDOW)?;
+    // ^^^^^^^^^ Broadcast the ListIdentity request ^^^^^^^^^^^^
+
+    println!("Found {} device(s)\n", discovered_devices.len());
+
+    for discovered_device in discovered_devices {
+        let identity = discovered_device.identity;
+
+        println!(
+            "  --> {} ({}) at {}",
+            String::from(identity.product_name),
+            identity.serial_number,
+            SocketAddrV4::from(identity.socket_address)
+        );
+    }
+
+    Ok(())
+}
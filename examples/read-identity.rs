@@ -1,9 +1,6 @@
-use tokio::net::TcpStream;
-
 use eipscanne_rs::cip::identity::IdentityResponse;
-use eipscanne_rs::object_assembly::RequestObjectAssembly;
-
-mod stream_utils;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::client::EipClient;
 
 const ETHERNET_IP_PORT: u16 = 0xAF12;
 
@@ -13,56 +10,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // let address = format!("172.28.0.10:{}", ETHERNET_IP_PORT); // Change this to the correct IP and port
     let address = format!("172.31.19.10:{}", ETHERNET_IP_PORT); // Change this to the correct IP and port
 
-    let mut stream = TcpStream::connect(address).await?;
-
     // ========= Register the session ============
     println!("REQUESTING registration");
-    stream_utils::write_object_assembly(&mut stream, RequestObjectAssembly::new_registration())
-        .await;
-    let registration_response = stream_utils::read_object_assembly::<u8>(&mut stream).await?;
+    let client = EipClient::connect(address).await?;
 
-    // println!("{:#?}\n", registration_response);     // NOTE: the :#? triggers a pretty-print
-    println!("{:?}\n", registration_response);
+    println!("Registered session {:#010x}\n", client.session_handle());
     // ^^^^^^^^^ Register the session ^^^^^^^^^^^^
 
-    let provided_session_handle = registration_response
-        .packet_description
-        .header
-        .session_handle;
-
     // ========= Request the identity object ============
     println!("REQUESTING identity");
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::new_identity(provided_session_handle),
-    )
-    .await;
-    let identity_response_object =
-        stream_utils::read_object_assembly::<IdentityResponse>(&mut stream).await?;
+    let identity_response = client
+        .get_attributes_all::<IdentityResponse>(CipPath::new(0x1, 0x1))
+        .await?;
 
-    // println!("{:#?}\n", identity_response_object);      // NOTE: the :#? triggers a pretty-print
-    println!("{:?}\n", identity_response_object);
+    // println!("{:#?}\n", identity_response);      // NOTE: the :#? triggers a pretty-print
+    println!("{:?}\n", identity_response);
 
-    let message_router_response = identity_response_object.cip_message.unwrap();
     println!(
         "  --> Product Name: {:?}\n",
-        String::from(
-            message_router_response
-                .response_data
-                .data
-                .unwrap()
-                .product_name
-        )
+        String::from(identity_response.product_name)
     );
     // ^^^^^^^^^ Request the identity object ^^^^^^^^^^^^
 
     // ========= UnRegister the sesion ============
     println!("REQUESTING un-registration");
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::new_unregistration(provided_session_handle),
-    )
-    .await;
+    client.close().await?;
 
     println!("UN Registered the CIP session");
     // ^^^^^^^^^ UnRegister the session ^^^^^^^^^^^^
@@ -169,7 +169,7 @@ mod tests {
 
     use bilge::prelude::u10;
 
-    use eipscanne_rs::eip::description::{CommonPacketDescriptor, CommonPacketItemId};
+    use eipscanne_rs::eip::description::CommonPacketItem;
     use eipscanne_rs::object_assembly::ResponseObjectAssembly;
     use hex_test_macros::prelude::*;
 
@@ -377,6 +377,7 @@ mod tests {
                 0,
             ),
             cip_message: Some(set_digital_output_message),
+            route: Default::default(),
         };
 
         // Write the object_assembly binary data to the buffer
@@ -497,24 +498,21 @@ mod tests {
                     sender_context: [0x0; 8],
                     options: 0x0,
                 },
-                command_specific_data: CommandSpecificData::SendRrData(RRPacketData {
-                    interface_handle: 0x0,
-                    timeout: 0,
-                    empty_data_packet: CommonPacketDescriptor {
-                        type_id: CommonPacketItemId::NullAddr,
-                        packet_length: Some(0),
-                    },
-                    unconnected_data_packet: CommonPacketDescriptor {
-                        type_id: CommonPacketItemId::UnconnectedMessage,
-                        packet_length: Some(284),
-                    },
-                }),
+                command_specific_data: CommandSpecificData::SendRrData(RRPacketData::with_items(
+                    0x0,
+                    0,
+                    vec![
+                        CommonPacketItem::NullAddress,
+                        CommonPacketItem::UnconnectedMessage(Some(raw_bytes[40..].to_vec())),
+                    ],
+                )),
             },
             cip_message: Some(MessageRouterResponse {
                 service_container: ServiceContainer::new(ServiceCode::GetAttributeSingle, true),
                 response_data: ResponseData {
                     status: ResponseStatusCode::Success,
                     additional_status_size: 0,
+                    additional_status: vec![],
                     data: Some(OutputAssemblyObject {
                         io_output_data: IOOutputData::new_digital_outputs(DigitalOutputs::new(
                             false,
@@ -535,7 +533,7 @@ mod tests {
             }),
         };
 
-        let byte_cursor = std::io::Cursor::new(raw_bytes);
+        let byte_cursor = std::io::Cursor::new(raw_bytes.clone());
         let mut buf_reader = std::io::BufReader::new(byte_cursor);
 
         let response_object =
@@ -1,21 +1,17 @@
 use clap::Parser;
-use tokio::net::TcpStream;
 
-use eipscanne_rs::cip::message::shared::ServiceCode;
 use eipscanne_rs::cip::path::CipPath;
-use eipscanne_rs::object_assembly::RequestObjectAssembly;
+use eipscanne_rs::client::EipClient;
 
 // Assert dependency on the different modules in this directory
 mod clearlink_config;
 mod clearlink_output;
 mod cli_config;
-mod duplicated_stream_utils;
 
 // Make sure the code itself looks the same
 use clearlink_config::ConfigAssemblyObject;
 use clearlink_output::OutputAssemblyObject;
 use cli_config::{set_io_data, CliArgs};
-use duplicated_stream_utils as stream_utils;
 
 const ETHERNET_IP_PORT: u16 = 0xAF12;
 
@@ -27,72 +23,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // let address = format!("172.28.0.10:{}", ETHERNET_IP_PORT); // Change this to the correct IP and port
     let address = format!("172.31.19.10:{}", ETHERNET_IP_PORT); // Change this to the correct IP and port
 
-    let mut stream = TcpStream::connect(address).await?;
-
     // ========= Register the session ============
     println!("REQUESTING - REGISTER session");
-    stream_utils::write_object_assembly(&mut stream, RequestObjectAssembly::new_registration())
-        .await;
-    let registration_response = stream_utils::read_object_assembly::<u8>(&mut stream).await?;
-
-    // println!("{:#?}\n", registration_response);     // NOTE: the :#? triggers a pretty-print
-    // println!("{:?}\n", registration_response);
+    let client = EipClient::connect(address).await?;
     // ^^^^^^^^^ Register the session ^^^^^^^^^^^^
 
-    let provided_session_handle = registration_response
-        .packet_description
-        .header
-        .session_handle;
-
     // ========= Write the ClearLink Config ============
     println!("REQUESTING - SET config");
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::new_service_request(
-            provided_session_handle,
+    client
+        .set_attribute_single(
             CipPath::new_full(0x4, 0x96, 0x3),
-            ServiceCode::SetAttributeSingle,
-            Some(ConfigAssemblyObject::default()),
-        ),
-    )
-    .await;
-
-    let _config_success_response = stream_utils::read_object_assembly::<u8>(&mut stream).await?;
-
-    // println!("{:#?}\n", _config_success_response);      // NOTE: the :#? triggers a pretty-print
-    // println!("{:?}\n", _config_success_response);
+            ConfigAssemblyObject::default(),
+        )
+        .await?;
     // ^^^^^^^^^ Write the ClearLink Config ^^^^^^^^^^^^
 
     // ========= Request the digital output ============
     println!("REQUESTING - GET digital output");
 
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::<u8>::new_service_request(
-            provided_session_handle,
-            CipPath::new_full(0x4, 0x70, 0x3),
-            ServiceCode::GetAttributeSingle,
-            None,
-        ),
-    )
-    .await;
-
-    // TODO: Create the response for the SetDigitalIO message in the teknic_cip
-    let set_digital_io_response_object =
-        stream_utils::read_object_assembly::<OutputAssemblyObject>(&mut stream).await?;
-
-    // println!("{:#?}\n", _set_digital_io_response_object);      // NOTE: the :#? triggers a pretty-print
-    // println!("{:?}\n", _set_digital_io_response_object);
+    let mut output_assembly_data = client
+        .get_attribute_single::<OutputAssemblyObject>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await?;
+
+    // println!("{:#?}\n", output_assembly_data);      // NOTE: the :#? triggers a pretty-print
+    // println!("{:?}\n", output_assembly_data);
     // ^^^^^^^^^ Request the digital output ^^^^^^^^^^^^
 
     // ========= Write the Digital Output ============
-    let mut output_assembly_data = set_digital_io_response_object
-        .cip_message
-        .unwrap()
-        .response_data
-        .data
-        .unwrap();
-
     // let mut output_assembly_data = OutputAssemblyObject::test_default();
 
     // |||||||||||||||||||||||||||||||||
@@ -106,29 +63,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("REQUESTING - SET digital output");
 
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::new_service_request(
-            provided_session_handle,
-            CipPath::new_full(0x4, 0x70, 0x3),
-            ServiceCode::SetAttributeSingle,
-            Some(output_assembly_data),
-        ),
-    )
-    .await;
-
-    let _set_digital_io_success_response =
-        stream_utils::read_object_assembly::<u8>(&mut stream).await?;
-
+    client
+        .set_attribute_single(CipPath::new_full(0x4, 0x70, 0x3), output_assembly_data)
+        .await?;
     // ^^^^^^^^^ Write the Digital Output ^^^^^^^^^^^^
 
     // ========= UnRegister the sesion ============
     println!("REQUESTING - UN REGISTER session");
-    stream_utils::write_object_assembly(
-        &mut stream,
-        RequestObjectAssembly::new_unregistration(provided_session_handle),
-    )
-    .await;
+    client.close().await?;
 
     println!("UN Registered the CIP session");
     // ^^^^^^^^^ UnRegister the session ^^^^^^^^^^^^
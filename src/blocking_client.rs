@@ -0,0 +1,527 @@
+use std::io::{BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use binrw::meta::{ReadEndian, WriteEndian};
+use binrw::{BinRead, BinWrite};
+
+use crate::cip::connection_manager::{
+    ForwardOpenReply, ForwardOpenRequest, LargeForwardOpenRequest, OpenedConnection,
+    CONNECTION_MANAGER_CLASS_ID,
+};
+use crate::cip::message::attribute_list::{
+    AttributeResponse, AttributeSetting, AttributeStatus, AttributeTypeMap, GetAttributeListReply,
+    GetAttributeListRequest, SetAttributeListReply, SetAttributeListRequest,
+};
+use crate::cip::message::multiple_service::{
+    MultipleServiceReply, MultipleServiceRequest, MESSAGE_ROUTER_CLASS_ID,
+};
+use crate::cip::message::response::MessageRouterResponse;
+use crate::cip::message::shared::ServiceCode;
+use crate::cip::path::CipPath;
+use crate::cip::types::{CipUdint, CipUint};
+use crate::eip::description::CommonPacketList;
+use crate::eip::framing::{check_frame_status, connected_sequence, read_frame, sender_context};
+use crate::error::{require_reply_data, EipError, EipResult};
+use crate::object_assembly::{
+    ConnectedResponseObjectAssembly, ExplicitConnection, RequestObjectAssembly,
+    ResponseObjectAssembly, ServiceReplyObjectAssembly,
+};
+use crate::session::{
+    discards_session, is_retryable, opened_connection, request_data_bytes, EipClientConfig,
+};
+
+// Used for connecting, reading and writing unless another timeout is given
+pub const DEFAULT_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The synchronous counterpart of `EipClient` for programs without an async runtime. Requests are
+//  sent one at a time, each with its own sender context, and retried and reconnected by the same
+//  `EipClientConfig` rules
+#[derive(Debug)]
+pub struct BlockingEipClient {
+    socket_addresses: Vec<SocketAddr>,
+    config: EipClientConfig,
+    // `None` once the session is closed or discarded after a failed request
+    stream: Option<TcpStream>,
+    session_handle: CipUdint,
+    next_sender_context: u64,
+}
+
+// ======= Start of BlockingEipClient impl ========
+
+impl BlockingEipClient {
+    /// Connects to the device and registers a new session
+    pub fn connect<A: ToSocketAddrs>(address: A) -> EipResult<Self> {
+        Self::connect_with_timeout(address, DEFAULT_IO_TIMEOUT)
+    }
+
+    /// Connects to the device and registers a new session, with `timeout` bounding the connect
+    ///  and every later read and write
+    pub fn connect_with_timeout<A: ToSocketAddrs>(
+        address: A,
+        timeout: Duration,
+    ) -> EipResult<Self> {
+        Self::connect_with_config(
+            address,
+            EipClientConfig {
+                request_timeout: timeout,
+                ..EipClientConfig::default()
+            },
+        )
+    }
+
+    pub fn connect_with_config<A: ToSocketAddrs>(
+        address: A,
+        config: EipClientConfig,
+    ) -> EipResult<Self> {
+        let mut client = BlockingEipClient {
+            socket_addresses: address.to_socket_addrs()?.collect(),
+            config,
+            stream: None,
+            session_handle: 0,
+            next_sender_context: 0,
+        };
+
+        client.open_session()?;
+
+        Ok(client)
+    }
+
+    /// The handle of the most recently registered session
+    pub fn session_handle(&self) -> CipUdint {
+        self.session_handle
+    }
+
+    pub fn config(&self) -> &EipClientConfig {
+        &self.config
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) -> EipResult<()> {
+        self.config.request_timeout = timeout;
+
+        if let Some(stream) = self.stream.as_ref() {
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.config.max_frame_size = max_frame_size;
+    }
+
+    /// Sends an unconnected explicit request and returns the reply data, if the device sent any
+    pub fn send_service<T, R>(
+        &mut self,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<Option<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        self.send_service_reply::<T, R>(request_path.clone(), service_code, data)?
+            .into_result(request_path)
+    }
+
+    /// Sends an unconnected explicit request and returns the whole reply, whatever its status
+    pub fn send_service_reply<T, R>(
+        &mut self,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<MessageRouterResponse<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        self.send_with_retries(
+            &request_path,
+            service_code,
+            request_data_bytes(data.as_ref())?,
+            ResponseObjectAssembly::<R>::into_message_router_response,
+        )
+    }
+
+    /// Opens a connection with a standard Forward_Open
+    pub fn forward_open(
+        &mut self,
+        forward_open: &ForwardOpenRequest,
+    ) -> EipResult<OpenedConnection> {
+        let (forward_open_reply, packet_items) =
+            self.send_connection_manager_request(ServiceCode::ForwardOpen, forward_open)?;
+
+        opened_connection(forward_open_reply, &packet_items)
+    }
+
+    /// Opens a connection with Large_Forward_Open. Targets that don't support it are sent the
+    ///  standard Forward_Open instead, if the connection sizes fit
+    pub fn large_forward_open(
+        &mut self,
+        large_forward_open: &LargeForwardOpenRequest,
+    ) -> EipResult<OpenedConnection> {
+        let (forward_open_reply, packet_items) = self
+            .send_connection_manager_request(ServiceCode::LargeForwardOpen, large_forward_open)?;
+
+        match large_forward_open.fallback_request(&forward_open_reply) {
+            Some(forward_open) => self.forward_open(&forward_open),
+            None => opened_connection(forward_open_reply, &packet_items),
+        }
+    }
+
+    pub fn get_attribute_single<R>(&mut self, request_path: CipPath) -> EipResult<R>
+    where
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let reply_data =
+            self.send_service::<u8, R>(request_path, ServiceCode::GetAttributeSingle, None)?;
+
+        require_reply_data(reply_data)
+    }
+
+    pub fn set_attribute_single<T>(&mut self, request_path: CipPath, value: T) -> EipResult<()>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        self.send_service::<T, u8>(request_path, ServiceCode::SetAttributeSingle, Some(value))?;
+
+        Ok(())
+    }
+
+    pub fn get_attributes_all<R>(&mut self, request_path: CipPath) -> EipResult<R>
+    where
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let reply_data =
+            self.send_service::<u8, R>(request_path, ServiceCode::GetAttributeAll, None)?;
+
+        require_reply_data(reply_data)
+    }
+
+    /// Reads the listed attributes, decoding each value with its data type in `type_map`
+    pub fn get_attribute_list(
+        &mut self,
+        request_path: CipPath,
+        attribute_ids: Vec<CipUint>,
+        type_map: &AttributeTypeMap,
+    ) -> EipResult<Vec<AttributeResponse>> {
+        let attribute_list_reply = self
+            .send_service_reply::<GetAttributeListRequest, GetAttributeListReply>(
+                request_path.clone(),
+                ServiceCode::GetAttributeList,
+                Some(GetAttributeListRequest { attribute_ids }),
+            )?
+            .into_get_attribute_list_reply(request_path)?;
+
+        Ok(attribute_list_reply.decode(type_map)?)
+    }
+
+    pub fn set_attribute_list(
+        &mut self,
+        request_path: CipPath,
+        attributes: Vec<AttributeSetting>,
+    ) -> EipResult<Vec<AttributeStatus>> {
+        let attribute_list_reply = self
+            .send_service_reply::<SetAttributeListRequest, SetAttributeListReply>(
+                request_path.clone(),
+                ServiceCode::SetAttributeList,
+                Some(SetAttributeListRequest { attributes }),
+            )?
+            .into_set_attribute_list_reply(request_path)?;
+
+        Ok(attribute_list_reply.attribute_statuses)
+    }
+
+    /// Sends several requests in one Multiple_Service_Packet. A failed embedded request only
+    ///  fails its own reply
+    pub fn send_multiple_service(
+        &mut self,
+        multiple_service_request: MultipleServiceRequest,
+    ) -> EipResult<MultipleServiceReply> {
+        let request_path = CipPath::new(MESSAGE_ROUTER_CLASS_ID, 0x1);
+
+        self.send_service_reply::<MultipleServiceRequest, MultipleServiceReply>(
+            request_path.clone(),
+            ServiceCode::MultipleServicePacket,
+            Some(multiple_service_request),
+        )?
+        .into_multiple_service_reply(request_path)
+    }
+
+    /// Sends an explicit request over a connection opened on this session (e.g. with a Class 3
+    ///  `forward_open`) and returns the reply data, if the device sent any
+    pub fn send_connected_service<T, R>(
+        &mut self,
+        connection: &mut ExplicitConnection,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<Option<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        // A connection ends with the session it was opened on
+        if self.stream.is_none() || connection.session_handle != self.session_handle {
+            return Err(EipError::SessionInvalid(connection.session_handle));
+        }
+
+        let request = connection.new_service_request(request_path.clone(), service_code, data);
+        let sequence_count = connection.sequence_count();
+        let reply_sequence = Some((connection.t2o_connection_id, sequence_count));
+
+        let reply_result = self
+            .exchange(request, |frame| connected_sequence(frame) == reply_sequence)
+            .and_then(|response_frame| {
+                check_frame_status(&response_frame)?;
+
+                let mut response_reader = BufReader::new(std::io::Cursor::new(response_frame));
+                Ok(ConnectedResponseObjectAssembly::<R>::read(
+                    &mut response_reader,
+                )?)
+            });
+
+        if let Err(reply_err) = &reply_result {
+            if discards_session(reply_err) {
+                self.stream.take();
+            }
+        }
+
+        let connected_response = reply_result?;
+
+        if !connection.is_reply_to(&connected_response, sequence_count) {
+            return Err(EipError::MalformedFrame(binrw::Error::AssertFail {
+                pos: 0,
+                message: format!("the reply isn't for sequence count {}", sequence_count),
+            }));
+        }
+
+        connected_response.into_result(request_path)
+    }
+
+    /// Unregisters the session and closes the connection
+    pub fn close(mut self) -> EipResult<()> {
+        self.unregister()
+    }
+
+    fn unregister(&mut self) -> EipResult<()> {
+        let mut stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => return Ok(()),
+        };
+
+        // The device closes the connection without replying to an unregistration
+        write_request(
+            &mut stream,
+            RequestObjectAssembly::new_unregistration(self.session_handle),
+        )?;
+        stream.shutdown(Shutdown::Both)?;
+
+        Ok(())
+    }
+
+    // Forward_Open replies carry data for both success and failure, so the whole reply is read
+    //  along with the socket address items sent next to it
+    fn send_connection_manager_request<T>(
+        &mut self,
+        service_code: ServiceCode,
+        request: &T,
+    ) -> EipResult<(ForwardOpenReply, CommonPacketList)>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        self.send_with_retries(
+            &CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            service_code,
+            request_data_bytes(Some(request))?,
+            ServiceReplyObjectAssembly::<ForwardOpenReply>::into_reply_with_items,
+        )
+    }
+
+    // Sends the request until it gets a reply, `into_reply` fails with an error that isn't worth
+    //  retrying, or the retry policy runs out
+    fn send_with_retries<A, O>(
+        &mut self,
+        request_path: &CipPath,
+        service_code: ServiceCode,
+        data_bytes: Option<Vec<u8>>,
+        into_reply: fn(A) -> EipResult<O>,
+    ) -> EipResult<O>
+    where
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        let mut retries = 0;
+
+        loop {
+            let reply_result =
+                self.send_service_once(request_path, service_code, data_bytes.clone(), into_reply);
+
+            let retry_allowed = match &reply_result {
+                Err(reply_err) => is_retryable(reply_err, service_code),
+                Ok(_) => false,
+            };
+
+            if !retry_allowed || retries >= self.config.retry_policy.max_retries {
+                return reply_result;
+            }
+
+            retries += 1;
+            std::thread::sleep(self.config.retry_policy.retry_delay);
+        }
+    }
+
+    fn send_service_once<A, O>(
+        &mut self,
+        request_path: &CipPath,
+        service_code: ServiceCode,
+        data_bytes: Option<Vec<u8>>,
+        into_reply: fn(A) -> EipResult<O>,
+    ) -> EipResult<O>
+    where
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        if self.stream.is_none() {
+            if !self.config.reconnect {
+                return Err(connection_closed());
+            }
+
+            self.open_session()?;
+        }
+
+        let request = RequestObjectAssembly::new_service_request(
+            self.session_handle,
+            request_path.clone(),
+            service_code,
+            data_bytes,
+        );
+
+        let reply_result = self.transact::<Vec<u8>, A>(request).and_then(into_reply);
+
+        // Dropping the stream leaves any late reply behind with the old connection
+        if let Err(reply_err) = &reply_result {
+            if discards_session(reply_err) {
+                self.stream.take();
+            }
+        }
+
+        reply_result
+    }
+
+    fn open_session(&mut self) -> EipResult<()> {
+        self.stream = Some(self.connect_stream()?);
+
+        let registration_result = self
+            .transact::<u8, ResponseObjectAssembly<u8>>(RequestObjectAssembly::new_registration())
+            .and_then(|registration_response| {
+                registration_response.packet_description.check_status()?;
+
+                Ok(registration_response
+                    .packet_description
+                    .header
+                    .session_handle)
+            });
+
+        match registration_result {
+            Ok(session_handle) => {
+                self.session_handle = session_handle;
+                Ok(())
+            }
+            Err(registration_err) => {
+                self.stream.take();
+                Err(registration_err)
+            }
+        }
+    }
+
+    fn connect_stream(&self) -> EipResult<TcpStream> {
+        let mut last_connect_err = None;
+
+        for socket_address in self.socket_addresses.iter() {
+            match TcpStream::connect_timeout(socket_address, self.config.request_timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(self.config.request_timeout))?;
+                    stream.set_write_timeout(Some(self.config.request_timeout))?;
+
+                    return Ok(stream);
+                }
+                Err(connect_err) => last_connect_err = Some(connect_err),
+            }
+        }
+
+        match last_connect_err {
+            Some(connect_err) => Err(connect_err.into()),
+            None => Err(EipError::Io(std::io::ErrorKind::AddrNotAvailable.into())),
+        }
+    }
+
+    // `A` is the object assembly the reply frame is read as
+    fn transact<T, A>(&mut self, mut request: RequestObjectAssembly<T>) -> EipResult<A>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        let request_context = self.next_sender_context.to_le_bytes();
+        self.next_sender_context = self.next_sender_context.wrapping_add(1);
+        request.packet_description.header.sender_context = request_context;
+
+        let response_frame = self.exchange(request, |frame| {
+            connected_sequence(frame).is_none() && sender_context(frame) == request_context
+        })?;
+
+        check_frame_status(&response_frame)?;
+        let mut response_reader = BufReader::new(std::io::Cursor::new(response_frame));
+
+        Ok(A::read(&mut response_reader)?)
+    }
+
+    // Writes the request and reads frames until `is_reply` picks out its reply. Replies to any
+    //  earlier request are skipped
+    fn exchange<Q>(&mut self, request: Q, is_reply: impl Fn(&[u8]) -> bool) -> EipResult<Vec<u8>>
+    where
+        Q: for<'a> BinWrite<Args<'a> = ()> + WriteEndian,
+    {
+        let max_frame_size = self.config.max_frame_size;
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Err(connection_closed()),
+        };
+
+        write_request(stream, request)?;
+
+        loop {
+            let frame = read_frame(stream, max_frame_size)?;
+
+            if is_reply(&frame) {
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+impl Drop for BlockingEipClient {
+    fn drop(&mut self) {
+        let _ = self.unregister();
+    }
+}
+
+// ^^^^^^^^ End of BlockingEipClient impl ^^^^^^^^
+
+fn write_request<Q>(stream: &mut TcpStream, request: Q) -> EipResult<()>
+where
+    Q: for<'a> BinWrite<Args<'a> = ()> + WriteEndian,
+{
+    let mut request_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut request_bytes);
+
+    request.write(&mut writer)?;
+
+    stream.write_all(&request_bytes)?;
+
+    Ok(())
+}
+
+fn connection_closed() -> EipError {
+    EipError::Io(std::io::ErrorKind::UnexpectedEof.into())
+}
@@ -0,0 +1,569 @@
+use std::net::SocketAddrV4;
+
+use bilge::prelude::{bitsize, u2, u3, u4, u9, Bitsized, DebugBits, FromBits, Number};
+
+use binrw::{
+    binread,
+    binrw,    // #[binrw] attribute
+    binwrite, // #[binwrite] attribute
+    BinRead,
+    BinResult,
+    BinWrite,
+};
+
+use super::message::request::MessageRouterRequest;
+use super::message::response::ResponseStatusCode;
+use super::message::shared::{ServiceCode, ServiceContainer};
+use super::path::CipPath;
+use super::route::Route;
+use super::types::{CipByte, CipUdint, CipUint, CipUsint};
+use crate::error::{EipError, EipResult};
+
+pub const CONNECTION_MANAGER_CLASS_ID: CipUint = 0x06;
+
+// Largest connection size that fits in the standard network connection parameters
+pub const MAX_FORWARD_OPEN_CONNECTION_SIZE: CipUint = 511;
+
+#[bitsize(8)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u8::into)]
+#[bw(map = |&x| u8::from(x))]
+pub struct PriorityTimeTick {
+    // Each tick is 2^tick_time milliseconds
+    pub tick_time: u4,
+    pub high_priority: bool,
+    pub reserved: u3,
+}
+
+#[bitsize(2)]
+#[derive(Debug, FromBits, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum ConnectionPriority {
+    Low = 0x00,
+    High = 0x01,
+    Scheduled = 0x02,
+    Urgent = 0x03,
+}
+
+#[bitsize(2)]
+#[derive(Debug, FromBits, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum ConnectionType {
+    Null = 0x00,
+    Multicast = 0x01,
+    PointToPoint = 0x02,
+    Reserved = 0x03,
+}
+
+#[bitsize(16)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u16::into)]
+#[bw(map = |&x| u16::from(x))]
+pub struct NetworkConnectionParameters {
+    // Size of the connection data in bytes
+    pub connection_size: u9,
+    pub variable_size: bool,
+    pub priority: ConnectionPriority,
+    pub reserved: bool,
+    pub connection_type: ConnectionType,
+    pub redundant_owner: bool,
+}
+
+// Large_Forward_Open widens the connection size to 16 bits
+#[bitsize(32)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u32::into)]
+#[bw(map = |&x| u32::from(x))]
+pub struct LargeNetworkConnectionParameters {
+    pub connection_size: u16,
+    pub reserved: u9,
+    pub variable_size: bool,
+    pub priority: ConnectionPriority,
+    pub reserved: bool,
+    pub connection_type: ConnectionType,
+    pub redundant_owner: bool,
+}
+
+// ======= Start of LargeNetworkConnectionParameters impl ========
+
+impl From<NetworkConnectionParameters> for LargeNetworkConnectionParameters {
+    fn from(parameters: NetworkConnectionParameters) -> Self {
+        LargeNetworkConnectionParameters::new(
+            parameters.connection_size().value(),
+            parameters.variable_size(),
+            parameters.priority(),
+            parameters.connection_type(),
+            parameters.redundant_owner(),
+        )
+    }
+}
+
+impl LargeNetworkConnectionParameters {
+    /// Converts to the standard network connection parameters if the connection size fits
+    pub fn to_standard(&self) -> Option<NetworkConnectionParameters> {
+        if self.connection_size() > MAX_FORWARD_OPEN_CONNECTION_SIZE {
+            return None;
+        }
+
+        Some(NetworkConnectionParameters::new(
+            u9::new(self.connection_size()),
+            self.variable_size(),
+            self.priority(),
+            self.connection_type(),
+            self.redundant_owner(),
+        ))
+    }
+}
+
+// ^^^^^^^^ End of LargeNetworkConnectionParameters impl ^^^^^^^^
+
+#[bitsize(3)]
+#[derive(Debug, FromBits, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum ProductionTrigger {
+    Cyclic = 0x00,
+    ChangeOfState = 0x01,
+    ApplicationObject = 0x02,
+
+    #[fallback]
+    Reserved(u3),
+}
+
+#[bitsize(8)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u8::into)]
+#[bw(map = |&x| u8::from(x))]
+pub struct TransportClassTrigger {
+    pub transport_class: u4,
+    pub production_trigger: ProductionTrigger,
+    pub server: bool,
+}
+
+// The connection serial number, originator vendor ID and originator serial number together identify a connection
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ConnectionTriad {
+    pub connection_serial_number: CipUint,
+    pub originator_vendor_id: CipUint,
+    pub originator_serial_number: CipUdint,
+}
+
+fn connection_manager_error(
+    service: ServiceCode,
+    general_status: ResponseStatusCode,
+    additional_status: Vec<CipUint>,
+) -> EipError {
+    EipError::Cip {
+        service,
+        path: Some(CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1)),
+        general_status,
+        additional_status,
+    }
+}
+
+fn connection_path_word_size(connection_path: &CipPath) -> Result<CipUsint, String> {
+    CipUsint::try_from(connection_path.word_size()).map_err(|_| {
+        format!(
+            "path of {} words is too long for its size",
+            connection_path.word_size()
+        )
+    })
+}
+
+// The network connection parameters are 16 bits for Forward_Open and 32 bits for Large_Forward_Open
+#[binwrite]
+#[bw(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardOpenRequest<P = NetworkConnectionParameters>
+where
+    P: for<'a> BinWrite<Args<'a> = ()>,
+{
+    pub priority_time_tick: PriorityTimeTick,
+    pub timeout_ticks: CipUsint,
+    pub o2t_connection_id: CipUdint,
+    pub t2o_connection_id: CipUdint,
+    pub connection_triad: ConnectionTriad,
+    pub connection_timeout_multiplier: CipUsint,
+    #[bw(calc = [0x00; 3])]
+    _reserved: [CipByte; 3],
+    // Requested packet intervals are in microseconds
+    pub o2t_rpi: CipUdint,
+    pub o2t_network_connection_parameters: P,
+    pub t2o_rpi: CipUdint,
+    pub t2o_network_connection_parameters: P,
+    pub transport_class_trigger: TransportClassTrigger,
+    #[bw(try_calc = connection_path_word_size(connection_path))]
+    _connection_path_size: CipUsint,
+    pub connection_path: CipPath,
+}
+
+pub type LargeForwardOpenRequest = ForwardOpenRequest<LargeNetworkConnectionParameters>;
+
+// ======= Start of ForwardOpenRequest impl ========
+
+impl<P> ForwardOpenRequest<P>
+where
+    P: for<'a> BinWrite<Args<'a> = ()>,
+{
+    /// Routes the connection through a bridge by prefixing the connection path with the route
+    pub fn with_route(mut self, route: &Route) -> Self {
+        self.connection_path = self.connection_path.with_route(route);
+        self
+    }
+
+    /// Creates the Forward_Close request that closes the connection this request opens
+    pub fn close_request(&self) -> ForwardCloseRequest {
+        ForwardCloseRequest {
+            priority_time_tick: self.priority_time_tick,
+            timeout_ticks: self.timeout_ticks,
+            connection_triad: self.connection_triad,
+            connection_path: self.connection_path.clone(),
+        }
+    }
+}
+
+impl From<ForwardOpenRequest> for LargeForwardOpenRequest {
+    fn from(forward_open: ForwardOpenRequest) -> Self {
+        LargeForwardOpenRequest {
+            priority_time_tick: forward_open.priority_time_tick,
+            timeout_ticks: forward_open.timeout_ticks,
+            o2t_connection_id: forward_open.o2t_connection_id,
+            t2o_connection_id: forward_open.t2o_connection_id,
+            connection_triad: forward_open.connection_triad,
+            connection_timeout_multiplier: forward_open.connection_timeout_multiplier,
+            o2t_rpi: forward_open.o2t_rpi,
+            o2t_network_connection_parameters: forward_open
+                .o2t_network_connection_parameters
+                .into(),
+            t2o_rpi: forward_open.t2o_rpi,
+            t2o_network_connection_parameters: forward_open
+                .t2o_network_connection_parameters
+                .into(),
+            transport_class_trigger: forward_open.transport_class_trigger,
+            connection_path: forward_open.connection_path,
+        }
+    }
+}
+
+impl LargeForwardOpenRequest {
+    /// Converts to a standard Forward_Open request if both connection sizes fit
+    pub fn to_standard(&self) -> Option<ForwardOpenRequest> {
+        Some(ForwardOpenRequest {
+            priority_time_tick: self.priority_time_tick,
+            timeout_ticks: self.timeout_ticks,
+            o2t_connection_id: self.o2t_connection_id,
+            t2o_connection_id: self.t2o_connection_id,
+            connection_triad: self.connection_triad,
+            connection_timeout_multiplier: self.connection_timeout_multiplier,
+            o2t_rpi: self.o2t_rpi,
+            o2t_network_connection_parameters: self
+                .o2t_network_connection_parameters
+                .to_standard()?,
+            t2o_rpi: self.t2o_rpi,
+            t2o_network_connection_parameters: self
+                .t2o_network_connection_parameters
+                .to_standard()?,
+            transport_class_trigger: self.transport_class_trigger,
+            connection_path: self.connection_path.clone(),
+        })
+    }
+
+    /// The standard Forward_Open request to retry with when the target rejected this
+    ///  Large_Forward_Open as an unsupported service
+    pub fn fallback_request(&self, reply: &ForwardOpenReply) -> Option<ForwardOpenRequest> {
+        if !reply.is_service_not_supported() {
+            return None;
+        }
+
+        self.to_standard()
+    }
+}
+
+// ^^^^^^^^ End of ForwardOpenRequest impl ^^^^^^^^
+
+#[binwrite]
+#[bw(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardCloseRequest {
+    pub priority_time_tick: PriorityTimeTick,
+    pub timeout_ticks: CipUsint,
+    pub connection_triad: ConnectionTriad,
+    #[bw(try_calc = connection_path_word_size(connection_path))]
+    _connection_path_size: CipUsint,
+    #[bw(calc = 0x00)]
+    _reserved: CipByte,
+    pub connection_path: CipPath,
+}
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardOpenSuccess {
+    pub o2t_connection_id: CipUdint,
+    pub t2o_connection_id: CipUdint,
+    pub connection_triad: ConnectionTriad,
+    // Actual packet intervals are in microseconds
+    pub o2t_api: CipUdint,
+    pub t2o_api: CipUdint,
+
+    #[br(temp, pad_after = 1)]
+    application_reply_size: CipUsint,
+
+    #[br(count = application_reply_size as usize * 2)]
+    pub application_reply: Vec<CipByte>,
+}
+
+// A successful Forward_Open with the O->T and T->O socket address items the target can send next
+//  to the reply, e.g. the multicast address it produces the T->O data to
+#[derive(Debug, PartialEq)]
+pub struct OpenedConnection {
+    pub forward_open: ForwardOpenSuccess,
+    pub o2t_socket_address: Option<SocketAddrV4>,
+    pub t2o_socket_address: Option<SocketAddrV4>,
+}
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardCloseSuccess {
+    pub connection_triad: ConnectionTriad,
+
+    #[br(temp, pad_after = 1)]
+    application_reply_size: CipUsint,
+
+    #[br(count = application_reply_size as usize * 2)]
+    pub application_reply: Vec<CipByte>,
+}
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct ConnectionFailure {
+    pub connection_triad: ConnectionTriad,
+    #[br(pad_after = 1)]
+    pub remaining_path_size: CipUsint,
+}
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardOpenReply {
+    #[br(assert(service_container.response()))]
+    pub service_container: ServiceContainer,
+
+    #[br(pad_before = 1)]
+    pub general_status: ResponseStatusCode,
+
+    #[br(temp)]
+    additional_status_size: CipUsint,
+
+    #[br(count = additional_status_size)]
+    pub additional_status: Vec<CipUint>,
+
+    #[br(if(general_status.is_success()))]
+    pub success: Option<ForwardOpenSuccess>,
+
+    // Some failures (e.g. an unsupported service) don't include the failure data
+    #[br(if(!general_status.is_success()), try)]
+    pub failure: Option<ConnectionFailure>,
+}
+
+// ======= Start of ForwardOpenReply impl ========
+
+impl ForwardOpenReply {
+    /// The connection manager's extended status word, only present when the request failed
+    pub fn extended_status(&self) -> Option<CipUint> {
+        self.additional_status.first().copied()
+    }
+
+    pub fn is_service_not_supported(&self) -> bool {
+        self.general_status == ResponseStatusCode::ServiceNotSupported
+    }
+
+    /// Converts the reply into the opened connection, or the CIP error that rejected it
+    pub fn into_result(self) -> EipResult<ForwardOpenSuccess> {
+        match self.success {
+            Some(forward_open_success) if self.general_status.is_success() => {
+                Ok(forward_open_success)
+            }
+            _ => Err(connection_manager_error(
+                self.service_container.service(),
+                self.general_status,
+                self.additional_status,
+            )),
+        }
+    }
+}
+
+// ^^^^^^^^ End of ForwardOpenReply impl ^^^^^^^^
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct ForwardCloseReply {
+    #[br(assert(service_container.response()))]
+    pub service_container: ServiceContainer,
+
+    #[br(pad_before = 1)]
+    pub general_status: ResponseStatusCode,
+
+    #[br(temp)]
+    additional_status_size: CipUsint,
+
+    #[br(count = additional_status_size)]
+    pub additional_status: Vec<CipUint>,
+
+    #[br(if(general_status.is_success()))]
+    pub success: Option<ForwardCloseSuccess>,
+
+    #[br(if(!general_status.is_success()), try)]
+    pub failure: Option<ConnectionFailure>,
+}
+
+// ======= Start of ForwardCloseReply impl ========
+
+impl ForwardCloseReply {
+    /// The connection manager's extended status word, only present when the request failed
+    pub fn extended_status(&self) -> Option<CipUint> {
+        self.additional_status.first().copied()
+    }
+
+    /// Converts the reply into the closed connection, or the CIP error that rejected it
+    pub fn into_result(self) -> EipResult<ForwardCloseSuccess> {
+        match self.success {
+            Some(forward_close_success) if self.general_status.is_success() => {
+                Ok(forward_close_success)
+            }
+            _ => Err(connection_manager_error(
+                self.service_container.service(),
+                self.general_status,
+                self.additional_status,
+            )),
+        }
+    }
+}
+
+// ^^^^^^^^ End of ForwardCloseReply impl ^^^^^^^^
+
+// About 5 seconds (5 ticks of 1024ms) for a bridge to deliver an Unconnected_Send request
+pub const DEFAULT_UNCONNECTED_SEND_TICK_TIME: u8 = 0x0a;
+pub const DEFAULT_UNCONNECTED_SEND_TIMEOUT_TICKS: CipUsint = 0x05;
+
+// Carries a request through bridges (e.g. an Ethernet module into a chassis backplane) to the
+//  target at the end of the route path
+#[binwrite]
+#[bw(little)]
+#[derive(Debug, PartialEq)]
+pub struct UnconnectedSendRequest {
+    pub priority_time_tick: PriorityTimeTick,
+    pub timeout_ticks: CipUsint,
+    #[bw(calc = message_request.len() as CipUint)]
+    _message_request_size: CipUint,
+    // The embedded Message Router request, already serialized
+    pub message_request: Vec<CipByte>,
+    #[bw(calc = (message_request.len() % 2 == 1).then_some(0x00))]
+    _pad: Option<CipByte>,
+    #[bw(try_calc = connection_path_word_size(route_path))]
+    _route_path_size: CipUsint,
+    #[bw(calc = 0x00)]
+    _reserved: CipByte,
+    pub route_path: CipPath,
+}
+
+// ======= Start of UnconnectedSendRequest impl ========
+
+impl UnconnectedSendRequest {
+    pub fn new(route: &Route, message_request: Vec<CipByte>) -> Self {
+        UnconnectedSendRequest {
+            priority_time_tick: PriorityTimeTick::new(
+                u4::new(DEFAULT_UNCONNECTED_SEND_TICK_TIME),
+                false,
+            ),
+            timeout_ticks: DEFAULT_UNCONNECTED_SEND_TIMEOUT_TICKS,
+            message_request,
+            route_path: route.clone().into(),
+        }
+    }
+
+    /// Embeds a Message Router request addressed to the target at the end of the route
+    pub fn from_request<T>(
+        route: &Route,
+        message_request: &MessageRouterRequest<T>,
+    ) -> BinResult<Self>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        let mut message_request_bytes: Vec<CipByte> = Vec::new();
+        message_request.write(&mut std::io::Cursor::new(&mut message_request_bytes))?;
+
+        Ok(Self::new(route, message_request_bytes))
+    }
+}
+
+impl MessageRouterRequest<UnconnectedSendRequest> {
+    pub fn new_unconnected_send(unconnected_send_request: UnconnectedSendRequest) -> Self {
+        Self::new_data(
+            ServiceCode::UnconnectedSend,
+            CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            Some(unconnected_send_request),
+        )
+    }
+}
+
+// ^^^^^^^^ End of UnconnectedSendRequest impl ^^^^^^^^
+
+// A delivered Unconnected_Send request is answered by the target's own reply, so the Connection
+//  Manager only replies to Unconnected_Send when routing failed
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct UnconnectedSendErrorReply {
+    #[br(assert(service_container.response()))]
+    pub service_container: ServiceContainer,
+
+    #[br(pad_before = 1)]
+    pub general_status: ResponseStatusCode,
+
+    #[br(temp)]
+    additional_status_size: CipUsint,
+
+    #[br(count = additional_status_size)]
+    pub additional_status: Vec<CipUint>,
+
+    // Only sent for errors along the route
+    #[br(try)]
+    pub remaining_path_size: Option<CipUsint>,
+}
+
+// ======= Start of UnconnectedSendErrorReply impl ========
+
+impl UnconnectedSendErrorReply {
+    pub fn into_error(self) -> EipError {
+        EipError::Routing {
+            general_status: self.general_status,
+            additional_status: self.additional_status,
+            remaining_path_size: self.remaining_path_size,
+        }
+    }
+}
+
+// ^^^^^^^^ End of UnconnectedSendErrorReply impl ^^^^^^^^
+
+/// Fails with the routing error of an unconnected message, if it is a failed Unconnected_Send reply
+pub fn check_unconnected_send_status(message_bytes: &[CipByte]) -> EipResult<()> {
+    let unconnected_send_reply = ServiceContainer::new(ServiceCode::UnconnectedSend, true);
+
+    match message_bytes.first() {
+        Some(service_byte) if *service_byte == u8::from(unconnected_send_reply) => {
+            let error_reply =
+                UnconnectedSendErrorReply::read(&mut std::io::Cursor::new(message_bytes))?;
+
+            match error_reply.general_status.is_success() {
+                true => Ok(()),
+                false => Err(error_reply.into_error()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
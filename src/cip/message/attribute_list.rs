@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use binrw::meta::{ReadEndian, WriteEndian};
+use binrw::{binread, binrw, binwrite, BinRead, BinResult, BinWrite};
+
+use crate::cip::path::CipPath;
+use crate::cip::types::{
+    CipBool, CipByte, CipDint, CipDword, CipInt, CipLint, CipLreal, CipLword, CipReal, CipRevision,
+    CipShortString, CipSint, CipUdint, CipUint, CipUlint, CipUsint, CipWord,
+};
+use crate::error::{require_reply_data, EipResult};
+
+use super::request::MessageRouterRequest;
+use super::response::{MessageRouterResponse, ResponseStatusCode};
+use super::shared::ServiceCode;
+
+// The data type of an attribute, which decides how its value is laid out in a reply
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CipDataType {
+    Bool,
+    Sint,
+    Int,
+    Dint,
+    Lint,
+    Usint,
+    Uint,
+    Udint,
+    Ulint,
+    Real,
+    Lreal,
+    Byte,
+    Word,
+    Dword,
+    Lword,
+    ShortString,
+    Revision,
+    // Any other fixed size value (e.g. a structure), kept as its raw bytes
+    Bytes(usize),
+}
+
+// The data type of each attribute in a Get_Attribute_List reply, keyed by attribute ID
+pub type AttributeTypeMap = HashMap<CipUint, CipDataType>;
+
+#[derive(Debug, PartialEq)]
+pub enum AttributeValue {
+    Bool(CipBool),
+    Sint(CipSint),
+    Int(CipInt),
+    Dint(CipDint),
+    Lint(CipLint),
+    Usint(CipUsint),
+    Uint(CipUint),
+    Udint(CipUdint),
+    Ulint(CipUlint),
+    Real(CipReal),
+    Lreal(CipLreal),
+    Byte(CipByte),
+    Word(CipWord),
+    Dword(CipDword),
+    Lword(CipLword),
+    ShortString(CipShortString),
+    Revision(CipRevision),
+    Bytes(Vec<u8>),
+}
+
+// ======= Start of AttributeValue impl ========
+
+impl BinRead for AttributeValue {
+    type Args<'a> = CipDataType;
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        data_type: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let attribute_value = match data_type {
+            CipDataType::Bool => AttributeValue::Bool(CipBool::read_options(reader, endian, ())?),
+            CipDataType::Sint => AttributeValue::Sint(CipSint::read_options(reader, endian, ())?),
+            CipDataType::Int => AttributeValue::Int(CipInt::read_options(reader, endian, ())?),
+            CipDataType::Dint => AttributeValue::Dint(CipDint::read_options(reader, endian, ())?),
+            CipDataType::Lint => AttributeValue::Lint(CipLint::read_options(reader, endian, ())?),
+            CipDataType::Usint => {
+                AttributeValue::Usint(CipUsint::read_options(reader, endian, ())?)
+            }
+            CipDataType::Uint => AttributeValue::Uint(CipUint::read_options(reader, endian, ())?),
+            CipDataType::Udint => {
+                AttributeValue::Udint(CipUdint::read_options(reader, endian, ())?)
+            }
+            CipDataType::Ulint => {
+                AttributeValue::Ulint(CipUlint::read_options(reader, endian, ())?)
+            }
+            CipDataType::Real => AttributeValue::Real(CipReal::read_options(reader, endian, ())?),
+            CipDataType::Lreal => {
+                AttributeValue::Lreal(CipLreal::read_options(reader, endian, ())?)
+            }
+            CipDataType::Byte => AttributeValue::Byte(CipByte::read_options(reader, endian, ())?),
+            CipDataType::Word => AttributeValue::Word(CipWord::read_options(reader, endian, ())?),
+            CipDataType::Dword => {
+                AttributeValue::Dword(CipDword::read_options(reader, endian, ())?)
+            }
+            CipDataType::Lword => {
+                AttributeValue::Lword(CipLword::read_options(reader, endian, ())?)
+            }
+            CipDataType::ShortString => {
+                AttributeValue::ShortString(CipShortString::read_options(reader, endian, ())?)
+            }
+            CipDataType::Revision => {
+                AttributeValue::Revision(CipRevision::read_options(reader, endian, ())?)
+            }
+            CipDataType::Bytes(value_size) => AttributeValue::Bytes(Vec::<u8>::read_options(
+                reader,
+                endian,
+                binrw::VecArgs::builder().count(value_size).finalize(),
+            )?),
+        };
+
+        Ok(attribute_value)
+    }
+}
+
+impl WriteEndian for AttributeValue {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinWrite for AttributeValue {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match self {
+            AttributeValue::Bool(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Sint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Int(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Dint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Lint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Usint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Uint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Udint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Ulint(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Real(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Lreal(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Byte(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Word(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Dword(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Lword(value) => value.write_options(writer, endian, ()),
+            AttributeValue::ShortString(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Revision(value) => value.write_options(writer, endian, ()),
+            AttributeValue::Bytes(value) => value.write_options(writer, endian, ()),
+        }
+    }
+}
+
+// ^^^^^^^^ End of AttributeValue impl ^^^^^^^^
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+pub struct GetAttributeListRequest {
+    #[bw(calc = attribute_ids.len() as CipUint)]
+    pub _attribute_count: CipUint,
+
+    #[br(count = _attribute_count)]
+    pub attribute_ids: Vec<CipUint>,
+}
+
+impl MessageRouterRequest<GetAttributeListRequest> {
+    pub fn new_get_attribute_list(request_path: CipPath, attribute_ids: Vec<CipUint>) -> Self {
+        Self::new_data(
+            ServiceCode::GetAttributeList,
+            request_path,
+            Some(GetAttributeListRequest { attribute_ids }),
+        )
+    }
+}
+
+#[binwrite]
+#[bw(little)]
+#[derive(Debug, PartialEq)]
+pub struct AttributeSetting {
+    pub attribute_id: CipUint,
+    pub value: AttributeValue,
+}
+
+#[binwrite]
+#[bw(little)]
+#[derive(Debug, PartialEq)]
+pub struct SetAttributeListRequest {
+    #[bw(calc = attributes.len() as CipUint)]
+    pub _attribute_count: CipUint,
+
+    pub attributes: Vec<AttributeSetting>,
+}
+
+impl MessageRouterRequest<SetAttributeListRequest> {
+    pub fn new_set_attribute_list(
+        request_path: CipPath,
+        attributes: Vec<AttributeSetting>,
+    ) -> Self {
+        Self::new_data(
+            ServiceCode::SetAttributeList,
+            request_path,
+            Some(SetAttributeListRequest { attributes }),
+        )
+    }
+}
+
+// The status of a single attribute, which is a general status code widened to 16 bits
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AttributeStatusCode(pub CipUint);
+
+// ======= Start of AttributeStatusCode impl ========
+
+impl AttributeStatusCode {
+    pub fn is_success(&self) -> bool {
+        self.0 == 0x0
+    }
+}
+
+impl TryFrom<AttributeStatusCode> for ResponseStatusCode {
+    type Error = AttributeStatusCode;
+
+    // Codes above 0xff aren't general status codes
+    fn try_from(attribute_status: AttributeStatusCode) -> Result<Self, Self::Error> {
+        let general_status = u8::try_from(attribute_status.0).map_err(|_| attribute_status)?;
+
+        ResponseStatusCode::read(&mut std::io::Cursor::new([general_status]))
+            .map_err(|_| attribute_status)
+    }
+}
+
+// ^^^^^^^^ End of AttributeStatusCode impl ^^^^^^^^
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+pub struct AttributeStatus {
+    pub attribute_id: CipUint,
+    pub status: AttributeStatusCode,
+}
+
+// ======= Start of AttributeStatus impl ========
+
+impl AttributeStatus {
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+}
+
+// ^^^^^^^^ End of AttributeStatus impl ^^^^^^^^
+
+#[derive(Debug, PartialEq)]
+pub struct AttributeResponse {
+    pub attribute_id: CipUint,
+    pub status: AttributeStatusCode,
+    // Only sent for attributes that were read successfully
+    pub value: Option<AttributeValue>,
+}
+
+// ======= Start of AttributeResponse impl ========
+
+impl AttributeResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+}
+
+// ^^^^^^^^ End of AttributeResponse impl ^^^^^^^^
+
+// Get_Attribute_List reply data. The size of each value depends on its data type, so the replies
+//  are kept as bytes until they are decoded with an `AttributeTypeMap`
+#[derive(Debug, PartialEq)]
+pub struct GetAttributeListReply {
+    pub attribute_count: CipUint,
+    pub attribute_bytes: Vec<u8>,
+}
+
+// ======= Start of GetAttributeListReply impl ========
+
+impl GetAttributeListReply {
+    /// Decodes the status and value of every attribute in the reply
+    pub fn decode(&self, type_map: &AttributeTypeMap) -> BinResult<Vec<AttributeResponse>> {
+        let mut reader = std::io::Cursor::new(&self.attribute_bytes);
+        let mut attribute_responses = Vec::with_capacity(self.attribute_count as usize);
+
+        for _ in 0..self.attribute_count {
+            let attribute_status = AttributeStatus::read(&mut reader)?;
+
+            let value = match attribute_status.is_success() {
+                true => {
+                    let data_type =
+                        type_map
+                            .get(&attribute_status.attribute_id)
+                            .ok_or_else(|| binrw::Error::AssertFail {
+                                pos: reader.position(),
+                                message: format!(
+                                    "no data type for attribute {}",
+                                    attribute_status.attribute_id
+                                ),
+                            })?;
+
+                    Some(AttributeValue::read_options(
+                        &mut reader,
+                        binrw::Endian::Little,
+                        *data_type,
+                    )?)
+                }
+                false => None,
+            };
+
+            attribute_responses.push(AttributeResponse {
+                attribute_id: attribute_status.attribute_id,
+                status: attribute_status.status,
+                value,
+            });
+        }
+
+        Ok(attribute_responses)
+    }
+}
+
+impl ReadEndian for GetAttributeListReply {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinRead for GetAttributeListReply {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let attribute_count = CipUint::read_options(reader, endian, ())?;
+
+        // The attributes fill the rest of the message router data
+        let mut attribute_bytes = Vec::new();
+        if let Err(read_err) = reader.read_to_end(&mut attribute_bytes) {
+            return Err(binrw::Error::Io(read_err));
+        }
+
+        Ok(GetAttributeListReply {
+            attribute_count,
+            attribute_bytes,
+        })
+    }
+}
+
+// ^^^^^^^^ End of GetAttributeListReply impl ^^^^^^^^
+
+#[binread]
+#[br(little)]
+#[derive(Debug, PartialEq)]
+pub struct SetAttributeListReply {
+    #[br(temp)]
+    _attribute_count: CipUint,
+
+    #[br(count = _attribute_count)]
+    pub attribute_statuses: Vec<AttributeStatus>,
+}
+
+// A failed attribute only fails its own status, so the reply reports an Attribute List Error
+//  instead of Success
+impl MessageRouterResponse<GetAttributeListReply> {
+    pub fn into_get_attribute_list_reply(
+        self,
+        request_path: CipPath,
+    ) -> EipResult<GetAttributeListReply> {
+        require_reply_data(
+            self.into_partial_result(request_path, ResponseStatusCode::AttributeListError)?,
+        )
+    }
+}
+
+impl MessageRouterResponse<SetAttributeListReply> {
+    pub fn into_set_attribute_list_reply(
+        self,
+        request_path: CipPath,
+    ) -> EipResult<SetAttributeListReply> {
+        require_reply_data(
+            self.into_partial_result(request_path, ResponseStatusCode::AttributeListError)?,
+        )
+    }
+}
@@ -1,3 +1,5 @@
+pub mod attribute_list;
+pub mod multiple_service;
 pub mod request;
 pub mod response;
 pub mod shared;
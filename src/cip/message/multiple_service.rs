@@ -0,0 +1,235 @@
+use std::mem;
+
+use binrw::meta::{ReadEndian, WriteEndian};
+use binrw::{BinRead, BinResult, BinWrite};
+
+use crate::cip::path::CipPath;
+use crate::cip::types::CipUint;
+use crate::error::{require_reply_data, EipResult};
+
+use super::request::MessageRouterRequest;
+use super::response::{MessageRouterResponse, ResponseStatusCode};
+use super::shared::ServiceCode;
+
+pub const MESSAGE_ROUTER_CLASS_ID: CipUint = 0x02;
+
+// Multiple_Service_Packet request data: the service count, an offset table and the embedded
+//  requests. Every offset is counted from the start of the service count
+#[derive(Debug, PartialEq, Default)]
+pub struct MultipleServiceRequest {
+    // Each embedded request, already serialized
+    pub embedded_requests: Vec<Vec<u8>>,
+}
+
+// ======= Start of MultipleServiceRequest impl ========
+
+impl MultipleServiceRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_request<T>(&mut self, request: &MessageRouterRequest<T>) -> BinResult<&mut Self>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        let mut request_bytes: Vec<u8> = Vec::new();
+        request.write(&mut std::io::Cursor::new(&mut request_bytes))?;
+
+        self.embedded_requests.push(request_bytes);
+
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.embedded_requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.embedded_requests.is_empty()
+    }
+}
+
+impl WriteEndian for MultipleServiceRequest {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinWrite for MultipleServiceRequest {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let packet_start = writer.stream_position()?;
+
+        let service_count = CipUint::try_from(self.embedded_requests.len()).map_err(|_| {
+            binrw::Error::AssertFail {
+                pos: packet_start,
+                message: format!(
+                    "{} embedded requests don't fit in the service count",
+                    self.embedded_requests.len()
+                ),
+            }
+        })?;
+        service_count.write_options(writer, endian, ())?;
+
+        // The first request starts right after the count and the offset table
+        let mut request_offset = mem::size_of::<CipUint>() * (self.embedded_requests.len() + 1);
+
+        for request_bytes in &self.embedded_requests {
+            let embedded_offset =
+                CipUint::try_from(request_offset).map_err(|_| binrw::Error::AssertFail {
+                    pos: packet_start,
+                    message: format!(
+                        "embedded request offset {} is too large for the offset table",
+                        request_offset
+                    ),
+                })?;
+            embedded_offset.write_options(writer, endian, ())?;
+            request_offset += request_bytes.len();
+        }
+
+        for request_bytes in &self.embedded_requests {
+            if let Err(write_err) = writer.write(request_bytes) {
+                return Err(binrw::Error::Io(write_err));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of MultipleServiceRequest impl ^^^^^^^^
+
+impl MessageRouterRequest<MultipleServiceRequest> {
+    /// Multiple_Service_Packet requests are addressed to the Message Router object
+    pub fn new_multiple_service(multiple_service_request: MultipleServiceRequest) -> Self {
+        Self::new_data(
+            ServiceCode::MultipleServicePacket,
+            CipPath::new(MESSAGE_ROUTER_CLASS_ID, 0x1),
+            Some(multiple_service_request),
+        )
+    }
+}
+
+// Multiple_Service_Packet reply data, laid out like the request. The embedded replies are kept as
+//  bytes since every service can reply with a different data type
+#[derive(Debug, PartialEq)]
+pub struct MultipleServiceReply {
+    pub embedded_replies: Vec<Vec<u8>>,
+}
+
+// ======= Start of MultipleServiceReply impl ========
+
+impl MultipleServiceReply {
+    pub fn len(&self) -> usize {
+        self.embedded_replies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.embedded_replies.is_empty()
+    }
+
+    /// Parses the embedded reply to the request that was added at `index`
+    pub fn response<T>(&self, index: usize) -> BinResult<MessageRouterResponse<T>>
+    where
+        T: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let reply_bytes =
+            self.embedded_replies
+                .get(index)
+                .ok_or_else(|| binrw::Error::AssertFail {
+                    pos: 0,
+                    message: format!("no embedded reply at index {}", index),
+                })?;
+
+        MessageRouterResponse::<T>::read(&mut std::io::Cursor::new(reply_bytes))
+    }
+
+    /// Parses every embedded reply as the same data type
+    pub fn responses<T>(&self) -> BinResult<Vec<MessageRouterResponse<T>>>
+    where
+        T: for<'a> BinRead<Args<'a> = ()>,
+    {
+        (0..self.embedded_replies.len())
+            .map(|index| self.response(index))
+            .collect()
+    }
+}
+
+impl ReadEndian for MultipleServiceReply {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinRead for MultipleServiceReply {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let reply_start = reader.stream_position()?;
+
+        let reply_count = CipUint::read_options(reader, endian, ())? as usize;
+        let reply_offsets = Vec::<CipUint>::read_options(
+            reader,
+            endian,
+            binrw::VecArgs::builder().count(reply_count).finalize(),
+        )?;
+
+        // The replies fill the rest of the message router data
+        let mut reply_table_bytes = Vec::new();
+        if let Err(read_err) = reader.read_to_end(&mut reply_table_bytes) {
+            return Err(binrw::Error::Io(read_err));
+        }
+
+        let table_size = mem::size_of::<CipUint>() * (reply_count + 1);
+        let mut embedded_replies = Vec::with_capacity(reply_count);
+
+        for (reply_index, reply_offset) in reply_offsets.iter().enumerate() {
+            let reply_end = reply_offsets
+                .get(reply_index + 1)
+                .map(|next_offset| *next_offset as usize)
+                .unwrap_or(table_size + reply_table_bytes.len());
+
+            let reply_offset = *reply_offset as usize;
+
+            if reply_offset < table_size || reply_end < reply_offset {
+                return Err(binrw::Error::AssertFail {
+                    pos: reply_start,
+                    message: format!("invalid offset {:#06x} of embedded reply", reply_offset),
+                });
+            }
+
+            match reply_table_bytes.get(reply_offset - table_size..reply_end - table_size) {
+                Some(reply_bytes) => embedded_replies.push(reply_bytes.to_vec()),
+                None => {
+                    return Err(binrw::Error::AssertFail {
+                        pos: reply_start,
+                        message: format!("embedded reply {} runs past the packet", reply_index),
+                    })
+                }
+            }
+        }
+
+        Ok(MultipleServiceReply { embedded_replies })
+    }
+}
+
+// ^^^^^^^^ End of MultipleServiceReply impl ^^^^^^^^
+
+impl MessageRouterResponse<MultipleServiceReply> {
+    /// The embedded replies. A failed embedded service only fails its own reply, so the
+    ///  Multiple_Service_Packet itself reports an Embedded Service Error instead of Success
+    pub fn into_multiple_service_reply(
+        self,
+        request_path: CipPath,
+    ) -> EipResult<MultipleServiceReply> {
+        require_reply_data(
+            self.into_partial_result(request_path, ResponseStatusCode::EmbeddedServiceError)?,
+        )
+    }
+}
@@ -1,17 +1,161 @@
+use std::io::SeekFrom;
+
 use binrw::{
     binread,
+    binrw,   // #[binrw] attribute
     BinRead, // BinRead,  // trait for reading
+    BinResult,
 };
 
-use crate::cip::types::CipUsint;
+use crate::cip::path::CipPath;
+use crate::cip::types::{CipUint, CipUsint};
+use crate::error::{EipError, EipResult};
 
 use super::shared::ServiceContainer;
 
-#[derive(BinRead)]
-#[br(little, repr = CipUsint)]
+// General status codes from Appendix B of the CIP specification
+#[binrw]
+#[brw(little)]
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(u8)]
 pub enum ResponseStatusCode {
-    Success = 0x0000,
+    #[brw(magic = 0x00u8)]
+    Success,
+    #[brw(magic = 0x01u8)]
+    ConnectionFailure,
+    #[brw(magic = 0x02u8)]
+    ResourceUnavailable,
+    #[brw(magic = 0x03u8)]
+    InvalidParameterValue,
+    #[brw(magic = 0x04u8)]
+    PathSegmentError,
+    #[brw(magic = 0x05u8)]
+    PathDestinationUnknown,
+    #[brw(magic = 0x06u8)]
+    PartialTransfer,
+    #[brw(magic = 0x07u8)]
+    ConnectionLost,
+    #[brw(magic = 0x08u8)]
+    ServiceNotSupported,
+    #[brw(magic = 0x09u8)]
+    InvalidAttributeValue,
+    #[brw(magic = 0x0au8)]
+    AttributeListError,
+    #[brw(magic = 0x0bu8)]
+    AlreadyInRequestedMode,
+    #[brw(magic = 0x0cu8)]
+    ObjectStateConflict,
+    #[brw(magic = 0x0du8)]
+    ObjectAlreadyExists,
+    #[brw(magic = 0x0eu8)]
+    AttributeNotSettable,
+    #[brw(magic = 0x0fu8)]
+    PrivilegeViolation,
+    #[brw(magic = 0x10u8)]
+    DeviceStateConflict,
+    #[brw(magic = 0x11u8)]
+    ReplyDataTooLarge,
+    #[brw(magic = 0x12u8)]
+    FragmentationOfPrimitiveValue,
+    #[brw(magic = 0x13u8)]
+    NotEnoughData,
+    #[brw(magic = 0x14u8)]
+    AttributeNotSupported,
+    #[brw(magic = 0x15u8)]
+    TooMuchData,
+    #[brw(magic = 0x16u8)]
+    ObjectDoesNotExist,
+    #[brw(magic = 0x17u8)]
+    ServiceFragmentationSequenceNotInProgress,
+    #[brw(magic = 0x18u8)]
+    NoStoredAttributeData,
+    #[brw(magic = 0x19u8)]
+    StoreOperationFailure,
+    #[brw(magic = 0x1au8)]
+    RoutingFailureRequestTooLarge,
+    #[brw(magic = 0x1bu8)]
+    RoutingFailureResponseTooLarge,
+    #[brw(magic = 0x1cu8)]
+    MissingAttributeListEntryData,
+    #[brw(magic = 0x1du8)]
+    InvalidAttributeValueList,
+    #[brw(magic = 0x1eu8)]
+    EmbeddedServiceError,
+    #[brw(magic = 0x1fu8)]
+    VendorSpecificError,
+    #[brw(magic = 0x20u8)]
+    InvalidParameter,
+    #[brw(magic = 0x21u8)]
+    WriteOnceValueAlreadyWritten,
+    #[brw(magic = 0x22u8)]
+    InvalidReplyReceived,
+    #[brw(magic = 0x23u8)]
+    BufferOverflow,
+    #[brw(magic = 0x24u8)]
+    MessageFormatError,
+    #[brw(magic = 0x25u8)]
+    KeyFailureInPath,
+    #[brw(magic = 0x26u8)]
+    PathSizeInvalid,
+    #[brw(magic = 0x27u8)]
+    UnexpectedAttributeInList,
+    #[brw(magic = 0x28u8)]
+    InvalidMemberId,
+    #[brw(magic = 0x29u8)]
+    MemberNotSettable,
+    #[brw(magic = 0x2au8)]
+    Group2OnlyServerGeneralFailure,
+    #[brw(magic = 0x2bu8)]
+    UnknownModbusError,
+    #[brw(magic = 0x2cu8)]
+    AttributeNotGettable,
+    #[brw(magic = 0x2du8)]
+    InstanceNotDeletable,
+    #[brw(magic = 0x2eu8)]
+    ServiceNotSupportedForPath,
+    Unknown(CipUsint),
+}
+
+// ======= Start of ResponseStatusCode impl ========
+
+impl ResponseStatusCode {
+    pub fn is_success(&self) -> bool {
+        *self == ResponseStatusCode::Success
+    }
+
+    /// Statuses that are sent with reply data. Services that can partly fail report the result
+    ///  of each part in the reply data
+    pub fn carries_reply_data(&self) -> bool {
+        matches!(
+            self,
+            ResponseStatusCode::Success
+                | ResponseStatusCode::AttributeListError
+                | ResponseStatusCode::EmbeddedServiceError
+        )
+    }
+}
+
+// ^^^^^^^^ End of ResponseStatusCode impl ^^^^^^^^
+
+// Error replies may carry extra bytes (e.g. the remaining path size of a failed Unconnected_Send)
+//  that aren't the service's reply data, so they are never read as `T`
+fn read_reply_data<R, T>(
+    reader: &mut R,
+    endian: binrw::Endian,
+    (status,): (ResponseStatusCode,),
+) -> BinResult<Option<T>>
+where
+    R: std::io::Read + std::io::Seek,
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    let data_start = reader.stream_position()?;
+    let data_end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(data_start))?;
+
+    match status.carries_reply_data() && data_start < data_end {
+        true => Ok(Some(T::read_options(reader, endian, ())?)),
+        false => Ok(None),
+    }
 }
 
 #[binread]
@@ -25,10 +169,27 @@ where
     pub status: ResponseStatusCode,
     pub additional_status_size: u8,
 
-    #[br(try)]
+    #[br(count = additional_status_size)]
+    pub additional_status: Vec<CipUint>,
+
+    #[br(parse_with = read_reply_data, args(status))]
     pub data: Option<T>,
 }
 
+// ======= Start of ResponseData impl ========
+
+impl<T> ResponseData<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    /// The first additional status word, which holds the object specific extended status
+    pub fn extended_status(&self) -> Option<CipUint> {
+        self.additional_status.first().copied()
+    }
+}
+
+// ^^^^^^^^ End of ResponseData impl ^^^^^^^^
+
 #[binread]
 #[brw(little)]
 #[derive(Debug, PartialEq)]
@@ -40,3 +201,42 @@ where
     pub service_container: ServiceContainer,
     pub response_data: ResponseData<T>,
 }
+
+// ======= Start of MessageRouterResponse impl ========
+
+impl<T> MessageRouterResponse<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    /// Converts the response into its data, or the CIP error for the request sent to `request_path`
+    pub fn into_result(self, request_path: CipPath) -> EipResult<Option<T>> {
+        let response_data = self.response_data;
+
+        if !response_data.status.is_success() {
+            return Err(EipError::Cip {
+                service: self.service_container.service(),
+                path: Some(request_path),
+                general_status: response_data.status,
+                additional_status: response_data.additional_status,
+            });
+        }
+
+        Ok(response_data.data)
+    }
+
+    /// Like `into_result`, but services that can partly fail (e.g. Multiple_Service_Packet) report
+    ///  `partial_status` along with data that holds the status of each part
+    pub fn into_partial_result(
+        self,
+        request_path: CipPath,
+        partial_status: ResponseStatusCode,
+    ) -> EipResult<Option<T>> {
+        if self.response_data.status == partial_status && self.response_data.data.is_some() {
+            return Ok(self.response_data.data);
+        }
+
+        self.into_result(request_path)
+    }
+}
+
+// ^^^^^^^^ End of MessageRouterResponse impl ^^^^^^^^
@@ -3,7 +3,7 @@ use binrw::{BinRead, BinWrite};
 use bilge::prelude::{bitsize, u7, Bitsized, DebugBits, FromBits, Number};
 
 #[bitsize(7)]
-#[derive(FromBits, PartialEq, Debug)]
+#[derive(FromBits, PartialEq, Debug, Clone, Copy)]
 #[repr(u8)]
 pub enum ServiceCode {
     None = 0x00,
@@ -32,10 +32,34 @@ pub enum ServiceCode {
     RemoveMember = 0x1B,
     GroupSync = 0x1C, /* End CIP common services */
 
+    /* Start Connection Manager services */
+    ForwardClose = 0x4E,
+    UnconnectedSend = 0x52,
+    ForwardOpen = 0x54,
+    LargeForwardOpen = 0x5B, /* End Connection Manager services */
+
     #[fallback]
     Unknown(u7),
 }
 
+// ======= Start of ServiceCode impl ========
+
+impl ServiceCode {
+    /// Services that only read from the device, so resending them after a lost reply is safe
+    pub fn is_idempotent(&self) -> bool {
+        matches!(
+            self,
+            ServiceCode::GetAttributeAll
+                | ServiceCode::GetAttributeList
+                | ServiceCode::GetAttributeSingle
+                | ServiceCode::GetMember
+                | ServiceCode::NoOperation
+        )
+    }
+}
+
+// ^^^^^^^^ End of ServiceCode impl ^^^^^^^^
+
 #[bitsize(8)]
 #[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
 #[br(map = u8::into)]
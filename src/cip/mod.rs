@@ -1,5 +1,8 @@
 // Make the cip types public
+pub mod connection_manager;
 pub mod identity;
 pub mod message;
 pub mod path;
+pub mod route;
+pub mod symbol;
 pub mod types;
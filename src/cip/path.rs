@@ -1,34 +1,72 @@
+use std::fmt;
+use std::io::SeekFrom;
+use std::str::FromStr;
+
+use binrw::meta::{ReadEndian, WriteEndian};
 use binrw::{
     binrw,
     BinRead,
+    BinResult,
     BinWrite, // #[binrw] attribute
               // BinRead,  // trait for reading
               // BinWrite, // trait for writing
 };
 
 //  Tried to use Deku but that didn't support nested structs: https://github.com/sharksforarms/deku
-use bilge::prelude::{bitsize, u2, u3, Bitsized, DebugBits, FromBits, Number};
+use bilge::prelude::{bitsize, u2, u3, u7, Bitsized, DebugBits, FromBits, Number};
+
+use crate::cip::types::{CipUdint, CipUint, CipUsint};
+
+use super::route::Route;
+use super::symbol::SymbolicSegment;
+
+// Padded EPATHs (used by the Message Router and the Connection Manager) align every 16 and 32-bit
+//  value to a word, packed EPATHs leave the pad bytes out
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum EPathFormat {
+    #[default]
+    Padded,
+    Packed,
+}
 
 #[bitsize(3)]
 #[derive(Debug, Clone, FromBits, PartialEq)]
 #[repr(u8)]
 pub enum SegmentType {
+    PortSegment = 0x00,
     LogicalSegment = 0x01,
+    NetworkSegment = 0x02,
+    DataSegment = 0x04,
 
     #[fallback]
     Unknown(u3),
 }
 
+// ======= Start of SegmentType impl ========
+
+impl SegmentType {
+    // The segment type is the top 3 bits of the first byte of every segment
+    fn from_segment_byte(segment_byte: u8) -> Self {
+        SegmentType::from(u3::new(segment_byte >> 5))
+    }
+}
+
+// ^^^^^^^^ End of SegmentType impl ^^^^^^^^
+
 #[bitsize(3)]
 #[derive(Debug, Clone, FromBits, PartialEq)]
 #[repr(u8)]
 pub enum LogicalSegmentType {
     ClassId = 0x00,
     InstanceId = 0x01,
+    MemberId = 0x02,
+    ConnectionPoint = 0x03,
     AttributeId = 0x04,
-
-    #[fallback]
-    Unknown(u3),
+    // Special segments carry their own format, the only one defined is the electronic key
+    Special = 0x05,
+    // Always 8-bit
+    ServiceId = 0x06,
+    Reserved = 0x07,
 }
 
 #[bitsize(2)]
@@ -37,9 +75,8 @@ pub enum LogicalSegmentType {
 pub enum LogicalSegmentFormat {
     FormatAsU8 = 0x00,
     FormatAsU16 = 0x01,
-
-    #[fallback]
-    Unknown(u2),
+    FormatAsU32 = 0x02,
+    Reserved = 0x03,
 }
 
 #[bitsize(8)]
@@ -60,7 +97,7 @@ pub struct LogicalPathDefinition {
 // #[bw(map = |&x| u32::from(x))]
 
 #[binrw]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 #[br(import(segment_format: LogicalSegmentFormat))]
 pub enum PathData {
     #[br(pre_assert(segment_format == LogicalSegmentFormat::FormatAsU8))]
@@ -68,16 +105,45 @@ pub enum PathData {
 
     #[br(pre_assert(segment_format == LogicalSegmentFormat::FormatAsU16))]
     FormatAsU16(u16),
+
+    #[br(pre_assert(segment_format == LogicalSegmentFormat::FormatAsU32))]
+    FormatAsU32(u32),
+}
+
+// ======= Start of PathData impl ========
+
+impl PathData {
+    pub fn value(&self) -> u32 {
+        match self {
+            PathData::FormatAsU8(value) => *value as u32,
+            PathData::FormatAsU16(value) => *value as u32,
+            PathData::FormatAsU32(value) => *value,
+        }
+    }
+}
+
+// ^^^^^^^^ End of PathData impl ^^^^^^^^
+
+fn logical_padding(
+    path_format: EPathFormat,
+    logical_segment_format: LogicalSegmentFormat,
+) -> Option<u8> {
+    match (path_format, logical_segment_format) {
+        (EPathFormat::Padded, LogicalSegmentFormat::FormatAsU16)
+        | (EPathFormat::Padded, LogicalSegmentFormat::FormatAsU32) => Some(0x0),
+        _ => None,
+    }
 }
 
 #[binrw]
-#[brw(little)]
-#[derive(Debug, PartialEq)]
+#[brw(little, import(path_format: EPathFormat))]
+#[derive(Debug, PartialEq, Clone)]
 pub struct LogicalPathSegment {
     pub path_definition: LogicalPathDefinition,
 
-    #[br(if (path_definition.logical_segment_format() == LogicalSegmentFormat::FormatAsU16))]
-    pub u16_padding: Option<u8>,
+    #[br(temp, if(logical_padding(path_format, path_definition.logical_segment_format()).is_some()))]
+    #[bw(calc = logical_padding(path_format, path_definition.logical_segment_format()))]
+    _padding: Option<u8>,
 
     #[br(args(path_definition.logical_segment_format(),))]
     pub data: PathData,
@@ -86,73 +152,1246 @@ pub struct LogicalPathSegment {
 // ======= Start of LogicalPathSegment impl ========
 
 impl LogicalPathSegment {
-    pub fn new_u8(logical_segment_type: LogicalSegmentType, data: u8) -> Self {
+    fn new_data(
+        logical_segment_format: LogicalSegmentFormat,
+        logical_segment_type: LogicalSegmentType,
+        data: PathData,
+    ) -> Self {
         LogicalPathSegment {
             path_definition: LogicalPathDefinition::new(
-                LogicalSegmentFormat::FormatAsU8,
+                logical_segment_format,
                 logical_segment_type,
                 SegmentType::LogicalSegment,
             ),
-            u16_padding: None,
-            data: PathData::FormatAsU8(data),
+            data,
         }
     }
 
-    pub fn new_u16(logical_segment_type: LogicalSegmentType, data: u16) -> Self {
-        LogicalPathSegment {
-            path_definition: LogicalPathDefinition::new(
-                LogicalSegmentFormat::FormatAsU16,
-                logical_segment_type,
-                SegmentType::LogicalSegment,
-            ),
-            u16_padding: Some(0x0),
-            data: PathData::FormatAsU16(data),
+    /// Uses the smallest format that fits the value
+    pub fn new(logical_segment_type: LogicalSegmentType, value: u32) -> Self {
+        match value {
+            0..=0xff => Self::new_u8(logical_segment_type, value as u8),
+            0x100..=0xffff => Self::new_u16(logical_segment_type, value as u16),
+            _ => Self::new_u32(logical_segment_type, value),
         }
     }
+
+    pub fn new_u8(logical_segment_type: LogicalSegmentType, data: u8) -> Self {
+        Self::new_data(
+            LogicalSegmentFormat::FormatAsU8,
+            logical_segment_type,
+            PathData::FormatAsU8(data),
+        )
+    }
+
+    pub fn new_u16(logical_segment_type: LogicalSegmentType, data: u16) -> Self {
+        Self::new_data(
+            LogicalSegmentFormat::FormatAsU16,
+            logical_segment_type,
+            PathData::FormatAsU16(data),
+        )
+    }
+
+    pub fn new_u32(logical_segment_type: LogicalSegmentType, data: u32) -> Self {
+        Self::new_data(
+            LogicalSegmentFormat::FormatAsU32,
+            logical_segment_type,
+            PathData::FormatAsU32(data),
+        )
+    }
+
+    pub fn logical_segment_type(&self) -> LogicalSegmentType {
+        self.path_definition.logical_segment_type()
+    }
+
+    pub fn value(&self) -> u32 {
+        self.data.value()
+    }
+
+    pub fn byte_size(&self, path_format: EPathFormat) -> usize {
+        let data_size = match self.data {
+            PathData::FormatAsU8(_) => 1,
+            PathData::FormatAsU16(_) => 2,
+            PathData::FormatAsU32(_) => 4,
+        };
+        let padding_size =
+            logical_padding(path_format, self.path_definition.logical_segment_format()).is_some();
+
+        1 + padding_size as usize + data_size
+    }
 }
 
-// ^^^^^^^^ End of CipPath impl ^^^^^^^^
+// ^^^^^^^^ End of LogicalPathSegment impl ^^^^^^^^
+
+#[bitsize(8)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u8::into)]
+#[bw(map = |&x| u8::from(x))]
+pub struct KeyMajorRevision {
+    pub major_revision: u7,
+    // Set when any compatible revision is accepted, not only an exact match
+    pub compatibility: bool,
+}
 
+// The special logical segment (0x34) with key format 4, checked by the target before it
+//  accepts a connection. Zero matches any value
 #[binrw]
-#[brw(little)]
-#[derive(Debug, PartialEq)]
-// #[br(import(path_length: u8))]
-pub struct CipPath {
-    pub class_id_segment: LogicalPathSegment,
-    pub instance_id_segment: LogicalPathSegment,
+#[brw(little, magic = b"\x34\x04")]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElectronicKeySegment {
+    pub vendor_id: CipUint,
+    pub device_type: CipUint,
+    pub product_code: CipUint,
+    pub major_revision: KeyMajorRevision,
+    pub minor_revision: CipUsint,
+}
+
+// ======= Start of ElectronicKeySegment impl ========
+
+impl ElectronicKeySegment {
+    pub fn new(
+        vendor_id: CipUint,
+        device_type: CipUint,
+        product_code: CipUint,
+        major_revision: u8,
+        minor_revision: CipUsint,
+        compatibility: bool,
+    ) -> Self {
+        ElectronicKeySegment {
+            vendor_id,
+            device_type,
+            product_code,
+            major_revision: KeyMajorRevision::new(u7::new(major_revision & 0x7f), compatibility),
+            minor_revision,
+        }
+    }
+
+    // The segment and key format bytes and 8 bytes of key data, in either path format
+    pub fn byte_size(&self) -> usize {
+        10
+    }
+}
+
+// ^^^^^^^^ End of ElectronicKeySegment impl ^^^^^^^^
 
-    // #[br(if(path_length == 3))]
-    #[br(try)]
-    pub attribute_id_segment: Option<LogicalPathSegment>,
+// Service IDs are only defined as 8-bit, and special segments aren't plain logical values
+fn check_logical_segment(logical_segment: &LogicalPathSegment, pos: u64) -> BinResult<()> {
+    match (
+        logical_segment.logical_segment_type(),
+        logical_segment.path_definition.logical_segment_format(),
+    ) {
+        (LogicalSegmentType::Special, _) => Err(binrw::Error::AssertFail {
+            pos,
+            message: "a special logical segment isn't a logical value".to_string(),
+        }),
+        (LogicalSegmentType::ServiceId, LogicalSegmentFormat::FormatAsU8) => Ok(()),
+        (LogicalSegmentType::ServiceId, logical_segment_format) => Err(binrw::Error::AssertFail {
+            pos,
+            message: format!("service ID segment in {:?}", logical_segment_format),
+        }),
+        _ => Ok(()),
+    }
+}
+
+const PORT_IDENTIFIER_MASK: u8 = 0x0f;
+const EXTENDED_LINK_ADDRESS_FLAG: u8 = 0x10;
+// A port identifier of 15 means the port number follows as a 16-bit value
+const EXTENDED_PORT_IDENTIFIER: u8 = 0x0f;
+// The size of an extended link address is a single byte
+const MAX_LINK_ADDRESS_SIZE: usize = u8::MAX as usize;
+
+// Routes a path out of a device port to the device at the link address. Link addresses that aren't
+//  a single byte (e.g. an IP address string) and ports above 14 use the extended forms
+#[derive(Debug, PartialEq, Clone)]
+pub struct PortSegment {
+    pub port: CipUint,
+    pub link_address: Vec<u8>,
+}
+
+// ======= Start of PortSegment impl ========
+
+impl PortSegment {
+    pub fn new(port: CipUint, link_address: u8) -> Self {
+        PortSegment {
+            port,
+            link_address: vec![link_address],
+        }
+    }
+
+    pub fn new_extended(port: CipUint, link_address: Vec<u8>) -> Result<Self, PathParseError> {
+        if link_address.len() > MAX_LINK_ADDRESS_SIZE {
+            return Err(PathParseError::new(
+                &String::from_utf8_lossy(&link_address),
+                "link address is longer than 255 bytes",
+            ));
+        }
+
+        Ok(PortSegment { port, link_address })
+    }
+
+    fn has_extended_link_address(&self) -> bool {
+        self.link_address.len() != 1
+    }
+
+    fn has_extended_port(&self) -> bool {
+        self.port >= EXTENDED_PORT_IDENTIFIER as CipUint
+    }
+
+    fn unpadded_size(&self) -> usize {
+        1 + self.has_extended_link_address() as usize
+            + 2 * self.has_extended_port() as usize
+            + self.link_address.len()
+    }
+
+    /// Port segments are padded to an even length in both path formats
+    pub fn byte_size(&self) -> usize {
+        self.unpadded_size() + self.unpadded_size() % 2
+    }
+}
+
+impl BinRead for PortSegment {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let segment_start = reader.stream_position()?;
+        let segment_byte = u8::read_options(reader, endian, ())?;
+
+        let link_address_size = match segment_byte & EXTENDED_LINK_ADDRESS_FLAG {
+            0 => 1,
+            _ => u8::read_options(reader, endian, ())? as usize,
+        };
+
+        let port = match segment_byte & PORT_IDENTIFIER_MASK {
+            EXTENDED_PORT_IDENTIFIER => CipUint::read_options(reader, endian, ())?,
+            port_identifier => port_identifier as CipUint,
+        };
+
+        let link_address = Vec::<u8>::read_options(
+            reader,
+            endian,
+            binrw::VecArgs::builder()
+                .count(link_address_size)
+                .finalize(),
+        )?;
+
+        if !(reader.stream_position()? - segment_start).is_multiple_of(2) {
+            u8::read_options(reader, endian, ())?;
+        }
+
+        Ok(PortSegment { port, link_address })
+    }
+}
+
+impl BinWrite for PortSegment {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        if self.link_address.len() > MAX_LINK_ADDRESS_SIZE {
+            return Err(binrw::Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: format!(
+                    "link address of {} bytes doesn't fit in a port segment",
+                    self.link_address.len()
+                ),
+            });
+        }
+
+        let mut segment_byte = match self.has_extended_port() {
+            true => EXTENDED_PORT_IDENTIFIER,
+            false => self.port as u8,
+        };
+
+        if self.has_extended_link_address() {
+            segment_byte |= EXTENDED_LINK_ADDRESS_FLAG;
+        }
+
+        segment_byte.write_options(writer, endian, ())?;
+
+        if self.has_extended_link_address() {
+            (self.link_address.len() as u8).write_options(writer, endian, ())?;
+        }
+
+        if self.has_extended_port() {
+            self.port.write_options(writer, endian, ())?;
+        }
+
+        self.link_address.write_options(writer, endian, ())?;
+
+        if !self.unpadded_size().is_multiple_of(2) {
+            0x0u8.write_options(writer, endian, ())?;
+        }
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of PortSegment impl ^^^^^^^^
+
+const NETWORK_SEGMENT_TYPE_BITS: u8 = 0x40;
+const NETWORK_SUBTYPE_MASK: u8 = 0x1f;
+// Subtypes from 0x10 up carry a word count before their data
+const NETWORK_SIZED_SUBTYPE: u8 = 0x10;
+
+// Connection parameters that are carried in a connection path
+#[derive(Debug, PartialEq, Clone)]
+pub enum NetworkSegment {
+    Schedule(CipUsint),
+    FixedTag(CipUsint),
+    // In milliseconds
+    ProductionInhibitTime(CipUsint),
+    // The safety segment data is kept as its raw bytes
+    Safety(Vec<u8>),
+    ProductionInhibitTimeMicroseconds(CipUdint),
+}
+
+// ======= Start of NetworkSegment impl ========
+
+impl NetworkSegment {
+    fn subtype(&self) -> u8 {
+        match self {
+            NetworkSegment::Schedule(_) => 0x01,
+            NetworkSegment::FixedTag(_) => 0x02,
+            NetworkSegment::ProductionInhibitTime(_) => 0x03,
+            NetworkSegment::Safety(_) => 0x10,
+            NetworkSegment::ProductionInhibitTimeMicroseconds(_) => 0x11,
+        }
+    }
+
+    fn data_word_size(&self) -> usize {
+        match self {
+            NetworkSegment::Safety(safety_data) => safety_data.len().div_ceil(2),
+            NetworkSegment::ProductionInhibitTimeMicroseconds(_) => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn byte_size(&self) -> usize {
+        match self.subtype() < NETWORK_SIZED_SUBTYPE {
+            true => 2,
+            false => 2 + 2 * self.data_word_size(),
+        }
+    }
+}
+
+impl BinRead for NetworkSegment {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let segment_start = reader.stream_position()?;
+        let subtype = u8::read_options(reader, endian, ())? & NETWORK_SUBTYPE_MASK;
+
+        let network_segment = match subtype {
+            0x01 => NetworkSegment::Schedule(CipUsint::read_options(reader, endian, ())?),
+            0x02 => NetworkSegment::FixedTag(CipUsint::read_options(reader, endian, ())?),
+            0x03 => {
+                NetworkSegment::ProductionInhibitTime(CipUsint::read_options(reader, endian, ())?)
+            }
+            0x10 => {
+                let data_word_size = u8::read_options(reader, endian, ())? as usize;
+
+                NetworkSegment::Safety(Vec::<u8>::read_options(
+                    reader,
+                    endian,
+                    binrw::VecArgs::builder()
+                        .count(2 * data_word_size)
+                        .finalize(),
+                )?)
+            }
+            0x11 => {
+                let data_word_size = u8::read_options(reader, endian, ())?;
+                if data_word_size != 2 {
+                    return Err(binrw::Error::AssertFail {
+                        pos: segment_start,
+                        message: format!(
+                            "production inhibit time segment of {} words",
+                            data_word_size
+                        ),
+                    });
+                }
+
+                NetworkSegment::ProductionInhibitTimeMicroseconds(CipUdint::read_options(
+                    reader,
+                    endian,
+                    (),
+                )?)
+            }
+            _ => {
+                return Err(binrw::Error::AssertFail {
+                    pos: segment_start,
+                    message: format!("unknown network segment subtype {:#04x}", subtype),
+                })
+            }
+        };
+
+        Ok(network_segment)
+    }
+}
+
+impl BinWrite for NetworkSegment {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        if self.data_word_size() > u8::MAX as usize {
+            return Err(binrw::Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: format!(
+                    "network segment data of {} words doesn't fit in its size",
+                    self.data_word_size()
+                ),
+            });
+        }
+
+        (NETWORK_SEGMENT_TYPE_BITS | self.subtype()).write_options(writer, endian, ())?;
+
+        if self.subtype() >= NETWORK_SIZED_SUBTYPE {
+            (self.data_word_size() as u8).write_options(writer, endian, ())?;
+        }
+
+        match self {
+            NetworkSegment::Schedule(value)
+            | NetworkSegment::FixedTag(value)
+            | NetworkSegment::ProductionInhibitTime(value) => {
+                value.write_options(writer, endian, ())
+            }
+            NetworkSegment::Safety(safety_data) => {
+                safety_data.write_options(writer, endian, ())?;
+
+                // The data is sized in words
+                if !safety_data.len().is_multiple_of(2) {
+                    0x0u8.write_options(writer, endian, ())?;
+                }
+
+                Ok(())
+            }
+            NetworkSegment::ProductionInhibitTimeMicroseconds(value) => {
+                value.write_options(writer, endian, ())
+            }
+        }
+    }
+}
+
+// ^^^^^^^^ End of NetworkSegment impl ^^^^^^^^
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PathSegment {
+    Port(PortSegment),
+    Logical(LogicalPathSegment),
+    ElectronicKey(ElectronicKeySegment),
+    Network(NetworkSegment),
+    Symbolic(SymbolicSegment),
+}
+
+// ======= Start of PathSegment impl ========
+
+impl PathSegment {
+    pub fn byte_size(&self, path_format: EPathFormat) -> usize {
+        match self {
+            PathSegment::Port(port_segment) => port_segment.byte_size(),
+            PathSegment::Logical(logical_segment) => logical_segment.byte_size(path_format),
+            PathSegment::ElectronicKey(electronic_key) => electronic_key.byte_size(),
+            PathSegment::Network(network_segment) => network_segment.byte_size(),
+            PathSegment::Symbolic(symbolic_segment) => symbolic_segment.byte_size(),
+        }
+    }
+}
+
+impl From<PortSegment> for PathSegment {
+    fn from(port_segment: PortSegment) -> Self {
+        PathSegment::Port(port_segment)
+    }
+}
+
+impl From<LogicalPathSegment> for PathSegment {
+    fn from(logical_segment: LogicalPathSegment) -> Self {
+        PathSegment::Logical(logical_segment)
+    }
+}
+
+impl From<ElectronicKeySegment> for PathSegment {
+    fn from(electronic_key: ElectronicKeySegment) -> Self {
+        PathSegment::ElectronicKey(electronic_key)
+    }
+}
+
+impl From<NetworkSegment> for PathSegment {
+    fn from(network_segment: NetworkSegment) -> Self {
+        PathSegment::Network(network_segment)
+    }
+}
+
+impl From<SymbolicSegment> for PathSegment {
+    fn from(symbolic_segment: SymbolicSegment) -> Self {
+        PathSegment::Symbolic(symbolic_segment)
+    }
+}
+
+impl BinRead for PathSegment {
+    type Args<'a> = EPathFormat;
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        path_format: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // Peek at the segment type without consuming it
+        let segment_start = reader.stream_position()?;
+        let segment_byte = u8::read_options(reader, endian, ())?;
+        reader.seek(SeekFrom::Start(segment_start))?;
+
+        let path_segment = match SegmentType::from_segment_byte(segment_byte) {
+            SegmentType::PortSegment => {
+                PathSegment::Port(PortSegment::read_options(reader, endian, ())?)
+            }
+            SegmentType::LogicalSegment
+                if LogicalPathDefinition::from(segment_byte).logical_segment_type()
+                    == LogicalSegmentType::Special =>
+            {
+                PathSegment::ElectronicKey(ElectronicKeySegment::read_options(reader, endian, ())?)
+            }
+            SegmentType::LogicalSegment => {
+                let logical_segment =
+                    LogicalPathSegment::read_options(reader, endian, (path_format,))?;
+                check_logical_segment(&logical_segment, segment_start)?;
+
+                PathSegment::Logical(logical_segment)
+            }
+            SegmentType::NetworkSegment => {
+                PathSegment::Network(NetworkSegment::read_options(reader, endian, ())?)
+            }
+            SegmentType::DataSegment => {
+                PathSegment::Symbolic(SymbolicSegment::read_options(reader, endian, ())?)
+            }
+            SegmentType::Unknown(segment_type) => {
+                return Err(binrw::Error::AssertFail {
+                    pos: segment_start,
+                    message: format!("unsupported path segment type {}", segment_type),
+                })
+            }
+        };
+
+        Ok(path_segment)
+    }
+}
+
+impl BinWrite for PathSegment {
+    type Args<'a> = EPathFormat;
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        path_format: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match self {
+            PathSegment::Port(port_segment) => port_segment.write_options(writer, endian, ()),
+            PathSegment::Logical(logical_segment) => {
+                check_logical_segment(logical_segment, writer.stream_position()?)?;
+                logical_segment.write_options(writer, endian, (path_format,))
+            }
+            PathSegment::ElectronicKey(electronic_key) => {
+                electronic_key.write_options(writer, endian, ())
+            }
+            PathSegment::Network(network_segment) => {
+                network_segment.write_options(writer, endian, ())
+            }
+            PathSegment::Symbolic(symbolic_segment) => {
+                symbolic_segment.write_options(writer, endian, ())
+            }
+        }
+    }
+}
+
+// ^^^^^^^^ End of PathSegment impl ^^^^^^^^
+
+// An EPATH of any number of segments. `BinRead` and `BinWrite` use the padded format
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CipPath {
+    pub segments: Vec<PathSegment>,
 }
 
 // ======= Start of CipPath impl ========
 
 impl CipPath {
     pub fn new(class_id: u16, instance_id: u16) -> Self {
-        CipPath {
-            class_id_segment: LogicalPathSegment::new_u16(LogicalSegmentType::ClassId, class_id),
-            instance_id_segment: LogicalPathSegment::new_u16(
+        CipPath::default()
+            .with_segment(LogicalPathSegment::new_u16(
+                LogicalSegmentType::ClassId,
+                class_id,
+            ))
+            .with_segment(LogicalPathSegment::new_u16(
                 LogicalSegmentType::InstanceId,
                 instance_id,
-            ),
-            attribute_id_segment: None,
-        }
+            ))
     }
 
     pub fn new_full(class_id: u8, instance_id: u8, attribute_id: u8) -> Self {
-        CipPath {
-            class_id_segment: LogicalPathSegment::new_u8(LogicalSegmentType::ClassId, class_id),
-            instance_id_segment: LogicalPathSegment::new_u8(
+        CipPath::default()
+            .with_segment(LogicalPathSegment::new_u8(
+                LogicalSegmentType::ClassId,
+                class_id,
+            ))
+            .with_segment(LogicalPathSegment::new_u8(
                 LogicalSegmentType::InstanceId,
                 instance_id,
-            ),
-            attribute_id_segment: Some(LogicalPathSegment::new_u8(
+            ))
+            .with_segment(LogicalPathSegment::new_u8(
                 LogicalSegmentType::AttributeId,
                 attribute_id,
+            ))
+    }
+
+    pub fn with_segment<S: Into<PathSegment>>(mut self, path_segment: S) -> Self {
+        self.segments.push(path_segment.into());
+        self
+    }
+
+    /// Prefixes the path with the port segments of the route, e.g. to reach a module in a chassis
+    pub fn with_route(mut self, route: &Route) -> Self {
+        let route_segments = route.port_segments.iter().cloned().map(PathSegment::from);
+        self.segments.splice(0..0, route_segments);
+        self
+    }
+
+    /// The port segments at the start of the path
+    pub fn route(&self) -> Route {
+        let port_segments = self
+            .segments
+            .iter()
+            .map_while(|path_segment| match path_segment {
+                PathSegment::Port(port_segment) => Some(port_segment.clone()),
+                _ => None,
+            })
+            .collect();
+
+        Route { port_segments }
+    }
+
+    /// The path after the route, addressed from the device at the end of the route
+    pub fn target(&self) -> CipPath {
+        let segments = self
+            .segments
+            .iter()
+            .skip_while(|path_segment| matches!(path_segment, PathSegment::Port(_)))
+            .cloned()
+            .collect();
+
+        CipPath { segments }
+    }
+
+    pub fn with_port(self, port: CipUint, link_address: u8) -> Self {
+        self.with_segment(PortSegment::new(port, link_address))
+    }
+
+    pub fn with_class(self, class_id: u32) -> Self {
+        self.with_segment(LogicalPathSegment::new(
+            LogicalSegmentType::ClassId,
+            class_id,
+        ))
+    }
+
+    pub fn with_instance(self, instance_id: u32) -> Self {
+        self.with_segment(LogicalPathSegment::new(
+            LogicalSegmentType::InstanceId,
+            instance_id,
+        ))
+    }
+
+    pub fn with_member(self, member_id: u32) -> Self {
+        self.with_segment(LogicalPathSegment::new(
+            LogicalSegmentType::MemberId,
+            member_id,
+        ))
+    }
+
+    pub fn with_connection_point(self, connection_point: u32) -> Self {
+        self.with_segment(LogicalPathSegment::new(
+            LogicalSegmentType::ConnectionPoint,
+            connection_point,
+        ))
+    }
+
+    pub fn with_attribute(self, attribute_id: u32) -> Self {
+        self.with_segment(LogicalPathSegment::new(
+            LogicalSegmentType::AttributeId,
+            attribute_id,
+        ))
+    }
+
+    /// The value of the first logical segment of the given type
+    pub fn logical_value(&self, logical_segment_type: LogicalSegmentType) -> Option<u32> {
+        self.segments
+            .iter()
+            .find_map(|path_segment| match path_segment {
+                PathSegment::Logical(logical_segment)
+                    if logical_segment.logical_segment_type() == logical_segment_type =>
+                {
+                    Some(logical_segment.value())
+                }
+                _ => None,
+            })
+    }
+
+    pub fn class_id(&self) -> Option<u32> {
+        self.logical_value(LogicalSegmentType::ClassId)
+    }
+
+    pub fn instance_id(&self) -> Option<u32> {
+        self.logical_value(LogicalSegmentType::InstanceId)
+    }
+
+    pub fn attribute_id(&self) -> Option<u32> {
+        self.logical_value(LogicalSegmentType::AttributeId)
+    }
+
+    pub fn byte_size(&self, path_format: EPathFormat) -> usize {
+        self.segments
+            .iter()
+            .map(|path_segment| path_segment.byte_size(path_format))
+            .sum()
+    }
+
+    /// The size of the padded path in 16-bit words, as sent before a request or connection path
+    pub fn word_size(&self) -> usize {
+        self.byte_size(EPathFormat::Padded) / 2
+    }
+
+    pub fn write_format<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        path_format: EPathFormat,
+    ) -> BinResult<()> {
+        for path_segment in &self.segments {
+            path_segment.write_options(writer, binrw::Endian::Little, path_format)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a path of exactly `byte_size` bytes, for paths that are sent with their size
+    pub fn read_sized<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        byte_size: u64,
+        path_format: EPathFormat,
+    ) -> BinResult<Self> {
+        let path_start = reader.stream_position()?;
+        let path_end = path_start + byte_size;
+
+        let mut segments = Vec::new();
+
+        while reader.stream_position()? < path_end {
+            segments.push(PathSegment::read_options(
+                reader,
+                binrw::Endian::Little,
+                path_format,
+            )?);
+        }
+
+        if reader.stream_position()? != path_end {
+            return Err(binrw::Error::AssertFail {
+                pos: path_start,
+                message: format!("path segments run past the {} byte path", byte_size),
+            });
+        }
+
+        Ok(CipPath { segments })
+    }
+}
+
+impl ReadEndian for CipPath {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinRead for CipPath {
+    type Args<'a> = ();
+
+    // Without a size, the path is every segment up to the end of the data
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut segments = Vec::new();
+
+        loop {
+            let segment_start = reader.stream_position()?;
+
+            let mut next_byte = [0u8; 1];
+            if reader.read(&mut next_byte)? == 0 {
+                break;
+            }
+            reader.seek(SeekFrom::Start(segment_start))?;
+
+            segments.push(PathSegment::read_options(
+                reader,
+                endian,
+                EPathFormat::Padded,
+            )?);
+        }
+
+        Ok(CipPath { segments })
+    }
+}
+
+impl WriteEndian for CipPath {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinWrite for CipPath {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        _endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.write_format(writer, EPathFormat::Padded)
+    }
+}
+
+// Path values are decimal, or hex with a `0x` prefix
+fn parse_path_value(path_text: &str, value_text: &str) -> Result<u32, PathParseError> {
+    let parsed_value = match value_text.strip_prefix("0x") {
+        Some(hex_text) => u32::from_str_radix(hex_text, 16),
+        None => value_text.parse::<u32>(),
+    };
+
+    parsed_value
+        .map_err(|_| PathParseError::new(path_text, &format!("\"{}\" is not a number", value_text)))
+}
+
+fn logical_keyword(keyword: &str) -> Option<LogicalSegmentType> {
+    match keyword.to_ascii_lowercase().as_str() {
+        "class" => Some(LogicalSegmentType::ClassId),
+        "instance" => Some(LogicalSegmentType::InstanceId),
+        "member" => Some(LogicalSegmentType::MemberId),
+        "point" => Some(LogicalSegmentType::ConnectionPoint),
+        "attr" | "attribute" => Some(LogicalSegmentType::AttributeId),
+        "service" => Some(LogicalSegmentType::ServiceId),
+        // The reserved logical type is written with its number
+        "logical7" => Some(LogicalSegmentType::Reserved),
+        _ => None,
+    }
+}
+
+fn logical_keyword_name(logical_segment_type: LogicalSegmentType) -> String {
+    match logical_segment_type {
+        LogicalSegmentType::ClassId => "class".to_string(),
+        LogicalSegmentType::InstanceId => "instance".to_string(),
+        LogicalSegmentType::MemberId => "member".to_string(),
+        LogicalSegmentType::ConnectionPoint => "point".to_string(),
+        LogicalSegmentType::AttributeId => "attr".to_string(),
+        LogicalSegmentType::ServiceId => "service".to_string(),
+        // Only reachable for a segment that can't be written, see `check_logical_segment`
+        LogicalSegmentType::Special => "logical5".to_string(),
+        LogicalSegmentType::Reserved => "logical7".to_string(),
+    }
+}
+
+fn is_segment_keyword(keyword: &str) -> bool {
+    logical_keyword(keyword).is_some()
+        || matches!(
+            keyword.to_ascii_lowercase().as_str(),
+            "port"
+                | "key"
+                | "compatible_key"
+                | "symbol"
+                | "schedule"
+                | "fixed_tag"
+                | "inhibit"
+                | "inhibit_us"
+                | "safety"
+        )
+}
+
+// Reads one `keyword value` pair of the keyword notation
+fn parse_keyword_segment(
+    path_text: &str,
+    keyword: &str,
+    value_text: &str,
+) -> Result<PathSegment, PathParseError> {
+    let byte_value = |value_text: &str| {
+        CipUsint::try_from(parse_path_value(path_text, value_text)?).map_err(|_| {
+            PathParseError::new(
+                path_text,
+                &format!("{} {} doesn't fit in a byte", keyword, value_text),
+            )
+        })
+    };
+
+    if let Some(logical_type) = logical_keyword(keyword) {
+        if logical_type == LogicalSegmentType::ServiceId {
+            return Ok(LogicalPathSegment::new_u8(logical_type, byte_value(value_text)?).into());
+        }
+
+        let value = parse_path_value(path_text, value_text)?;
+        return Ok(LogicalPathSegment::new(logical_type, value).into());
+    }
+
+    let path_segment = match keyword.to_ascii_lowercase().as_str() {
+        "port" => {
+            let route: Route = value_text.parse().map_err(|route_err: PathParseError| {
+                PathParseError::new(path_text, &route_err.reason)
+            })?;
+
+            match route.port_segments.as_slice() {
+                [port_segment] => PathSegment::Port(port_segment.clone()),
+                _ => {
+                    return Err(PathParseError::new(
+                        path_text,
+                        "a port segment is a single port and link address",
+                    ))
+                }
+            }
+        }
+        // `vendor:device_type:product_code:major.minor`
+        "key" | "compatible_key" => {
+            let key_error = || {
+                PathParseError::new(
+                    path_text,
+                    &format!(
+                        "\"{}\" is not a key of vendor:device_type:product_code:major.minor",
+                        value_text
+                    ),
+                )
+            };
+            let word_value = |value_text: &str| {
+                CipUint::try_from(parse_path_value(path_text, value_text)?).map_err(|_| key_error())
+            };
+
+            let (key_ids, revision_text) = value_text.rsplit_once(':').ok_or_else(key_error)?;
+            let (major_text, minor_text) = revision_text.split_once('.').ok_or_else(key_error)?;
+
+            let [vendor_id, device_type, product_code] = key_ids
+                .split(':')
+                .map(word_value)
+                .collect::<Result<Vec<CipUint>, _>>()?
+                .try_into()
+                .map_err(|_| key_error())?;
+
+            let major_revision = byte_value(major_text)?;
+            if major_revision > 0x7f {
+                return Err(key_error());
+            }
+
+            PathSegment::ElectronicKey(ElectronicKeySegment::new(
+                vendor_id,
+                device_type,
+                product_code,
+                major_revision,
+                byte_value(minor_text)?,
+                keyword.eq_ignore_ascii_case("compatible_key"),
+            ))
+        }
+        "symbol" => SymbolicSegment::new(value_text)
+            .map_err(|symbol_err| PathParseError::new(path_text, &symbol_err.reason))?
+            .into(),
+        "schedule" => PathSegment::Network(NetworkSegment::Schedule(byte_value(value_text)?)),
+        "fixed_tag" => PathSegment::Network(NetworkSegment::FixedTag(byte_value(value_text)?)),
+        "inhibit" => PathSegment::Network(NetworkSegment::ProductionInhibitTime(byte_value(
+            value_text,
+        )?)),
+        "inhibit_us" => PathSegment::Network(NetworkSegment::ProductionInhibitTimeMicroseconds(
+            parse_path_value(path_text, value_text)?,
+        )),
+        "safety" => {
+            // The raw segment data as hex bytes, e.g. `0x0102`
+            let hex_text = value_text
+                .strip_prefix("0x")
+                .filter(|hex_text| hex_text.len().is_multiple_of(2))
+                .ok_or_else(|| {
+                    PathParseError::new(
+                        path_text,
+                        &format!("\"{}\" is not hex segment data", value_text),
+                    )
+                })?;
+
+            let safety_data = (0..hex_text.len())
+                .step_by(2)
+                .map(|hex_index| u8::from_str_radix(&hex_text[hex_index..hex_index + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| {
+                    PathParseError::new(
+                        path_text,
+                        &format!("\"{}\" is not hex segment data", value_text),
+                    )
+                })?;
+
+            PathSegment::Network(NetworkSegment::Safety(safety_data))
+        }
+        _ => {
+            return Err(PathParseError::new(
+                path_text,
+                &format!("unknown segment keyword \"{}\"", keyword),
+            ))
+        }
+    };
+
+    Ok(path_segment)
+}
+
+// The keyword notation of a single segment, which `parse_keyword_segment` reads back
+fn write_keyword_segment(f: &mut fmt::Formatter<'_>, path_segment: &PathSegment) -> fmt::Result {
+    match path_segment {
+        PathSegment::Logical(logical_segment) => write!(
+            f,
+            "{} {:#04x}",
+            logical_keyword_name(logical_segment.logical_segment_type()),
+            logical_segment.value()
+        ),
+        PathSegment::Port(port_segment) => write!(
+            f,
+            "port {}",
+            Route {
+                port_segments: vec![port_segment.clone()],
+            }
+        ),
+        PathSegment::ElectronicKey(electronic_key) => write!(
+            f,
+            "{} {:#06x}:{:#06x}:{:#06x}:{}.{}",
+            match electronic_key.major_revision.compatibility() {
+                true => "compatible_key",
+                false => "key",
+            },
+            electronic_key.vendor_id,
+            electronic_key.device_type,
+            electronic_key.product_code,
+            electronic_key.major_revision.major_revision(),
+            electronic_key.minor_revision
+        ),
+        PathSegment::Symbolic(symbolic_segment) => write!(f, "symbol {}", symbolic_segment.symbol),
+        PathSegment::Network(NetworkSegment::Schedule(value)) => {
+            write!(f, "schedule {:#04x}", value)
+        }
+        PathSegment::Network(NetworkSegment::FixedTag(value)) => {
+            write!(f, "fixed_tag {:#04x}", value)
+        }
+        PathSegment::Network(NetworkSegment::ProductionInhibitTime(value)) => {
+            write!(f, "inhibit {}", value)
+        }
+        PathSegment::Network(NetworkSegment::ProductionInhibitTimeMicroseconds(value)) => {
+            write!(f, "inhibit_us {}", value)
+        }
+        PathSegment::Network(NetworkSegment::Safety(safety_data)) => {
+            write!(f, "safety 0x")?;
+
+            for safety_byte in safety_data {
+                write!(f, "{:02x}", safety_byte)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+// Parses a route (`1,0`) followed by the target in one of `@4/150/3`,
+//  `class 0x04 instance 0x96 attr 3` or a tag name like `Program:Main.Tag[2]`. Every logical
+//  value uses the smallest format that fits it
+impl FromStr for CipPath {
+    type Err = PathParseError;
+
+    fn from_str(path_text: &str) -> Result<Self, Self::Err> {
+        // Routes start with a port number, the target with `@` or a letter
+        let (route_text, target_text) = match path_text.split_once('@') {
+            Some((route_text, slash_text)) => (
+                route_text.split_whitespace().collect::<String>(),
+                format!("@{}", slash_text.trim()),
+            ),
+            None => {
+                let words: Vec<&str> = path_text.split_whitespace().collect();
+                let target_start = words
+                    .iter()
+                    .position(|word| !word.starts_with(|c: char| c.is_ascii_digit() || c == ','))
+                    .unwrap_or(words.len());
+
+                (
+                    words[..target_start].concat(),
+                    words[target_start..].join(" "),
+                )
+            }
+        };
+
+        if route_text.is_empty() && target_text.is_empty() {
+            return Err(PathParseError::new(path_text, "empty path"));
+        }
+
+        let mut cip_path = CipPath::default();
+
+        let route_text = route_text.trim_end_matches(',');
+        if !route_text.is_empty() {
+            let route: Route = route_text.parse().map_err(|route_err: PathParseError| {
+                PathParseError::new(path_text, &route_err.reason)
+            })?;
+            cip_path = cip_path.with_route(&route);
+        }
+
+        let target_words: Vec<&str> = target_text.split_whitespace().collect();
+
+        match target_words.as_slice() {
+            [] => Ok(cip_path),
+            [slash_text] if slash_text.starts_with('@') => {
+                let logical_types = [
+                    LogicalSegmentType::ClassId,
+                    LogicalSegmentType::InstanceId,
+                    LogicalSegmentType::AttributeId,
+                ];
+                let slash_values: Vec<&str> = slash_text[1..].split('/').collect();
+
+                if slash_values.len() > logical_types.len() {
+                    return Err(PathParseError::new(
+                        path_text,
+                        "@ takes at most a class, instance and attribute",
+                    ));
+                }
+
+                for (logical_type, value_text) in logical_types.into_iter().zip(slash_values) {
+                    let value = parse_path_value(path_text, value_text)?;
+                    cip_path = cip_path.with_segment(LogicalPathSegment::new(logical_type, value));
+                }
+
+                Ok(cip_path)
+            }
+            // A single word is always a tag name, even one like `class`
+            [keyword, _, ..] if is_segment_keyword(keyword) => {
+                if !target_words.len().is_multiple_of(2) {
+                    return Err(PathParseError::new(
+                        path_text,
+                        "every segment keyword needs a value",
+                    ));
+                }
+
+                for keyword_value in target_words.chunks(2) {
+                    cip_path = cip_path.with_segment(parse_keyword_segment(
+                        path_text,
+                        keyword_value[0],
+                        keyword_value[1],
+                    )?);
+                }
+
+                Ok(cip_path)
+            }
+            [tag_name] => {
+                let tag_path = CipPath::from_tag_name(tag_name)
+                    .map_err(|tag_err| PathParseError::new(path_text, &tag_err.reason))?;
+                cip_path.segments.extend(tag_path.segments);
+
+                Ok(cip_path)
+            }
+            _ => Err(PathParseError::new(
+                path_text,
+                &format!("\"{}\" is not a path target", target_text),
             )),
         }
     }
 }
 
+// Writes the route and then the target in the notation `FromStr` reads, picking the `@` form
+//  for a class, instance and attribute and the keyword form for any other segments
+impl fmt::Display for CipPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let route = self.route();
+        let target = CipPath {
+            segments: self.segments[route.port_segments.len()..].to_vec(),
+        };
+
+        if !route.is_empty() {
+            write!(f, "{}", route)?;
+
+            if target.segments.is_empty() {
+                return Ok(());
+            }
+
+            write!(f, " ")?;
+        }
+
+        if let Some(tag_name) = target.tag_name() {
+            return write!(f, "{}", tag_name);
+        }
+
+        let slash_types = [
+            LogicalSegmentType::ClassId,
+            LogicalSegmentType::InstanceId,
+            LogicalSegmentType::AttributeId,
+        ];
+        let slash_values: Option<Vec<String>> = target
+            .segments
+            .iter()
+            .zip(slash_types)
+            .map(|(path_segment, slash_type)| match path_segment {
+                PathSegment::Logical(logical_segment)
+                    if logical_segment.logical_segment_type() == slash_type =>
+                {
+                    Some(logical_segment.value().to_string())
+                }
+                _ => None,
+            })
+            .collect();
+
+        match slash_values {
+            Some(slash_values)
+                if !slash_values.is_empty() && slash_values.len() == target.segments.len() =>
+            {
+                write!(f, "@{}", slash_values.join("/"))
+            }
+            _ => {
+                for (segment_index, path_segment) in target.segments.iter().enumerate() {
+                    if segment_index > 0 {
+                        write!(f, " ")?;
+                    }
+
+                    write_keyword_segment(f, path_segment)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 // ^^^^^^^^ End of CipPath impl ^^^^^^^^
+
+// Text that isn't a valid path or route
+#[derive(Debug, PartialEq, Clone)]
+pub struct PathParseError {
+    pub input: String,
+    pub reason: String,
+}
+
+// ======= Start of PathParseError impl ========
+
+impl PathParseError {
+    pub fn new(input: &str, reason: &str) -> Self {
+        PathParseError {
+            input: input.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid path \"{}\": {}", self.input, self.reason)
+    }
+}
+
+impl std::error::Error for PathParseError {}
+
+// ^^^^^^^^ End of PathParseError impl ^^^^^^^^
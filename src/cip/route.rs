@@ -0,0 +1,120 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::path::{CipPath, PathParseError, PortSegment};
+use super::types::CipUint;
+
+// The hops from the device the client is connected to (e.g. an Ethernet bridge module) to the
+//  target, written as port/link address pairs like `1,0` or `1,3,2,192.168.1.5,1,0`
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Route {
+    pub port_segments: Vec<PortSegment>,
+}
+
+// ======= Start of Route impl ========
+
+impl Route {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves through `port` to the device at a single byte link address (e.g. a backplane slot)
+    pub fn with_hop(mut self, port: CipUint, link_address: u8) -> Self {
+        self.port_segments
+            .push(PortSegment::new(port, link_address));
+        self
+    }
+
+    /// Leaves through `port` to the device at a text link address (e.g. an IP address)
+    pub fn with_address_hop(
+        mut self,
+        port: CipUint,
+        link_address: &str,
+    ) -> Result<Self, PathParseError> {
+        self.port_segments.push(PortSegment::new_extended(
+            port,
+            link_address.as_bytes().to_vec(),
+        )?);
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.port_segments.is_empty()
+    }
+}
+
+impl From<Route> for CipPath {
+    fn from(route: Route) -> Self {
+        CipPath::default().with_route(&route)
+    }
+}
+
+impl FromStr for Route {
+    type Err = PathParseError;
+
+    fn from_str(route_text: &str) -> Result<Self, Self::Err> {
+        let route_parts: Vec<&str> = route_text.split(',').map(str::trim).collect();
+
+        if route_text.trim().is_empty() || !route_parts.len().is_multiple_of(2) {
+            return Err(PathParseError::new(
+                route_text,
+                "a route is a list of port and link address pairs",
+            ));
+        }
+
+        let mut route = Route::new();
+
+        for hop in route_parts.chunks(2) {
+            let port = match hop[0].parse::<CipUint>() {
+                Ok(port) if port > 0 => port,
+                _ => {
+                    return Err(PathParseError::new(
+                        route_text,
+                        &format!("\"{}\" is not a port number", hop[0]),
+                    ))
+                }
+            };
+
+            let link_address = hop[1];
+            if link_address.is_empty() {
+                return Err(PathParseError::new(route_text, "empty link address"));
+            }
+
+            route = match link_address.parse::<u8>() {
+                Ok(link_address) => route.with_hop(port, link_address),
+                Err(_) if link_address.chars().all(|c| c.is_ascii_digit()) => {
+                    return Err(PathParseError::new(
+                        route_text,
+                        &format!("link address {} doesn't fit in a byte", link_address),
+                    ))
+                }
+                Err(_) => route
+                    .with_address_hop(port, link_address)
+                    .map_err(|err| PathParseError::new(route_text, &err.reason))?,
+            };
+        }
+
+        Ok(route)
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (hop_index, port_segment) in self.port_segments.iter().enumerate() {
+            if hop_index > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{},", port_segment.port)?;
+
+            match port_segment.link_address.as_slice() {
+                [link_address] => write!(f, "{}", link_address)?,
+                link_address => write!(f, "{}", String::from_utf8_lossy(link_address))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of Route impl ^^^^^^^^
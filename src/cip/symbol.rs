@@ -0,0 +1,214 @@
+use binrw::{BinRead, BinResult, BinWrite};
+
+use super::path::{CipPath, LogicalSegmentType, PathParseError, PathSegment};
+
+// A data segment (segment type 4) with the ANSI extended symbol subtype
+const ANSI_EXTENDED_SYMBOL_SEGMENT: u8 = 0x91;
+
+// Names an object by its symbol instead of its class and instance (e.g. a Logix controller tag).
+//  The symbol is padded to an even length in both path formats
+#[derive(Debug, PartialEq, Clone)]
+pub struct SymbolicSegment {
+    pub symbol: String,
+}
+
+// ======= Start of SymbolicSegment impl ========
+
+impl SymbolicSegment {
+    /// Symbols are ASCII names of at most 255 bytes, like `Program:Main` or `_Counter`
+    pub fn new(symbol: &str) -> Result<Self, PathParseError> {
+        if !is_valid_symbol(symbol) {
+            return Err(PathParseError::new(
+                symbol,
+                &format!("\"{}\" is not a symbol", symbol),
+            ));
+        }
+
+        Ok(SymbolicSegment {
+            symbol: symbol.to_string(),
+        })
+    }
+
+    pub fn byte_size(&self) -> usize {
+        2 + self.symbol.len() + self.symbol.len() % 2
+    }
+}
+
+impl BinRead for SymbolicSegment {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let segment_start = reader.stream_position()?;
+        let segment_byte = u8::read_options(reader, endian, ())?;
+
+        if segment_byte != ANSI_EXTENDED_SYMBOL_SEGMENT {
+            return Err(binrw::Error::AssertFail {
+                pos: segment_start,
+                message: format!("unsupported data segment {:#04x}", segment_byte),
+            });
+        }
+
+        let symbol_size = u8::read_options(reader, endian, ())? as usize;
+        let symbol_bytes = Vec::<u8>::read_options(
+            reader,
+            endian,
+            binrw::VecArgs::builder().count(symbol_size).finalize(),
+        )?;
+
+        if !symbol_size.is_multiple_of(2) {
+            u8::read_options(reader, endian, ())?;
+        }
+
+        match String::from_utf8(symbol_bytes) {
+            Ok(symbol) => Ok(SymbolicSegment { symbol }),
+            Err(_) => Err(binrw::Error::AssertFail {
+                pos: segment_start,
+                message: "symbol isn't ANSI text".to_string(),
+            }),
+        }
+    }
+}
+
+impl BinWrite for SymbolicSegment {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        if self.symbol.len() > u8::MAX as usize {
+            return Err(binrw::Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: format!(
+                    "symbol of {} bytes doesn't fit in a symbolic segment",
+                    self.symbol.len()
+                ),
+            });
+        }
+
+        ANSI_EXTENDED_SYMBOL_SEGMENT.write_options(writer, endian, ())?;
+        (self.symbol.len() as u8).write_options(writer, endian, ())?;
+        self.symbol.as_bytes().write_options(writer, endian, ())?;
+
+        if !self.symbol.len().is_multiple_of(2) {
+            0x0u8.write_options(writer, endian, ())?;
+        }
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of SymbolicSegment impl ^^^^^^^^
+
+fn is_valid_symbol(symbol: &str) -> bool {
+    let starts_with_letter = symbol
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+
+    starts_with_letter
+        && symbol.len() <= u8::MAX as usize
+        && symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+// ======= Start of CipPath tag impl ========
+
+impl CipPath {
+    pub fn with_symbol(self, symbol: &str) -> Result<Self, PathParseError> {
+        Ok(self.with_segment(SymbolicSegment::new(symbol)?))
+    }
+
+    /// Builds the path of a tag like `Program:Main.MyUDT.Member[3]`. Every name between the dots
+    ///  is a symbolic segment and every array index is a member segment
+    pub fn from_tag_name(tag_name: &str) -> Result<Self, PathParseError> {
+        let mut tag_path = CipPath::default();
+
+        for tag_element in tag_name.split('.') {
+            let (symbol, indices) = match tag_element.split_once('[') {
+                Some((symbol, indices)) => match indices.strip_suffix(']') {
+                    Some(indices) => (symbol, Some(indices)),
+                    None => {
+                        return Err(PathParseError::new(
+                            tag_name,
+                            &format!("unclosed array index in \"{}\"", tag_element),
+                        ))
+                    }
+                },
+                None => (tag_element, None),
+            };
+
+            tag_path = tag_path.with_symbol(symbol).map_err(|_| {
+                PathParseError::new(tag_name, &format!("\"{}\" is not a tag name", symbol))
+            })?;
+
+            // Each dimension of a multi-dimensional array gets its own member segment
+            for index in indices.into_iter().flat_map(|indices| indices.split(',')) {
+                match index.trim().parse::<u32>() {
+                    Ok(index) => tag_path = tag_path.with_member(index),
+                    Err(_) => {
+                        return Err(PathParseError::new(
+                            tag_name,
+                            &format!("\"{}\" is not an array index", index),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(tag_path)
+    }
+
+    /// The tag name of a path of symbolic and member segments, after any route
+    pub fn tag_name(&self) -> Option<String> {
+        let mut tag_name = String::new();
+        let mut indices: Vec<String> = Vec::new();
+
+        let tag_segments = self
+            .segments
+            .iter()
+            .skip_while(|path_segment| matches!(path_segment, PathSegment::Port(_)));
+
+        for path_segment in tag_segments {
+            match path_segment {
+                PathSegment::Symbolic(symbolic_segment) => {
+                    if !indices.is_empty() {
+                        tag_name.push_str(&format!("[{}]", indices.join(",")));
+                        indices.clear();
+                    }
+
+                    if !tag_name.is_empty() {
+                        tag_name.push('.');
+                    }
+
+                    tag_name.push_str(&symbolic_segment.symbol);
+                }
+                PathSegment::Logical(logical_segment)
+                    if logical_segment.logical_segment_type() == LogicalSegmentType::MemberId
+                        && !tag_name.is_empty() =>
+                {
+                    indices.push(logical_segment.value().to_string());
+                }
+                _ => return None,
+            }
+        }
+
+        if !indices.is_empty() {
+            tag_name.push_str(&format!("[{}]", indices.join(",")));
+        }
+
+        match tag_name.is_empty() {
+            true => None,
+            false => Some(tag_name),
+        }
+    }
+}
+
+// ^^^^^^^^ End of CipPath tag impl ^^^^^^^^
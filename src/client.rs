@@ -0,0 +1,747 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use binrw::meta::ReadEndian;
+use binrw::{BinRead, BinWrite};
+use bytes::BytesMut;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::net::ToSocketAddrs;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::cip::connection_manager::{
+    ForwardOpenReply, ForwardOpenRequest, LargeForwardOpenRequest, OpenedConnection,
+    CONNECTION_MANAGER_CLASS_ID,
+};
+use crate::cip::message::attribute_list::{
+    AttributeResponse, AttributeSetting, AttributeStatus, AttributeTypeMap, GetAttributeListReply,
+    GetAttributeListRequest, SetAttributeListReply, SetAttributeListRequest,
+};
+use crate::cip::message::multiple_service::{
+    MultipleServiceReply, MultipleServiceRequest, MESSAGE_ROUTER_CLASS_ID,
+};
+use crate::cip::message::response::MessageRouterResponse;
+use crate::cip::message::shared::ServiceCode;
+use crate::cip::path::CipPath;
+use crate::cip::types::{CipUdint, CipUint};
+use crate::eip::constants::SENDER_CONTEXT_SIZE;
+use crate::eip::description::CommonPacketList;
+use crate::eip::framing::{
+    check_frame_status, connected_sequence, sender_context, EncapsulationCodec,
+};
+use crate::error::{require_reply_data, EipError, EipResult};
+use crate::object_assembly::{
+    ConnectedResponseObjectAssembly, ExplicitConnection, RequestObjectAssembly,
+    ResponseObjectAssembly, ServiceReplyObjectAssembly,
+};
+use crate::session::{discards_session, is_retryable, opened_connection, request_data_bytes};
+use crate::transport::{Connector, TcpConnector, Transport};
+
+pub use crate::session::{EipClientConfig, RetryPolicy};
+
+type SenderContext = [u8; SENDER_CONTEXT_SIZE];
+
+// Unconnected replies echo the sender context of their request, connected replies carry the
+//  connection ID and sequence count of theirs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ReplyKey {
+    Unconnected(SenderContext),
+    Connected {
+        t2o_connection_id: CipUdint,
+        sequence_count: CipUint,
+    },
+}
+
+// Requests waiting on a reply, keyed by what their reply is matched with. `None` once the
+//  connection is closed, so new requests fail instead of waiting forever
+type ReplyWaiters = Arc<Mutex<Option<HashMap<ReplyKey, oneshot::Sender<EipResult<BytesMut>>>>>>;
+
+// A registered session on one connection
+struct Session<T: Transport> {
+    writer: tokio::sync::Mutex<WriteHalf<T>>,
+    reply_waiters: ReplyWaiters,
+    reader_task: JoinHandle<()>,
+    session_handle: CipUdint,
+}
+
+impl<T: Transport> Session<T> {
+    fn is_closed(&self) -> bool {
+        self.reply_waiters.lock().unwrap().is_none()
+    }
+}
+
+impl<T: Transport> Drop for Session<T> {
+    fn drop(&mut self) {
+        self.reader_task.abort();
+    }
+}
+
+// An explicit messaging session with a single device. The session is registered on connect and
+//  unregistered on `close` (or, best-effort, on drop)
+//
+// Every request gets a unique sender context, so several requests can be in flight at once (e.g.
+//  with `tokio::join!`) and each reply is routed back to the request that the device echoes
+//
+// When the connection drops or the device no longer recognizes the session (e.g. after a power
+//  cycle), the next request reconnects and registers a new session
+//
+// The client runs over any transport its `Connector` opens, TCP by default
+pub struct EipClient<C: Connector = TcpConnector> {
+    connector: C,
+    config: EipClientConfig,
+    codec: EncapsulationCodec,
+    session: tokio::sync::Mutex<Option<Arc<Session<C::Transport>>>>,
+    next_sender_context: AtomicU64,
+    session_handle: AtomicU32,
+    closed: bool,
+}
+
+// ======= Start of EipClient impl ========
+
+impl EipClient<TcpConnector> {
+    /// Connects to the device over TCP and registers a new session
+    pub async fn connect<A: ToSocketAddrs>(address: A) -> EipResult<Self> {
+        Self::connect_with_config(address, EipClientConfig::default()).await
+    }
+
+    pub async fn connect_with_config<A: ToSocketAddrs>(
+        address: A,
+        config: EipClientConfig,
+    ) -> EipResult<Self> {
+        Self::with_connector(TcpConnector::new(address).await?, config).await
+    }
+}
+
+impl<C: Connector> EipClient<C> {
+    /// Opens a transport with the connector and registers a new session over it
+    pub async fn with_connector(connector: C, config: EipClientConfig) -> EipResult<Self> {
+        let client = EipClient {
+            connector,
+            config,
+            codec: EncapsulationCodec::with_max_frame_size(config.max_frame_size),
+            session: tokio::sync::Mutex::new(None),
+            next_sender_context: AtomicU64::new(0),
+            session_handle: AtomicU32::new(0),
+            closed: false,
+        };
+
+        let session = client.open_session().await?;
+        *client.session.lock().await = Some(Arc::new(session));
+
+        Ok(client)
+    }
+
+    /// The handle of the most recently registered session
+    pub fn session_handle(&self) -> CipUdint {
+        self.session_handle.load(Ordering::Relaxed)
+    }
+
+    pub fn config(&self) -> &EipClientConfig {
+        &self.config
+    }
+
+    /// Sends an unconnected explicit request and returns the reply data, if the device sent any
+    pub async fn send_service<T, R>(
+        &self,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<Option<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        self.send_service_reply::<T, R>(request_path.clone(), service_code, data)
+            .await?
+            .into_result(request_path)
+    }
+
+    /// Sends an unconnected explicit request and returns the whole reply, whatever its status
+    pub async fn send_service_reply<T, R>(
+        &self,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<MessageRouterResponse<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        self.send_with_retries(
+            &request_path,
+            service_code,
+            request_data_bytes(data.as_ref())?,
+            ResponseObjectAssembly::<R>::into_message_router_response,
+        )
+        .await
+    }
+
+    /// Opens a connection with a standard Forward_Open
+    pub async fn forward_open(
+        &self,
+        forward_open: &ForwardOpenRequest,
+    ) -> EipResult<OpenedConnection> {
+        let (forward_open_reply, packet_items) = self
+            .send_connection_manager_request(ServiceCode::ForwardOpen, forward_open)
+            .await?;
+
+        opened_connection(forward_open_reply, &packet_items)
+    }
+
+    /// Opens a connection with Large_Forward_Open. Targets that don't support it are sent the
+    ///  standard Forward_Open instead, if the connection sizes fit
+    pub async fn large_forward_open(
+        &self,
+        large_forward_open: &LargeForwardOpenRequest,
+    ) -> EipResult<OpenedConnection> {
+        let (forward_open_reply, packet_items) = self
+            .send_connection_manager_request(ServiceCode::LargeForwardOpen, large_forward_open)
+            .await?;
+
+        match large_forward_open.fallback_request(&forward_open_reply) {
+            Some(forward_open) => self.forward_open(&forward_open).await,
+            None => opened_connection(forward_open_reply, &packet_items),
+        }
+    }
+
+    /// Reads the listed attributes, decoding each value with its data type in `type_map`
+    pub async fn get_attribute_list(
+        &self,
+        request_path: CipPath,
+        attribute_ids: Vec<CipUint>,
+        type_map: &AttributeTypeMap,
+    ) -> EipResult<Vec<AttributeResponse>> {
+        let attribute_list_reply = self
+            .send_service_reply::<GetAttributeListRequest, GetAttributeListReply>(
+                request_path.clone(),
+                ServiceCode::GetAttributeList,
+                Some(GetAttributeListRequest { attribute_ids }),
+            )
+            .await?
+            .into_get_attribute_list_reply(request_path)?;
+
+        Ok(attribute_list_reply.decode(type_map)?)
+    }
+
+    pub async fn set_attribute_list(
+        &self,
+        request_path: CipPath,
+        attributes: Vec<AttributeSetting>,
+    ) -> EipResult<Vec<AttributeStatus>> {
+        let attribute_list_reply = self
+            .send_service_reply::<SetAttributeListRequest, SetAttributeListReply>(
+                request_path.clone(),
+                ServiceCode::SetAttributeList,
+                Some(SetAttributeListRequest { attributes }),
+            )
+            .await?
+            .into_set_attribute_list_reply(request_path)?;
+
+        Ok(attribute_list_reply.attribute_statuses)
+    }
+
+    /// Sends several requests in one Multiple_Service_Packet. A failed embedded request only
+    ///  fails its own reply
+    pub async fn send_multiple_service(
+        &self,
+        multiple_service_request: MultipleServiceRequest,
+    ) -> EipResult<MultipleServiceReply> {
+        let request_path = CipPath::new(MESSAGE_ROUTER_CLASS_ID, 0x1);
+
+        self.send_service_reply::<MultipleServiceRequest, MultipleServiceReply>(
+            request_path.clone(),
+            ServiceCode::MultipleServicePacket,
+            Some(multiple_service_request),
+        )
+        .await?
+        .into_multiple_service_reply(request_path)
+    }
+
+    pub async fn get_attribute_single<R>(&self, request_path: CipPath) -> EipResult<R>
+    where
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let reply_data = self
+            .send_service::<u8, R>(request_path, ServiceCode::GetAttributeSingle, None)
+            .await?;
+
+        require_reply_data(reply_data)
+    }
+
+    pub async fn set_attribute_single<T>(&self, request_path: CipPath, value: T) -> EipResult<()>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        self.send_service::<T, u8>(request_path, ServiceCode::SetAttributeSingle, Some(value))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_attributes_all<R>(&self, request_path: CipPath) -> EipResult<R>
+    where
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let reply_data = self
+            .send_service::<u8, R>(request_path, ServiceCode::GetAttributeAll, None)
+            .await?;
+
+        require_reply_data(reply_data)
+    }
+
+    /// Sends an explicit request over a connection opened on this session (e.g. with a Class 3
+    ///  `forward_open`) and returns the reply data, if the device sent any
+    pub async fn send_connected_service<T, R>(
+        &self,
+        connection: &mut ExplicitConnection,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> EipResult<Option<R>>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        R: for<'a> BinRead<Args<'a> = ()>,
+    {
+        let session = self.current_session().await?;
+
+        // A connection ends with the session it was opened on
+        if connection.session_handle != session.session_handle {
+            return Err(EipError::SessionInvalid(connection.session_handle));
+        }
+
+        let request = connection.new_service_request(request_path.clone(), service_code, data);
+        let sequence_count = connection.sequence_count();
+
+        let reply_key = ReplyKey::Connected {
+            t2o_connection_id: connection.t2o_connection_id,
+            sequence_count,
+        };
+
+        let reply_result =
+            self.exchange(&session, reply_key, request)
+                .await
+                .and_then(|response_frame| {
+                    check_frame_status(&response_frame)?;
+
+                    let mut response_reader = BufReader::new(std::io::Cursor::new(response_frame));
+                    Ok(ConnectedResponseObjectAssembly::<R>::read(
+                        &mut response_reader,
+                    )?)
+                });
+
+        if let Err(reply_err) = &reply_result {
+            if discards_session(reply_err) {
+                self.discard_session(&session).await;
+            }
+        }
+
+        let connected_response = reply_result?;
+
+        if !connection.is_reply_to(&connected_response, sequence_count) {
+            return Err(EipError::MalformedFrame(binrw::Error::AssertFail {
+                pos: 0,
+                message: format!("the reply isn't for sequence count {}", sequence_count),
+            }));
+        }
+
+        connected_response.into_result(request_path)
+    }
+
+    /// Unregisters the session and closes the connection
+    pub async fn close(mut self) -> EipResult<()> {
+        self.closed = true;
+
+        let session = match self.session.lock().await.take() {
+            Some(session) => session,
+            None => return Ok(()),
+        };
+
+        // The device closes the connection without replying to an unregistration
+        self.write_request(
+            &session,
+            RequestObjectAssembly::new_unregistration(session.session_handle),
+        )
+        .await?;
+        session.writer.lock().await.shutdown().await?;
+
+        Ok(())
+    }
+
+    // Forward_Open replies carry data for both success and failure, so the whole reply is read
+    //  along with the socket address items sent next to it
+    async fn send_connection_manager_request<T>(
+        &self,
+        service_code: ServiceCode,
+        request: &T,
+    ) -> EipResult<(ForwardOpenReply, CommonPacketList)>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        self.send_with_retries(
+            &CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            service_code,
+            request_data_bytes(Some(request))?,
+            ServiceReplyObjectAssembly::<ForwardOpenReply>::into_reply_with_items,
+        )
+        .await
+    }
+
+    // Sends the request until it gets a reply, `into_reply` fails with an error that isn't worth
+    //  retrying, or the retry policy runs out
+    async fn send_with_retries<A, O>(
+        &self,
+        request_path: &CipPath,
+        service_code: ServiceCode,
+        data_bytes: Option<Vec<u8>>,
+        into_reply: fn(A) -> EipResult<O>,
+    ) -> EipResult<O>
+    where
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        let mut retries = 0;
+
+        loop {
+            let reply_result = self
+                .send_service_once(request_path, service_code, data_bytes.clone(), into_reply)
+                .await;
+
+            let retry_allowed = match &reply_result {
+                Err(reply_err) => is_retryable(reply_err, service_code),
+                Ok(_) => false,
+            };
+
+            if !retry_allowed || retries >= self.config.retry_policy.max_retries {
+                return reply_result;
+            }
+
+            retries += 1;
+            tokio::time::sleep(self.config.retry_policy.retry_delay).await;
+        }
+    }
+
+    async fn send_service_once<A, O>(
+        &self,
+        request_path: &CipPath,
+        service_code: ServiceCode,
+        data_bytes: Option<Vec<u8>>,
+        into_reply: fn(A) -> EipResult<O>,
+    ) -> EipResult<O>
+    where
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        let session = self.current_session().await?;
+
+        let request = RequestObjectAssembly::new_service_request(
+            session.session_handle,
+            request_path.clone(),
+            service_code,
+            data_bytes,
+        );
+
+        let reply_result = self
+            .transact::<Vec<u8>, A>(&session, request)
+            .await
+            .and_then(into_reply);
+
+        if let Err(reply_err) = &reply_result {
+            if discards_session(reply_err) {
+                self.discard_session(&session).await;
+            }
+        }
+
+        reply_result
+    }
+
+    async fn current_session(&self) -> EipResult<Arc<Session<C::Transport>>> {
+        let mut current_session = self.session.lock().await;
+
+        if let Some(session) = current_session.as_ref() {
+            if !session.is_closed() {
+                return Ok(session.clone());
+            }
+        }
+
+        if !self.config.reconnect {
+            return Err(connection_closed());
+        }
+
+        // Holding the lock while reconnecting keeps concurrent requests from opening more sessions
+        let session = Arc::new(self.open_session().await?);
+        *current_session = Some(session.clone());
+
+        Ok(session)
+    }
+
+    async fn discard_session(&self, session: &Arc<Session<C::Transport>>) {
+        let mut current_session = self.session.lock().await;
+
+        if let Some(current) = current_session.as_ref() {
+            if Arc::ptr_eq(current, session) {
+                current_session.take();
+            }
+        }
+    }
+
+    async fn open_session(&self) -> EipResult<Session<C::Transport>> {
+        let transport =
+            tokio::time::timeout(self.config.request_timeout, self.connector.connect()).await??;
+        let (reader, writer) = tokio::io::split(transport);
+
+        let reply_waiters: ReplyWaiters = Arc::new(Mutex::new(Some(HashMap::new())));
+        let reader_task = tokio::spawn(route_replies(reader, self.codec, reply_waiters.clone()));
+
+        let mut session = Session {
+            writer: tokio::sync::Mutex::new(writer),
+            reply_waiters,
+            reader_task,
+            session_handle: 0,
+        };
+
+        let registration_response = self
+            .transact::<u8, ResponseObjectAssembly<u8>>(
+                &session,
+                RequestObjectAssembly::new_registration(),
+            )
+            .await?;
+        registration_response.packet_description.check_status()?;
+
+        session.session_handle = registration_response
+            .packet_description
+            .header
+            .session_handle;
+        self.session_handle
+            .store(session.session_handle, Ordering::Relaxed);
+
+        Ok(session)
+    }
+
+    // `A` is the object assembly the reply frame is read as
+    async fn transact<T, A>(
+        &self,
+        session: &Session<C::Transport>,
+        mut request: RequestObjectAssembly<T>,
+    ) -> EipResult<A>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+        A: for<'a> BinRead<Args<'a> = ()> + ReadEndian,
+    {
+        let request_context = self
+            .next_sender_context
+            .fetch_add(1, Ordering::Relaxed)
+            .to_le_bytes();
+        request.packet_description.header.sender_context = request_context;
+
+        let response_frame = self
+            .exchange(session, ReplyKey::Unconnected(request_context), request)
+            .await?;
+
+        check_frame_status(&response_frame)?;
+
+        let mut response_reader = BufReader::new(std::io::Cursor::new(response_frame));
+
+        Ok(A::read(&mut response_reader)?)
+    }
+
+    // Writes the request and waits for the reply frame matched by `reply_key`
+    async fn exchange<Q>(
+        &self,
+        session: &Session<C::Transport>,
+        reply_key: ReplyKey,
+        request: Q,
+    ) -> EipResult<BytesMut>
+    where
+        EncapsulationCodec: Encoder<Q, Error = EipError>,
+    {
+        let (reply_sender, reply_receiver) = oneshot::channel();
+
+        match session.reply_waiters.lock().unwrap().as_mut() {
+            Some(reply_waiters) => reply_waiters.insert(reply_key, reply_sender),
+            None => return Err(connection_closed()),
+        };
+
+        let reply_result = tokio::time::timeout(self.config.request_timeout, async {
+            self.write_request(session, request).await?;
+
+            reply_receiver
+                .await
+                .unwrap_or_else(|_| Err(connection_closed()))
+        })
+        .await;
+
+        match reply_result {
+            Ok(Ok(response_frame)) => Ok(response_frame),
+            Ok(Err(request_err)) => {
+                forget_request(session, &reply_key);
+                Err(request_err)
+            }
+            Err(elapsed) => {
+                forget_request(session, &reply_key);
+                Err(elapsed.into())
+            }
+        }
+    }
+
+    async fn write_request<Q>(&self, session: &Session<C::Transport>, request: Q) -> EipResult<()>
+    where
+        EncapsulationCodec: Encoder<Q, Error = EipError>,
+    {
+        let mut codec = self.codec;
+        let mut request_bytes = BytesMut::new();
+        codec.encode(request, &mut request_bytes)?;
+
+        session
+            .writer
+            .lock()
+            .await
+            .write_all(&request_bytes)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Connectors are often closures, so only the client's own state is shown
+impl<C: Connector> fmt::Debug for EipClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EipClient")
+            .field("config", &self.config)
+            .field("codec", &self.codec)
+            .field("session_handle", &self.session_handle())
+            .field("closed", &self.closed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Connector> Drop for EipClient<C> {
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+
+        let session = match self.session.try_lock() {
+            Ok(mut current_session) => current_session.take(),
+            Err(_) => None,
+        };
+
+        let session = match session {
+            Some(session) if !session.is_closed() => session,
+            _ => return,
+        };
+
+        let mut unregistration_bytes = BytesMut::new();
+
+        if self
+            .codec
+            .encode(
+                RequestObjectAssembly::new_unregistration(session.session_handle),
+                &mut unregistration_bytes,
+            )
+            .is_err()
+        {
+            return;
+        }
+
+        // Can't await here, so the unregistration is sent best-effort from a task
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                let _ = session
+                    .writer
+                    .lock()
+                    .await
+                    .write_all(&unregistration_bytes)
+                    .await;
+            });
+        }
+    }
+}
+
+// ^^^^^^^^ End of EipClient impl ^^^^^^^^
+
+fn forget_request<T: Transport>(session: &Session<T>, reply_key: &ReplyKey) {
+    if let Some(reply_waiters) = session.reply_waiters.lock().unwrap().as_mut() {
+        reply_waiters.remove(reply_key);
+    }
+}
+
+fn reply_key(frame: &[u8]) -> ReplyKey {
+    match connected_sequence(frame) {
+        Some((t2o_connection_id, sequence_count)) => ReplyKey::Connected {
+            t2o_connection_id,
+            sequence_count,
+        },
+        None => ReplyKey::Unconnected(sender_context(frame)),
+    }
+}
+
+// Hands every reply frame to the request it answers until the connection closes
+async fn route_replies<T: AsyncRead>(
+    mut reader: ReadHalf<T>,
+    mut codec: EncapsulationCodec,
+    reply_waiters: ReplyWaiters,
+) {
+    let mut read_buffer = BytesMut::new();
+
+    let reader_err = 'reading: loop {
+        loop {
+            match codec.decode(&mut read_buffer) {
+                Ok(Some(frame)) => {
+                    let reply_sender = reply_waiters
+                        .lock()
+                        .unwrap()
+                        .as_mut()
+                        .and_then(|reply_waiters| reply_waiters.remove(&reply_key(&frame)));
+
+                    // Replies nobody is waiting for anymore are dropped
+                    if let Some(reply_sender) = reply_sender {
+                        let _ = reply_sender.send(Ok(frame));
+                    }
+                }
+                Ok(None) => break,
+                Err(decode_err) => break 'reading decode_err,
+            }
+        }
+
+        match reader.read_buf(&mut read_buffer).await {
+            Ok(0) => break connection_closed(),
+            Err(read_err) => break read_err.into(),
+            Ok(_) => {}
+        }
+    };
+
+    // Every request still waiting on a reply fails with the error that ended the connection
+    if let Some(reply_waiters) = reply_waiters.lock().unwrap().take() {
+        for (_, reply_sender) in reply_waiters {
+            let _ = reply_sender.send(Err(copy_reader_error(&reader_err)));
+        }
+    }
+}
+
+// `EipError` can't be cloned, so each waiting request gets its own copy of the reader's error
+fn copy_reader_error(reader_err: &EipError) -> EipError {
+    match reader_err {
+        EipError::FrameTooLarge {
+            frame_size,
+            max_frame_size,
+        } => EipError::FrameTooLarge {
+            frame_size: *frame_size,
+            max_frame_size: *max_frame_size,
+        },
+        EipError::Io(io_err) => {
+            EipError::Io(std::io::Error::new(io_err.kind(), io_err.to_string()))
+        }
+        EipError::Timeout => EipError::Timeout,
+        reader_err => EipError::MalformedFrame(binrw::Error::AssertFail {
+            pos: 0,
+            message: reader_err.to_string(),
+        }),
+    }
+}
+
+fn connection_closed() -> EipError {
+    EipError::Io(std::io::ErrorKind::UnexpectedEof.into())
+}
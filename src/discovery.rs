@@ -0,0 +1,91 @@
+use std::io::{Cursor, ErrorKind};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::time::{Duration, Instant};
+
+use binrw::{BinRead, BinWrite};
+
+use crate::eip::command::{CommandSpecificData, ListIdentityItem};
+use crate::eip::constants as eip_constants;
+use crate::eip::packet::EnIpPacketDescription;
+
+// Largest UDP payload that can be received without fragmentation on an ethernet link
+const MAX_DATAGRAM_SIZE: usize = 1500;
+
+#[derive(Debug, PartialEq)]
+pub struct DiscoveredDevice {
+    // The address the ListIdentity reply was sent from
+    pub source_address: SocketAddr,
+    pub identity: ListIdentityItem,
+}
+
+/// Broadcasts a ListIdentity request on the local network and collects every reply
+/// received before the `window` elapses
+pub fn discover(window: Duration) -> std::io::Result<Vec<DiscoveredDevice>> {
+    discover_on(
+        SocketAddrV4::new(Ipv4Addr::BROADCAST, eip_constants::ENCAPSULATION_PORT).into(),
+        window,
+    )
+}
+
+/// Sends a ListIdentity request to `target_address` (a broadcast, directed broadcast or
+/// unicast address) and collects every reply received before the `window` elapses
+pub fn discover_on(
+    target_address: SocketAddr,
+    window: Duration,
+) -> std::io::Result<Vec<DiscoveredDevice>> {
+    let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_broadcast(true)?;
+
+    // Write the ListIdentity request (it has no command specific data)
+    let mut request_buffer: Vec<u8> = Vec::new();
+    let mut writer = Cursor::new(&mut request_buffer);
+
+    EnIpPacketDescription::new_list_identity_description()
+        .write(&mut writer)
+        .map_err(std::io::Error::other)?;
+
+    socket.send_to(&request_buffer, target_address)?;
+
+    let deadline = Instant::now() + window;
+    let mut response_buffer = [0u8; MAX_DATAGRAM_SIZE];
+    let mut discovered_devices = Vec::new();
+
+    loop {
+        let remaining_window = deadline.saturating_duration_since(Instant::now());
+        if remaining_window.is_zero() {
+            break;
+        }
+
+        socket.set_read_timeout(Some(remaining_window))?;
+
+        let (response_bytes_read, source_address) = match socket.recv_from(&mut response_buffer) {
+            Ok(received) => received,
+            Err(recv_err)
+                if matches!(recv_err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                break;
+            }
+            Err(recv_err) => return Err(recv_err),
+        };
+
+        // Anything that isn't a well-formed ListIdentity reply is ignored
+        let Ok(packet_description) =
+            EnIpPacketDescription::read(&mut Cursor::new(&response_buffer[..response_bytes_read]))
+        else {
+            continue;
+        };
+
+        if let CommandSpecificData::ListIdentity(Some(list_identity_data)) =
+            packet_description.command_specific_data
+        {
+            discovered_devices.extend(list_identity_data.identity_items.into_iter().map(
+                |identity| DiscoveredDevice {
+                    source_address,
+                    identity,
+                },
+            ));
+        }
+    }
+
+    Ok(discovered_devices)
+}
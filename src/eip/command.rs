@@ -4,9 +4,12 @@ use binrw::{
     BinWrite, // trait for writing
 };
 
-use crate::cip::types::{CipUdint, CipUint};
+use bilge::prelude::{bitsize, u2, u5, u7, Bitsized, DebugBits, FromBits, Number};
 
-use super::description::{CommonPacketDescriptor, CommonPacketItemId};
+use crate::cip::identity::{DeviceType, IdentityStatus, Revision, VendorId};
+use crate::cip::types::{CipShortString, CipUdint, CipUint, CipUsint};
+
+use super::description::{CommonPacketItem, CommonPacketList, RawPacketItem, SockAddrInfo};
 
 #[derive(BinRead, BinWrite)]
 #[br(little, repr = CipUint)]
@@ -26,18 +29,25 @@ pub enum EnIpCommand {
     Cancel = 0x0073,
 }
 
-#[derive(BinRead, BinWrite)]
-#[br(little, repr = CipUdint)]
-#[bw(little, repr = CipUdint)]
+#[binrw]
+#[brw(little)]
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum EncapsStatusCode {
     // Needs to be of type CipUdint (u32)
-    Success = 0x0000,
-    UnsupportedCommand = 0x0001,
-    InsufficientMemory = 0x0002,
-    InvalidFormatOrData = 0x0003,
-    InvalidSessionHandle = 0x0064,
-    UnsupportedProtocolVersion = 0x0069,
+    #[brw(magic = 0x0000u32)]
+    Success,
+    #[brw(magic = 0x0001u32)]
+    UnsupportedCommand,
+    #[brw(magic = 0x0002u32)]
+    InsufficientMemory,
+    #[brw(magic = 0x0003u32)]
+    InvalidFormatOrData,
+    #[brw(magic = 0x0064u32)]
+    InvalidSessionHandle,
+    #[brw(magic = 0x0069u32)]
+    UnsupportedProtocolVersion,
+    // Vendor specific and newer status codes are kept as their raw value
+    Unknown(CipUdint),
 }
 
 #[binrw]
@@ -48,39 +58,48 @@ pub struct RRPacketData {
     pub interface_handle: CipUdint,
     pub timeout: CipUint,
 
-    #[bw(calc = 2)]
-    pub _item_count: CipUint,
-    pub empty_data_packet: CommonPacketDescriptor,
-
     #[bw(args(Some(provided_packet_length)))]
-    pub unconnected_data_packet: CommonPacketDescriptor,
+    pub packet_items: CommonPacketList,
 }
 
 // ======= Start of RRPacketData impl ========
 
 impl RRPacketData {
-    /// WARNING: Exposed only for testing. All normal declarations should be made with Self::new(...)
-    pub fn test_with_size(
+    pub fn with_items(
         interface_handle: CipUdint,
         timeout: CipUint,
-        unconnected_length: Option<u16>,
+        items: Vec<CommonPacketItem>,
     ) -> Self {
         RRPacketData {
             interface_handle,
             timeout,
-            empty_data_packet: CommonPacketDescriptor {
-                type_id: CommonPacketItemId::NullAddr,
-                packet_length: Some(0),
-            },
-            unconnected_data_packet: CommonPacketDescriptor {
-                type_id: CommonPacketItemId::UnconnectedMessage,
-                packet_length: unconnected_length,
-            },
+            packet_items: CommonPacketList::new(items),
         }
     }
 
     pub fn new(interface_handle: CipUdint, timeout: CipUint) -> Self {
-        Self::test_with_size(interface_handle, timeout, None)
+        // The unconnected message is written by the object assembly after the packet description
+        Self::with_items(
+            interface_handle,
+            timeout,
+            vec![
+                CommonPacketItem::NullAddress,
+                CommonPacketItem::UnconnectedMessage(None),
+            ],
+        )
+    }
+
+    pub fn new_connected(connection_identifier: CipUdint) -> Self {
+        // The interface handle and timeout are always 0 for connected messages
+        // The connected transport packet is written by the object assembly after the packet description
+        Self::with_items(
+            0,
+            0,
+            vec![
+                CommonPacketItem::ConnectedAddress(connection_identifier),
+                CommonPacketItem::ConnectedTransportPacket(None),
+            ],
+        )
     }
 }
 
@@ -94,20 +113,188 @@ pub struct RegisterData {
     pub option_flags: CipUint,
 }
 
+// Size of the ListIdentity item fields that are not the product name
+const LIST_IDENTITY_ITEM_FIXED_SIZE: usize = 34;
+
+#[binrw]
+#[brw(little, magic = 0x000Cu16)]
+#[derive(Debug, PartialEq)]
+#[br(assert(
+    _item_length as usize >= LIST_IDENTITY_ITEM_FIXED_SIZE + product_name.value.len(),
+    "list identity item of {} bytes",
+    _item_length
+))]
+pub struct ListIdentityItem {
+    #[bw(calc = (LIST_IDENTITY_ITEM_FIXED_SIZE + product_name.value.len()) as CipUint)]
+    pub _item_length: CipUint,
+
+    pub encapsulation_version: CipUint,
+    pub socket_address: SockAddrInfo,
+    pub vendor_id: VendorId,
+    pub device_type: DeviceType,
+    pub product_code: CipUint,
+    pub revision: Revision,
+    pub status: IdentityStatus,
+    pub serial_number: CipUdint,
+    pub product_name: CipShortString,
+
+    // Any bytes a device appends past the state (e.g. vendor data) are skipped
+    #[br(pad_after = (_item_length as usize)
+        .saturating_sub(LIST_IDENTITY_ITEM_FIXED_SIZE + product_name.value.len()))]
+    pub state: CipUsint,
+}
+
 #[binrw]
 #[brw(little)]
 #[derive(Debug, PartialEq)]
-#[br(import(command_type: EnIpCommand))]
+pub struct ListIdentityData {
+    #[bw(calc = identity_items.len() as CipUint)]
+    pub _item_count: CipUint,
+
+    #[br(count = _item_count)]
+    pub identity_items: Vec<ListIdentityItem>,
+}
+
+#[bitsize(16)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u16::into)]
+#[bw(map = |&x| u16::from(x))]
+pub struct ServiceCapabilityFlags {
+    pub reserved1: u5,
+    pub cip_over_tcp: bool,
+    pub reserved2: u2,
+    pub class_0_1_udp: bool,
+    pub reserved3: u7,
+}
+
+// Size of the ListServices item fields (version, flags and name)
+const LIST_SERVICES_ITEM_SIZE: CipUint = 20;
+
+pub const SERVICE_NAME_SIZE: usize = 16;
+
+#[binrw]
+#[brw(little, magic = 0x0100u16)]
+#[derive(Debug, PartialEq)]
+pub struct ListServicesItem {
+    #[br(assert(
+        _item_length >= LIST_SERVICES_ITEM_SIZE,
+        "list services item of {} bytes",
+        _item_length
+    ))]
+    #[bw(calc = LIST_SERVICES_ITEM_SIZE)]
+    pub _item_length: CipUint,
+
+    pub encapsulation_version: CipUint,
+    pub capability_flags: ServiceCapabilityFlags,
+
+    // Any bytes a device appends past the name (e.g. vendor data) are skipped
+    #[br(pad_after = _item_length - LIST_SERVICES_ITEM_SIZE)]
+    pub service_name: [CipUsint; SERVICE_NAME_SIZE],
+}
+
+// ======= Start of ListServicesItem impl ========
+
+impl ListServicesItem {
+    pub fn name(&self) -> String {
+        // The service name is null terminated within its fixed size array
+        let name_length = self
+            .service_name
+            .iter()
+            .position(|&name_byte| name_byte == 0x00)
+            .unwrap_or(SERVICE_NAME_SIZE);
+
+        String::from_utf8_lossy(&self.service_name[..name_length]).to_string()
+    }
+
+    pub fn supports_cip_over_tcp(&self) -> bool {
+        self.capability_flags.cip_over_tcp()
+    }
+
+    pub fn supports_implicit_io(&self) -> bool {
+        self.capability_flags.class_0_1_udp()
+    }
+}
+
+// ^^^^^^^^ End of ListServicesItem impl ^^^^^^^^
+
+// Communications is the only service item type defined, so every other item is kept as raw bytes
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+pub enum ServiceItem {
+    Communications(ListServicesItem),
+    Unknown(RawPacketItem),
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+pub struct ListServicesData {
+    #[bw(calc = service_items.len() as CipUint)]
+    pub _item_count: CipUint,
+
+    #[br(count = _item_count)]
+    pub service_items: Vec<ServiceItem>,
+}
+
+// ======= Start of ListServicesData impl ========
+
+impl ListServicesData {
+    /// The Communications service of the device, if it reported one
+    pub fn communications(&self) -> Option<&ListServicesItem> {
+        self.service_items
+            .iter()
+            .find_map(|service_item| match service_item {
+                ServiceItem::Communications(communications_item) => Some(communications_item),
+                ServiceItem::Unknown(_) => None,
+            })
+    }
+}
+
+// ^^^^^^^^ End of ListServicesData impl ^^^^^^^^
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+pub struct ListInterfacesData {
+    #[bw(calc = interface_items.len() as CipUint)]
+    pub _item_count: CipUint,
+
+    // No public interface items are defined, so every item is kept as raw bytes
+    #[br(count = _item_count)]
+    pub interface_items: Vec<RawPacketItem>,
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+#[br(import(command_type: EnIpCommand, data_length: CipUint))]
 #[bw(import(provided_packet_length: u16))]
 pub enum CommandSpecificData {
     #[br(pre_assert(command_type == EnIpCommand::UnRegisterSession))]
     UnregisterSession,
 
+    /*  The ListIdentity request has no command specific data, only the reply does */
+    #[br(pre_assert(command_type == EnIpCommand::ListIdentity))]
+    ListIdentity(#[br(if(data_length > 0))] Option<ListIdentityData>),
+
+    /*  The ListServices request has no command specific data, only the reply does */
+    #[br(pre_assert(command_type == EnIpCommand::ListServices))]
+    ListServices(#[br(if(data_length > 0))] Option<ListServicesData>),
+
+    /*  The ListInterfaces request has no command specific data, only the reply does */
+    #[br(pre_assert(command_type == EnIpCommand::ListInterfaces))]
+    ListInterfaces(#[br(if(data_length > 0))] Option<ListInterfacesData>),
+
     #[br(pre_assert(command_type == EnIpCommand::RegisterSession))]
     RegisterSession(RegisterData),
 
     #[br(pre_assert(command_type == EnIpCommand::SendRrData))]
     SendRrData(#[bw(args(provided_packet_length))] RRPacketData),
+
+    /*  Connected (class 3) messages share the SendRrData layout, but address a connection instead */
+    #[br(pre_assert(command_type == EnIpCommand::SendUnitData))]
+    SendUnitData(#[bw(args(provided_packet_length))] RRPacketData),
     /*  When reading -- make sure the provided command_type matches.
     When writing -- make sure the packet length is passed on */
 }
@@ -125,6 +312,31 @@ impl CommandSpecificData {
     pub fn new_request(interface_handle: CipUdint, timeout: CipUint) -> Self {
         Self::SendRrData(RRPacketData::new(interface_handle, timeout))
     }
+
+    pub fn new_connected_request(connection_identifier: CipUdint) -> Self {
+        Self::SendUnitData(RRPacketData::new_connected(connection_identifier))
+    }
+
+    pub fn packet_items(&self) -> Option<&CommonPacketList> {
+        match self {
+            Self::SendRrData(rr_packet_data) | Self::SendUnitData(rr_packet_data) => {
+                Some(&rr_packet_data.packet_items)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn new_list_identity() -> Self {
+        Self::ListIdentity(None)
+    }
+
+    pub fn new_list_services() -> Self {
+        Self::ListServices(None)
+    }
+
+    pub fn new_list_interfaces() -> Self {
+        Self::ListInterfaces(None)
+    }
 }
 
 // ^^^^^^^^ End of CommandSpecificData impl ^^^^^^^^
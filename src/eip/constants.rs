@@ -1 +1,10 @@
 pub const SENDER_CONTEXT_SIZE: usize = 8;
+
+// TCP and UDP port used for encapsulation messages (44818)
+pub const ENCAPSULATION_PORT: u16 = 0xAF12;
+
+// UDP port used for Class 0/1 implicit I/O messages (2222)
+pub const IMPLICIT_IO_PORT: u16 = 0x08AE;
+
+// Size of the encapsulation header that starts every encapsulation message
+pub const ENCAPSULATION_HEADER_SIZE: usize = 24;
@@ -1,10 +1,15 @@
+use binrw::meta::{ReadEndian, WriteEndian};
 use binrw::{
-    binrw,    // #[binrw] attribute
-    BinRead,  // trait for reading
+    binrw,   // #[binrw] attribute
+    BinRead, // trait for reading
+    BinResult,
     BinWrite, // trait for writing
 };
 
-use crate::cip::types::CipUint;
+use std::io::{Cursor, SeekFrom};
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+use crate::cip::types::{CipInt, CipUdint, CipUint, CipUsint};
 
 #[derive(BinRead, BinWrite)]
 #[br(little, repr = CipUint)]
@@ -13,6 +18,7 @@ use crate::cip::types::CipUint;
 pub enum CommonPacketItemId {
     NullAddr = 0x0000,
     ListIdentity = 0x000C,
+    ListServices = 0x0100,
     ConnectionAddressItem = 0x00A1,
     ConnectedTransportPacket = 0x00B1,
     UnconnectedMessage = 0x00B2,
@@ -21,24 +27,320 @@ pub enum CommonPacketItemId {
     SequencedAddressItem = 0x8002,
 }
 
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct RawPacketItem {
+    // Kept as a raw value so that unrecognized item types can still be read
+    pub type_id: CipUint,
+
+    #[bw(calc = data.len() as CipUint)]
+    pub _item_length: CipUint,
+
+    #[br(count = _item_length)]
+    pub data: Vec<CipUsint>,
+}
+
 #[binrw]
 #[brw(little)]
 #[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SequencedAddress {
+    pub connection_identifier: CipUdint,
+    pub encapsulation_sequence_number: CipUdint,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CommonPacketItem {
+    NullAddress,
+    ConnectedAddress(CipUdint),
+    SequencedAddress(SequencedAddress),
+    O2TSockAddrInfo(SockAddrInfo),
+    T2OSockAddrInfo(SockAddrInfo),
+
+    /*  The data items hold None when their payload is written separately (i.e. by an object assembly)
+    In that case, the item length is the packet length passed in when writing */
+    ConnectedTransportPacket(Option<Vec<CipUsint>>),
+    UnconnectedMessage(Option<Vec<CipUsint>>),
+
+    // Any item type that doesn't have a typed representation
+    Unknown(RawPacketItem),
+}
+
+// ======= Start of CommonPacketItem impl ========
+
+impl CommonPacketItem {
+    pub fn type_id(&self) -> CipUint {
+        match self {
+            Self::NullAddress => CommonPacketItemId::NullAddr as CipUint,
+            Self::ConnectedAddress(_) => CommonPacketItemId::ConnectionAddressItem as CipUint,
+            Self::SequencedAddress(_) => CommonPacketItemId::SequencedAddressItem as CipUint,
+            Self::ConnectedTransportPacket(_) => {
+                CommonPacketItemId::ConnectedTransportPacket as CipUint
+            }
+            Self::UnconnectedMessage(_) => CommonPacketItemId::UnconnectedMessage as CipUint,
+            Self::O2TSockAddrInfo(_) => CommonPacketItemId::O2TSockAddrInfo as CipUint,
+            Self::T2OSockAddrInfo(_) => CommonPacketItemId::T2OSockAddrInfo as CipUint,
+            Self::Unknown(raw_item) => raw_item.type_id,
+        }
+    }
+
+    fn from_raw_item(raw_item: RawPacketItem, endian: binrw::Endian) -> BinResult<Self> {
+        let mut data_reader = Cursor::new(&raw_item.data);
+
+        let packet_item = match raw_item.type_id {
+            type_id if type_id == CommonPacketItemId::NullAddr as CipUint => Self::NullAddress,
+            type_id if type_id == CommonPacketItemId::ConnectionAddressItem as CipUint => {
+                Self::ConnectedAddress(CipUdint::read_options(&mut data_reader, endian, ())?)
+            }
+            type_id if type_id == CommonPacketItemId::SequencedAddressItem as CipUint => {
+                Self::SequencedAddress(SequencedAddress::read_options(
+                    &mut data_reader,
+                    endian,
+                    (),
+                )?)
+            }
+            type_id if type_id == CommonPacketItemId::O2TSockAddrInfo as CipUint => {
+                Self::O2TSockAddrInfo(SockAddrInfo::read_options(&mut data_reader, endian, ())?)
+            }
+            type_id if type_id == CommonPacketItemId::T2OSockAddrInfo as CipUint => {
+                Self::T2OSockAddrInfo(SockAddrInfo::read_options(&mut data_reader, endian, ())?)
+            }
+            type_id if type_id == CommonPacketItemId::ConnectedTransportPacket as CipUint => {
+                Self::ConnectedTransportPacket(Some(raw_item.data))
+            }
+            type_id if type_id == CommonPacketItemId::UnconnectedMessage as CipUint => {
+                Self::UnconnectedMessage(Some(raw_item.data))
+            }
+            _ => Self::Unknown(raw_item),
+        };
+
+        Ok(packet_item)
+    }
+
+    fn write_payload(&self, endian: binrw::Endian) -> BinResult<Option<Vec<CipUsint>>> {
+        let mut payload_buffer = Vec::new();
+        let mut payload_writer = Cursor::new(&mut payload_buffer);
+
+        match self {
+            Self::NullAddress => {}
+            Self::ConnectedAddress(connection_identifier) => {
+                connection_identifier.write_options(&mut payload_writer, endian, ())?
+            }
+            Self::SequencedAddress(sequenced_address) => {
+                sequenced_address.write_options(&mut payload_writer, endian, ())?
+            }
+            Self::O2TSockAddrInfo(sock_addr_info) | Self::T2OSockAddrInfo(sock_addr_info) => {
+                sock_addr_info.write_options(&mut payload_writer, endian, ())?
+            }
+            Self::ConnectedTransportPacket(data) | Self::UnconnectedMessage(data) => {
+                return Ok(data.clone())
+            }
+            Self::Unknown(raw_item) => return Ok(Some(raw_item.data.clone())),
+        };
+
+        Ok(Some(payload_buffer))
+    }
+}
+
+impl ReadEndian for CommonPacketItem {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinRead for CommonPacketItem {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // Step 1: Read the type and length
+        let type_id = CipUint::read_options(reader, endian, args)?;
+        let item_length = CipUint::read_options(reader, endian, ())?;
+
+        // Step 2: A data item can end with its header when the payload is read separately, which
+        //  is only an error once something decodes the missing payload
+        let payload_start = reader.stream_position()?;
+        let stream_end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(payload_start))?;
+
+        if item_length > 0 && payload_start == stream_end {
+            match type_id {
+                type_id if type_id == CommonPacketItemId::ConnectedTransportPacket as CipUint => {
+                    return Ok(Self::ConnectedTransportPacket(None))
+                }
+                type_id if type_id == CommonPacketItemId::UnconnectedMessage as CipUint => {
+                    return Ok(Self::UnconnectedMessage(None))
+                }
+                _ => {}
+            }
+        }
+
+        // Step 3: Read the payload without interpreting it
+        let data = Vec::<CipUsint>::read_options(
+            reader,
+            endian,
+            binrw::VecArgs::builder()
+                .count(item_length as usize)
+                .finalize(),
+        )?;
+
+        // Step 4: Interpret the payload based on the item type
+        Self::from_raw_item(RawPacketItem { type_id, data }, endian)
+    }
+}
+
+impl WriteEndian for CommonPacketItem {
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl BinWrite for CommonPacketItem {
+    // The length of a payload that is written separately
+    type Args<'a> = (Option<u16>,);
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        // Step 1: Serialize the payload of the item
+        let payload = self.write_payload(endian)?;
+
+        // Step 2: Calculate the item length (if there isn't a payload or provided size, then just write 0)
+        let item_length = match &payload {
+            Some(payload_bytes) => payload_bytes.len() as CipUint,
+            None => args.0.unwrap_or(0),
+        };
+
+        // Step 3: Write the full item
+        self.type_id().write_options(writer, endian, ())?;
+        item_length.write_options(writer, endian, ())?;
+
+        if let Some(payload_bytes) = payload {
+            if let Err(write_err) = writer.write(&payload_bytes) {
+                return Err(binrw::Error::Io(write_err));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of CommonPacketItem impl ^^^^^^^^
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq, Clone)]
 #[bw(import(provided_packet_length: Option<u16>))]
-pub struct CommonPacketDescriptor {
-    pub type_id: CommonPacketItemId,
+pub struct CommonPacketList {
+    #[bw(calc = items.len() as CipUint)]
+    pub _item_count: CipUint,
+
+    #[br(count = _item_count)]
+    #[bw(args(provided_packet_length))]
+    pub items: Vec<CommonPacketItem>,
+}
+
+// ======= Start of CommonPacketList impl ========
 
-    #[bw(args(provided_packet_length), write_with = descripter_length_writer)]
-    pub packet_length: Option<CipUint>,
+impl CommonPacketList {
+    pub fn new(items: Vec<CommonPacketItem>) -> Self {
+        CommonPacketList { items }
+    }
+
+    pub fn connected_address(&self) -> Option<CipUdint> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::ConnectedAddress(connection_identifier) => {
+                Some(*connection_identifier)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn sequenced_address(&self) -> Option<SequencedAddress> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::SequencedAddress(sequenced_address) => Some(*sequenced_address),
+            _ => None,
+        })
+    }
+
+    pub fn unconnected_message(&self) -> Option<&Vec<CipUsint>> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::UnconnectedMessage(Some(data)) => Some(data),
+            _ => None,
+        })
+    }
+
+    pub fn connected_transport_packet(&self) -> Option<&Vec<CipUsint>> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::ConnectedTransportPacket(Some(data)) => Some(data),
+            _ => None,
+        })
+    }
+
+    /// Whether a data item was read without its payload, see `CommonPacketItem::read_options`
+    pub fn has_missing_payload(&self) -> bool {
+        self.items.iter().any(|item| {
+            matches!(
+                item,
+                CommonPacketItem::ConnectedTransportPacket(None)
+                    | CommonPacketItem::UnconnectedMessage(None)
+            )
+        })
+    }
+
+    pub fn o2t_socket_address(&self) -> Option<SocketAddrV4> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::O2TSockAddrInfo(sock_addr_info) => Some((*sock_addr_info).into()),
+            _ => None,
+        })
+    }
+
+    pub fn t2o_socket_address(&self) -> Option<SocketAddrV4> {
+        self.items.iter().find_map(|item| match item {
+            CommonPacketItem::T2OSockAddrInfo(sock_addr_info) => Some((*sock_addr_info).into()),
+            _ => None,
+        })
+    }
 }
 
-// ======= Start of CommonPacketDescriptor impl ========
+// ^^^^^^^^ End of CommonPacketList impl ^^^^^^^^
+
+// The only address family used by the socket address items
+pub const AF_INET: CipInt = 2;
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SockAddrInfo {
+    // NOTE: Unlike the rest of the encapsulation layer, the socket address fields are big endian
+    pub sin_family: CipInt,
+    pub sin_port: CipUint,
+    pub sin_addr: CipUdint,
+    pub sin_zero: [CipUsint; 8],
+}
+
+// ======= Start of SockAddrInfo impl ========
+
+impl From<SocketAddrV4> for SockAddrInfo {
+    fn from(socket_address: SocketAddrV4) -> Self {
+        SockAddrInfo {
+            sin_family: AF_INET,
+            sin_port: socket_address.port(),
+            sin_addr: u32::from(*socket_address.ip()),
+            sin_zero: [0x00; 8],
+        }
+    }
+}
 
-#[binrw::writer(writer: writer, endian)]
-fn descripter_length_writer(_obj: &Option<CipUint>, arg0: Option<u16>) -> binrw::BinResult<()> {
-    // If there isn't an input argument size, then just write 0
-    let write_value = arg0.unwrap_or(0);
-    write_value.write_options(writer, endian, ())
+impl From<SockAddrInfo> for SocketAddrV4 {
+    fn from(sock_addr_info: SockAddrInfo) -> Self {
+        SocketAddrV4::new(
+            Ipv4Addr::from(sock_addr_info.sin_addr),
+            sock_addr_info.sin_port,
+        )
+    }
 }
 
-// ^^^^^^^^ End of CommonPacketDescriptor impl ^^^^^^^^
+// ^^^^^^^^ End of SockAddrInfo impl ^^^^^^^^
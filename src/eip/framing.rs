@@ -0,0 +1,206 @@
+use std::io::Read;
+
+use binrw::BinRead;
+
+#[cfg(feature = "tokio")]
+use binrw::BinWrite;
+#[cfg(feature = "tokio")]
+use bytes::{BufMut, BytesMut};
+#[cfg(feature = "tokio")]
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::error::{EipError, EipResult};
+#[cfg(feature = "tokio")]
+use crate::object_assembly::{ConnectedRequestObjectAssembly, RequestObjectAssembly};
+
+use super::command::EnIpCommand;
+use super::constants::{ENCAPSULATION_HEADER_SIZE, SENDER_CONTEXT_SIZE};
+use super::packet::{EnIpPacketDescription, EncapsulationHeader};
+use crate::cip::types::{CipUdint, CipUint};
+
+// The header length field is 16 bits, so no frame can be larger than this
+pub const DEFAULT_MAX_FRAME_SIZE: usize = ENCAPSULATION_HEADER_SIZE + u16::MAX as usize;
+
+// The length field sits right after the 2 byte command
+fn frame_size(header_bytes: &[u8]) -> usize {
+    let payload_length = u16::from_le_bytes([header_bytes[2], header_bytes[3]]) as usize;
+
+    ENCAPSULATION_HEADER_SIZE + payload_length
+}
+
+// The sender context follows the command, length, session handle and status fields
+const SENDER_CONTEXT_OFFSET: usize = 12;
+
+/// The sender context of a whole encapsulation message, which a device echoes back in its reply
+pub fn sender_context(frame: &[u8]) -> [u8; SENDER_CONTEXT_SIZE] {
+    let mut sender_context = [0x00; SENDER_CONTEXT_SIZE];
+    sender_context.copy_from_slice(
+        &frame[SENDER_CONTEXT_OFFSET..SENDER_CONTEXT_OFFSET + SENDER_CONTEXT_SIZE],
+    );
+
+    sender_context
+}
+
+/// The connection ID and sequence count of a whole SendUnitData message. Connected replies are
+///  matched to their request by these (see `ExplicitConnection::is_reply_to`) since the sender
+///  context of SendUnitData doesn't have to be echoed
+pub fn connected_sequence(frame: &[u8]) -> Option<(CipUdint, CipUint)> {
+    let packet_description = EnIpPacketDescription::read(&mut std::io::Cursor::new(frame)).ok()?;
+
+    if packet_description.header.command != EnIpCommand::SendUnitData {
+        return None;
+    }
+
+    let packet_items = packet_description.command_specific_data.packet_items()?;
+    let transport_bytes = packet_items.connected_transport_packet()?;
+
+    match transport_bytes.as_slice() {
+        [sequence_low, sequence_high, ..] => Some((
+            packet_items.connected_address()?,
+            CipUint::from_le_bytes([*sequence_low, *sequence_high]),
+        )),
+        _ => None,
+    }
+}
+
+/// Fails with the encapsulation error of a whole encapsulation message. Devices may drop the
+///  command specific data from an error reply, so this is checked before parsing the rest
+pub fn check_frame_status(frame: &[u8]) -> EipResult<()> {
+    let header = EncapsulationHeader::read(&mut std::io::Cursor::new(frame))?;
+
+    match EipError::from_encapsulation_status(header.status_code, header.session_handle) {
+        Some(encapsulation_err) => Err(encapsulation_err),
+        None => Ok(()),
+    }
+}
+
+fn check_frame_size(frame_size: usize, max_frame_size: usize) -> EipResult<()> {
+    if frame_size > max_frame_size {
+        return Err(EipError::FrameTooLarge {
+            frame_size,
+            max_frame_size,
+        });
+    }
+
+    Ok(())
+}
+
+// Splits a TCP byte stream into whole encapsulation messages (header included). The tokio codec
+//  traits need the `tokio` feature, `read_frame` is the blocking equivalent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncapsulationCodec {
+    max_frame_size: usize,
+}
+
+// ======= Start of EncapsulationCodec impl ========
+
+impl EncapsulationCodec {
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        EncapsulationCodec { max_frame_size }
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+}
+
+impl Default for EncapsulationCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Decoder for EncapsulationCodec {
+    type Item = BytesMut;
+    type Error = EipError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < ENCAPSULATION_HEADER_SIZE {
+            src.reserve(ENCAPSULATION_HEADER_SIZE - src.len());
+            return Ok(None);
+        }
+
+        let frame_size = frame_size(&src[..ENCAPSULATION_HEADER_SIZE]);
+        check_frame_size(frame_size, self.max_frame_size)?;
+
+        if src.len() < frame_size {
+            src.reserve(frame_size - src.len());
+            return Ok(None);
+        }
+
+        // Any bytes past this frame stay in `src` for the next call
+        Ok(Some(src.split_to(frame_size)))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> Encoder<RequestObjectAssembly<T>> for EncapsulationCodec
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Error = EipError;
+
+    fn encode(
+        &mut self,
+        item: RequestObjectAssembly<T>,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        self.encode_frame(item, dst)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> Encoder<ConnectedRequestObjectAssembly<T>> for EncapsulationCodec
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Error = EipError;
+
+    fn encode(
+        &mut self,
+        item: ConnectedRequestObjectAssembly<T>,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        self.encode_frame(item, dst)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl EncapsulationCodec {
+    fn encode_frame<F>(&self, item: F, dst: &mut BytesMut) -> EipResult<()>
+    where
+        F: for<'a> BinWrite<Args<'a> = ()> + binrw::meta::WriteEndian,
+    {
+        let mut frame_bytes: Vec<u8> = Vec::new();
+        let mut writer = std::io::Cursor::new(&mut frame_bytes);
+
+        item.write(&mut writer)?;
+
+        check_frame_size(frame_bytes.len(), self.max_frame_size)?;
+
+        dst.put_slice(&frame_bytes);
+
+        Ok(())
+    }
+}
+
+// ^^^^^^^^ End of EncapsulationCodec impl ^^^^^^^^
+
+/// Reads exactly one encapsulation message (header included) from a blocking reader
+pub fn read_frame<R: Read>(reader: &mut R, max_frame_size: usize) -> EipResult<Vec<u8>> {
+    let mut frame_bytes = vec![0; ENCAPSULATION_HEADER_SIZE];
+    reader.read_exact(&mut frame_bytes)?;
+
+    let frame_size = frame_size(&frame_bytes);
+    check_frame_size(frame_size, max_frame_size)?;
+
+    frame_bytes.resize(frame_size, 0);
+    reader.read_exact(&mut frame_bytes[ENCAPSULATION_HEADER_SIZE..])?;
+
+    Ok(frame_bytes)
+}
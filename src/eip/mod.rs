@@ -1,4 +1,5 @@
 pub mod command;
 pub mod constants;
 pub mod description;
+pub mod framing;
 pub mod packet;
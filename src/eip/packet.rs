@@ -6,6 +6,7 @@ use binrw::{
 };
 
 use crate::cip::types::{CipByte, CipUdint, CipUint};
+use crate::error::{EipError, EipResult};
 
 use super::command::{CommandSpecificData, EnIpCommand, EncapsStatusCode, RegisterData};
 use super::constants as eip_constants;
@@ -40,9 +41,9 @@ fn header_length_writer(_obj: &Option<CipUint>, arg0: u16) -> binrw::BinResult<(
 pub struct EnIpPacketDescription {
     pub header: EncapsulationHeader,
 
-    #[br(args(header.command))]
+    #[br(args(header.command, header.length.unwrap_or_default()))]
     pub command_specific_data: CommandSpecificData,
-    /* Passes the command field of the header to the command_specific_data field for binary reading */
+    /* Passes the command and length fields of the header to the command_specific_data field for binary reading */
 }
 
 // ======= Start of EnIpPacketDescription impl ========
@@ -86,6 +87,30 @@ impl EnIpPacketDescription {
         )
     }
 
+    pub fn new_list_identity_description() -> Self {
+        EnIpPacketDescription::new(
+            EnIpCommand::ListIdentity,
+            0,
+            CommandSpecificData::new_list_identity(),
+        )
+    }
+
+    pub fn new_list_services_description() -> Self {
+        EnIpPacketDescription::new(
+            EnIpCommand::ListServices,
+            0,
+            CommandSpecificData::new_list_services(),
+        )
+    }
+
+    pub fn new_list_interfaces_description() -> Self {
+        EnIpPacketDescription::new(
+            EnIpCommand::ListInterfaces,
+            0,
+            CommandSpecificData::new_list_interfaces(),
+        )
+    }
+
     pub fn new_cip_description(session_handle: CipUdint, timeout: CipUint) -> Self {
         EnIpPacketDescription::new(
             EnIpCommand::SendRrData,
@@ -93,6 +118,28 @@ impl EnIpPacketDescription {
             CommandSpecificData::new_request(0, timeout),
         )
     }
+
+    pub fn new_connected_description(
+        session_handle: CipUdint,
+        connection_identifier: CipUdint,
+    ) -> Self {
+        EnIpPacketDescription::new(
+            EnIpCommand::SendUnitData,
+            session_handle,
+            CommandSpecificData::new_connected_request(connection_identifier),
+        )
+    }
+
+    /// Fails with the encapsulation error if the header status isn't success
+    pub fn check_status(&self) -> EipResult<()> {
+        match EipError::from_encapsulation_status(
+            self.header.status_code,
+            self.header.session_handle,
+        ) {
+            Some(encapsulation_err) => Err(encapsulation_err),
+            None => Ok(()),
+        }
+    }
 }
 
 impl WriteEndian for EnIpPacketDescription {
@@ -113,24 +160,15 @@ impl BinWrite for EnIpPacketDescription {
         let mut temp_buffer = Vec::new();
         let mut temp_writer = std::io::Cursor::new(&mut temp_buffer);
 
-        let data_write_result =
-            self.command_specific_data
-                .write_options(&mut temp_writer, endian, args);
-
-        if let Err(write_err) = data_write_result {
-            return Err(write_err);
-        };
+        self.command_specific_data
+            .write_options(&mut temp_writer, endian, args)?;
 
         // Step 2: Calculate the total data size after header
         let full_proceeding_data_length = (temp_buffer.len() as u16) + args.0;
 
         // Step 3: Write the full struct to the actual writer
-        if let Err(write_err) =
-            self.header
-                .write_options(writer, endian, (full_proceeding_data_length,))
-        {
-            return Err(write_err);
-        }
+        self.header
+            .write_options(writer, endian, (full_proceeding_data_length,))?;
 
         if let Err(write_err) = writer.write(&temp_buffer) {
             return Err(binrw::Error::Io(write_err));
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::io::ErrorKind;
+
+use crate::cip::message::response::ResponseStatusCode;
+use crate::cip::message::shared::ServiceCode;
+use crate::cip::path::CipPath;
+use crate::cip::types::{CipUdint, CipUint, CipUsint};
+use crate::eip::command::EncapsStatusCode;
+
+pub type EipResult<T> = Result<T, EipError>;
+
+#[derive(Debug)]
+pub enum EipError {
+    // The connection to the device failed or was dropped
+    Io(std::io::Error),
+    // The bytes received could not be parsed as the expected packet
+    MalformedFrame(binrw::Error),
+    // The encapsulation header announced a frame larger than the configured maximum
+    FrameTooLarge {
+        frame_size: usize,
+        max_frame_size: usize,
+    },
+    // The encapsulation header of the reply had a non-success status
+    Encapsulation(EncapsStatusCode),
+    // The device processed the request and rejected it
+    Cip {
+        service: ServiceCode,
+        path: Option<CipPath>,
+        general_status: ResponseStatusCode,
+        additional_status: Vec<CipUint>,
+    },
+    // A bridge couldn't deliver an Unconnected_Send request along its route
+    Routing {
+        general_status: ResponseStatusCode,
+        additional_status: Vec<CipUint>,
+        // Words of the route that were left when routing failed
+        remaining_path_size: Option<CipUsint>,
+    },
+    // The device doesn't recognize the session handle (e.g. after the device restarted)
+    SessionInvalid(CipUdint),
+    Timeout,
+}
+
+// ======= Start of EipError impl ========
+
+impl EipError {
+    /// The object specific extended status of a CIP error
+    pub fn extended_status(&self) -> Option<CipUint> {
+        match self {
+            EipError::Cip {
+                additional_status, ..
+            } => additional_status.first().copied(),
+            _ => None,
+        }
+    }
+
+    /// Maps a non-success encapsulation status onto its error
+    pub fn from_encapsulation_status(
+        status_code: EncapsStatusCode,
+        session_handle: CipUdint,
+    ) -> Option<Self> {
+        match status_code {
+            EncapsStatusCode::Success => None,
+            EncapsStatusCode::InvalidSessionHandle => {
+                Some(EipError::SessionInvalid(session_handle))
+            }
+            _ => Some(EipError::Encapsulation(status_code)),
+        }
+    }
+}
+
+impl fmt::Display for EipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EipError::Io(io_err) => write!(f, "I/O failure: {}", io_err),
+            EipError::MalformedFrame(parse_err) => write!(f, "malformed frame: {}", parse_err),
+            EipError::FrameTooLarge {
+                frame_size,
+                max_frame_size,
+            } => write!(
+                f,
+                "{} byte frame exceeds the {} byte maximum",
+                frame_size, max_frame_size
+            ),
+            EipError::Encapsulation(EncapsStatusCode::Unknown(status_code)) => {
+                write!(f, "encapsulation status {:#010x}", status_code)
+            }
+            EipError::Encapsulation(status_code) => {
+                write!(f, "encapsulation status {:?}", status_code)
+            }
+            EipError::Cip {
+                service,
+                path,
+                general_status,
+                additional_status,
+            } => {
+                write!(f, "{:?} failed with {:?}", service, general_status)?;
+
+                if let Some(path) = path {
+                    write!(f, " on {}", path)?;
+                }
+
+                if !additional_status.is_empty() {
+                    write!(f, " (additional status {:04x?})", additional_status)?;
+                }
+
+                Ok(())
+            }
+            EipError::Routing {
+                general_status,
+                additional_status,
+                remaining_path_size,
+            } => {
+                write!(f, "routing failed with {:?}", general_status)?;
+
+                if !additional_status.is_empty() {
+                    write!(f, " (additional status {:04x?})", additional_status)?;
+                }
+
+                if let Some(remaining_path_size) = remaining_path_size {
+                    write!(f, " with {} route words remaining", remaining_path_size)?;
+                }
+
+                Ok(())
+            }
+            EipError::SessionInvalid(session_handle) => {
+                write!(f, "session 0x{:08x} is invalid", session_handle)
+            }
+            EipError::Timeout => write!(f, "timed out waiting for a reply"),
+        }
+    }
+}
+
+impl std::error::Error for EipError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EipError::Io(io_err) => Some(io_err),
+            EipError::MalformedFrame(parse_err) => Some(parse_err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for EipError {
+    fn from(io_err: std::io::Error) -> Self {
+        match io_err.kind() {
+            // Blocking sockets report an elapsed read timeout as either kind depending on the platform
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => EipError::Timeout,
+            _ => EipError::Io(io_err),
+        }
+    }
+}
+
+impl From<binrw::Error> for EipError {
+    fn from(parse_err: binrw::Error) -> Self {
+        EipError::MalformedFrame(parse_err)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl From<tokio::time::error::Elapsed> for EipError {
+    fn from(_: tokio::time::error::Elapsed) -> Self {
+        EipError::Timeout
+    }
+}
+
+// ^^^^^^^^ End of EipError impl ^^^^^^^^
+
+// A successful reply without the data the service should have returned
+pub(crate) fn require_reply_data<R>(reply_data: Option<R>) -> EipResult<R> {
+    reply_data.ok_or_else(|| {
+        EipError::MalformedFrame(binrw::Error::AssertFail {
+            pos: 0,
+            message: "the reply is missing the requested data".to_string(),
+        })
+    })
+}
@@ -0,0 +1,486 @@
+use std::future::Future;
+use std::io::Cursor;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use binrw::{BinRead, BinWrite};
+
+use tokio::net::UdpSocket;
+use tokio::sync::{broadcast, watch};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::MissedTickBehavior;
+
+use super::packet::{ImplicitIoPacket, RunIdleHeader};
+use super::watchdog::{self, ConnectionEvent, ConnectionWatchdog};
+use crate::cip::connection_manager::{ForwardOpenRequest, ForwardOpenSuccess, OpenedConnection};
+use crate::cip::types::{CipUdint, CipUint, CipUsint};
+use crate::eip::constants as eip_constants;
+use crate::eip::description::{CommonPacketList, SequencedAddress};
+
+// Large_Forward_Open connections can be bigger than a single ethernet frame
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+const EVENT_CHANNEL_CAPACITY: usize = 16;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImplicitConnectionConfig {
+    // Both directions use the same socket, normally bound to UDP 2222
+    pub local_address: SocketAddr,
+    pub target_address: SocketAddr,
+    pub o2t_connection_id: CipUdint,
+    pub t2o_connection_id: CipUdint,
+    pub o2t_api: Duration,
+    pub t2o_api: Duration,
+    // Should match the multiplier sent in the Forward_Open (0 is the smallest, RPI x 4)
+    pub connection_timeout_multiplier: CipUsint,
+    pub o2t_run_idle_header: bool,
+    pub t2o_run_idle_header: bool,
+    pub t2o_multicast_address: Option<Ipv4Addr>,
+    // T->O datagrams from any other address are dropped
+    pub t2o_source_ip: IpAddr,
+}
+
+// ======= Start of ImplicitConnectionConfig impl ========
+
+impl ImplicitConnectionConfig {
+    /// Creates the configuration for the connection opened by a successful Forward_Open
+    pub fn new<P>(
+        target_ip: Ipv4Addr,
+        forward_open_request: &ForwardOpenRequest<P>,
+        forward_open: &ForwardOpenSuccess,
+    ) -> Self
+    where
+        P: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        ImplicitConnectionConfig {
+            local_address: SocketAddrV4::new(
+                Ipv4Addr::UNSPECIFIED,
+                eip_constants::IMPLICIT_IO_PORT,
+            )
+            .into(),
+            target_address: SocketAddrV4::new(target_ip, eip_constants::IMPLICIT_IO_PORT).into(),
+            o2t_connection_id: forward_open.o2t_connection_id,
+            t2o_connection_id: forward_open.t2o_connection_id,
+            o2t_api: Duration::from_micros(forward_open.o2t_api.into()),
+            t2o_api: Duration::from_micros(forward_open.t2o_api.into()),
+            connection_timeout_multiplier: forward_open_request.connection_timeout_multiplier,
+            o2t_run_idle_header: true,
+            t2o_run_idle_header: false,
+            t2o_multicast_address: None,
+            t2o_source_ip: target_ip.into(),
+        }
+    }
+
+    /// Switches to the connection IDs and APIs of a new Forward_Open
+    pub fn apply_forward_open(&mut self, forward_open: &ForwardOpenSuccess) {
+        self.o2t_connection_id = forward_open.o2t_connection_id;
+        self.t2o_connection_id = forward_open.t2o_connection_id;
+        self.o2t_api = Duration::from_micros(forward_open.o2t_api.into());
+        self.t2o_api = Duration::from_micros(forward_open.t2o_api.into());
+    }
+
+    /// How long the T->O direction can go without a consumed packet
+    pub fn connection_timeout(&self) -> Duration {
+        watchdog::connection_timeout(self.t2o_api, self.connection_timeout_multiplier)
+    }
+
+    /// Switches to a new Forward_Open and the socket address items sent with it
+    pub fn apply_opened_connection(&mut self, opened_connection: &OpenedConnection) {
+        self.apply_forward_open(&opened_connection.forward_open);
+        self.apply_socket_addresses(
+            opened_connection.o2t_socket_address,
+            opened_connection.t2o_socket_address,
+        );
+    }
+
+    /// Applies the socket address items a target can add to the Forward_Open reply
+    pub fn with_socket_address_items(mut self, packet_items: &CommonPacketList) -> Self {
+        self.apply_socket_addresses(
+            packet_items.o2t_socket_address(),
+            packet_items.t2o_socket_address(),
+        );
+
+        self
+    }
+
+    // A multicast T->O address is joined, a unicast one is where the T->O data comes from
+    fn apply_socket_addresses(
+        &mut self,
+        o2t_socket_address: Option<SocketAddrV4>,
+        t2o_socket_address: Option<SocketAddrV4>,
+    ) {
+        if let Some(o2t_socket_address) = o2t_socket_address {
+            self.target_address = o2t_socket_address.into();
+        }
+
+        if let Some(t2o_socket_address) = t2o_socket_address {
+            match t2o_socket_address.ip().is_multicast() {
+                true => self.t2o_multicast_address = Some(*t2o_socket_address.ip()),
+                false => self.t2o_source_ip = (*t2o_socket_address.ip()).into(),
+            }
+        }
+    }
+}
+
+// ^^^^^^^^ End of ImplicitConnectionConfig impl ^^^^^^^^
+
+/// Called after the connection timed out to open a replacement connection with a new Forward_Open
+pub type ReconnectHandler =
+    Box<dyn FnMut() -> Pin<Box<dyn Future<Output = Option<OpenedConnection>> + Send>> + Send>;
+
+// Everything the supervisor hands to each (re)started producer and consumer
+struct ConnectionChannels<I, O> {
+    socket: Arc<UdpSocket>,
+    input_sender: Arc<watch::Sender<Option<I>>>,
+    output_receiver: watch::Receiver<O>,
+    run_mode: Arc<AtomicBool>,
+    timed_out: Arc<AtomicBool>,
+    event_sender: broadcast::Sender<ConnectionEvent>,
+    config_sender: watch::Sender<ImplicitConnectionConfig>,
+}
+
+/// A running Class 1 connection that produces the output assembly every O->T API and
+///  keeps the latest consumed input assembly. Dropping it stops both directions.
+pub struct ImplicitConnection<I, O> {
+    config_receiver: watch::Receiver<ImplicitConnectionConfig>,
+    local_address: SocketAddr,
+    input_receiver: watch::Receiver<Option<I>>,
+    output_sender: watch::Sender<O>,
+    run_mode: Arc<AtomicBool>,
+    timed_out: Arc<AtomicBool>,
+    event_sender: broadcast::Sender<ConnectionEvent>,
+    supervisor_task: JoinHandle<()>,
+}
+
+// ======= Start of ImplicitConnection impl ========
+
+impl<I, O> ImplicitConnection<I, O>
+where
+    I: for<'a> BinRead<Args<'a> = ()> + Send + Sync + 'static,
+    O: for<'a> BinWrite<Args<'a> = ()> + Send + Sync + 'static,
+{
+    pub async fn start(
+        config: ImplicitConnectionConfig,
+        initial_output: O,
+    ) -> std::io::Result<Self> {
+        Self::start_with_reconnect(config, initial_output, None).await
+    }
+
+    /// Starts the connection, calling `reconnect_handler` for a new Forward_Open whenever the
+    ///  connection times out. Without a handler the connection stops once it times out.
+    pub async fn start_with_reconnect(
+        config: ImplicitConnectionConfig,
+        initial_output: O,
+        reconnect_handler: Option<ReconnectHandler>,
+    ) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(config.local_address).await?;
+
+        if let Some(multicast_address) = config.t2o_multicast_address {
+            socket.join_multicast_v4(multicast_address, Ipv4Addr::UNSPECIFIED)?;
+        }
+
+        let local_address = socket.local_addr()?;
+
+        let (input_sender, input_receiver) = watch::channel(None);
+        let (output_sender, output_receiver) = watch::channel(initial_output);
+        let (event_sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let (config_sender, config_receiver) = watch::channel(config.clone());
+        let run_mode = Arc::new(AtomicBool::new(true));
+        let timed_out = Arc::new(AtomicBool::new(false));
+
+        let connection_channels = ConnectionChannels {
+            socket: Arc::new(socket),
+            input_sender: Arc::new(input_sender),
+            output_receiver,
+            run_mode: run_mode.clone(),
+            timed_out: timed_out.clone(),
+            event_sender: event_sender.clone(),
+            config_sender,
+        };
+
+        let supervisor_task = tokio::spawn(supervise_connection(
+            config,
+            connection_channels,
+            reconnect_handler,
+        ));
+
+        Ok(ImplicitConnection {
+            config_receiver,
+            local_address,
+            input_receiver,
+            output_sender,
+            run_mode,
+            timed_out,
+            event_sender,
+            supervisor_task,
+        })
+    }
+}
+
+impl<I, O> ImplicitConnection<I, O> {
+    /// The current configuration, including the connection IDs of the latest reconnect
+    pub fn config(&self) -> ImplicitConnectionConfig {
+        self.config_receiver.borrow().clone()
+    }
+
+    pub fn local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    /// The most recently consumed input assembly
+    pub fn latest_input(&self) -> Option<I>
+    where
+        I: Clone,
+    {
+        self.input_receiver.borrow().clone()
+    }
+
+    /// A receiver that can wait for the next input assembly to be consumed
+    pub fn input_receiver(&self) -> watch::Receiver<Option<I>> {
+        self.input_receiver.clone()
+    }
+
+    /// Replaces the output assembly sent on the next O->T production
+    pub fn write_output(&self, output: O) {
+        self.output_sender.send_replace(output);
+    }
+
+    /// Modifies the output assembly in place, e.g. to toggle a single digital output
+    pub fn modify_output(&self, modify: impl FnOnce(&mut O)) {
+        self.output_sender.send_modify(modify);
+    }
+
+    /// Sets the run/idle header of the produced outputs. Adapters ignore outputs while idle.
+    pub fn set_run_mode(&self, run: bool) {
+        self.run_mode.store(run, Ordering::Relaxed);
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out.load(Ordering::Relaxed)
+    }
+
+    /// Subscribes to the watchdog events raised after this call
+    pub fn events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.event_sender.subscribe()
+    }
+}
+
+impl<I, O> Drop for ImplicitConnection<I, O> {
+    fn drop(&mut self) {
+        // Aborting the supervisor also aborts the producer and consumer it started
+        self.supervisor_task.abort();
+    }
+}
+
+// ^^^^^^^^ End of ImplicitConnection impl ^^^^^^^^
+
+fn write_output_packet<O>(
+    config: &ImplicitConnectionConfig,
+    encapsulation_sequence_number: CipUdint,
+    sequence_count: CipUint,
+    run: bool,
+    output: &O,
+) -> binrw::BinResult<Vec<u8>>
+where
+    O: for<'a> BinWrite<Args<'a> = ()>,
+{
+    let mut output_bytes: Vec<u8> = Vec::new();
+    output.write_le(&mut Cursor::new(&mut output_bytes))?;
+
+    let output_packet = ImplicitIoPacket {
+        sequenced_address: SequencedAddress {
+            connection_identifier: config.o2t_connection_id,
+            encapsulation_sequence_number,
+        },
+        sequence_count,
+        run_idle_header: config.o2t_run_idle_header.then(|| RunIdleHeader::new(run)),
+        data: output_bytes,
+    };
+
+    let mut packet_bytes: Vec<u8> = Vec::new();
+    output_packet.write(&mut Cursor::new(&mut packet_bytes))?;
+
+    Ok(packet_bytes)
+}
+
+async fn produce_outputs<O>(
+    socket: Arc<UdpSocket>,
+    config: ImplicitConnectionConfig,
+    output_receiver: watch::Receiver<O>,
+    run_mode: Arc<AtomicBool>,
+) where
+    O: for<'a> BinWrite<Args<'a> = ()>,
+{
+    let mut api_interval = tokio::time::interval(config.o2t_api);
+    api_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut encapsulation_sequence_number: CipUdint = 0;
+    let mut sequence_count: CipUint = 0;
+
+    loop {
+        api_interval.tick().await;
+
+        encapsulation_sequence_number = encapsulation_sequence_number.wrapping_add(1);
+        sequence_count = sequence_count.wrapping_add(1);
+
+        // The borrowed output can't be held across the send
+        let packet_bytes = write_output_packet(
+            &config,
+            encapsulation_sequence_number,
+            sequence_count,
+            run_mode.load(Ordering::Relaxed),
+            &*output_receiver.borrow(),
+        );
+
+        // An output assembly that can't be written never will be, so stop producing
+        let Ok(packet_bytes) = packet_bytes else {
+            return;
+        };
+
+        // Lost datagrams are expected, the next production replaces this one anyway
+        let _ = socket.send_to(&packet_bytes, config.target_address).await;
+    }
+}
+
+async fn consume_inputs<I>(
+    socket: Arc<UdpSocket>,
+    config: ImplicitConnectionConfig,
+    input_sender: Arc<watch::Sender<Option<I>>>,
+    watchdog: Arc<Mutex<ConnectionWatchdog>>,
+) where
+    I: for<'a> BinRead<Args<'a> = ()>,
+{
+    let mut receive_buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+    let mut last_encapsulation_sequence_number: Option<CipUdint> = None;
+
+    loop {
+        // Errors like an ICMP port unreachable tend to repeat, so wait for the next expected
+        //  packet instead of spinning on them. The watchdog times out if they never clear
+        let Ok((bytes_received, source_address)) = socket.recv_from(&mut receive_buffer).await
+        else {
+            tokio::time::sleep(config.t2o_api).await;
+            continue;
+        };
+
+        if source_address.ip() != config.t2o_source_ip {
+            continue;
+        }
+
+        // Anything that isn't a well-formed packet for this connection is ignored
+        let Ok(input_packet) = ImplicitIoPacket::<I>::read_options(
+            &mut Cursor::new(&receive_buffer[..bytes_received]),
+            binrw::Endian::Little,
+            (config.t2o_run_idle_header,),
+        ) else {
+            continue;
+        };
+
+        let sequenced_address = input_packet.sequenced_address;
+
+        if sequenced_address.connection_identifier != config.t2o_connection_id {
+            continue;
+        }
+
+        // Drop duplicated and out of order packets, accounting for the sequence number wrapping
+        if let Some(last_sequence_number) = last_encapsulation_sequence_number {
+            let sequence_difference = sequenced_address
+                .encapsulation_sequence_number
+                .wrapping_sub(last_sequence_number) as i32;
+
+            if sequence_difference <= 0 {
+                continue;
+            }
+        }
+
+        last_encapsulation_sequence_number = Some(sequenced_address.encapsulation_sequence_number);
+
+        if let Ok(mut watchdog) = watchdog.lock() {
+            watchdog.packet_consumed(Instant::now());
+        }
+
+        input_sender.send_replace(Some(input_packet.data));
+    }
+}
+
+async fn supervise_connection<I, O>(
+    mut config: ImplicitConnectionConfig,
+    channels: ConnectionChannels<I, O>,
+    mut reconnect_handler: Option<ReconnectHandler>,
+) where
+    I: for<'a> BinRead<Args<'a> = ()> + Send + Sync + 'static,
+    O: for<'a> BinWrite<Args<'a> = ()> + Send + Sync + 'static,
+{
+    loop {
+        let watchdog = Arc::new(Mutex::new(ConnectionWatchdog::new(
+            config.connection_timeout(),
+            Instant::now(),
+        )));
+
+        // Dropping the join set aborts both tasks
+        let mut connection_tasks = JoinSet::new();
+        connection_tasks.spawn(produce_outputs(
+            channels.socket.clone(),
+            config.clone(),
+            channels.output_receiver.clone(),
+            channels.run_mode.clone(),
+        ));
+        connection_tasks.spawn(consume_inputs(
+            channels.socket.clone(),
+            config.clone(),
+            channels.input_sender.clone(),
+            watchdog.clone(),
+        ));
+
+        // Sleep until the deadline, which moves forward every time a packet is consumed
+        loop {
+            let Ok(deadline) = watchdog.lock().map(|watchdog| watchdog.deadline()) else {
+                return;
+            };
+
+            if Instant::now() >= deadline {
+                break;
+            }
+
+            tokio::time::sleep_until(deadline.into()).await;
+        }
+
+        drop(connection_tasks);
+
+        channels.timed_out.store(true, Ordering::Relaxed);
+        let _ = channels.event_sender.send(ConnectionEvent::TimedOut);
+
+        let Some(reconnect) = reconnect_handler.as_mut() else {
+            return;
+        };
+
+        match reconnect().await {
+            Some(opened_connection) => {
+                let previous_multicast_address = config.t2o_multicast_address;
+                config.apply_opened_connection(&opened_connection);
+
+                // The replacement connection can produce to a different multicast group
+                if let Some(multicast_address) =
+                    config.t2o_multicast_address.filter(|&multicast_address| {
+                        Some(multicast_address) != previous_multicast_address
+                    })
+                {
+                    let _ = channels
+                        .socket
+                        .join_multicast_v4(multicast_address, Ipv4Addr::UNSPECIFIED);
+                }
+
+                channels.config_sender.send_replace(config.clone());
+
+                channels.timed_out.store(false, Ordering::Relaxed);
+                let _ = channels.event_sender.send(ConnectionEvent::Reconnected);
+            }
+            None => {
+                let _ = channels.event_sender.send(ConnectionEvent::ReconnectFailed);
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "tokio")]
+pub mod connection;
+pub mod packet;
+pub mod watchdog;
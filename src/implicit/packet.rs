@@ -0,0 +1,135 @@
+use bilge::prelude::{bitsize, u31, Bitsized, DebugBits, FromBits, Number};
+
+use binrw::meta::WriteEndian;
+use binrw::{BinRead, BinWrite};
+
+use crate::cip::types::CipUint;
+use crate::eip::description::{CommonPacketItem, CommonPacketList, SequencedAddress};
+
+#[bitsize(32)]
+#[derive(FromBits, PartialEq, DebugBits, BinRead, BinWrite, Copy, Clone)]
+#[br(map = u32::into)]
+#[bw(map = |&x| u32::from(x))]
+pub struct RunIdleHeader {
+    pub run: bool,
+    pub reserved: u31,
+}
+
+/*
+Implicit I/O packets are a common packet list sent over UDP without an encapsulation header
+
+Common Packet Format: Sequenced Address Item, Connected Data Item
+    Item Count: 2
+        Type ID: Sequenced Address Item (0x8002)
+            Length: 8
+            Connection ID: 0x0a0b0c01
+            Encapsulation Sequence Number: 7
+        Type ID: Connected Data Item (0x00b1)
+            Length: 10
+            CIP Sequence Count: 7
+            32-bit Header: 0x00000001 (Run)
+            Data: 01000000
+*/
+#[derive(Debug, PartialEq)]
+pub struct ImplicitIoPacket<T> {
+    pub sequenced_address: SequencedAddress,
+    pub sequence_count: CipUint,
+    // O->T packets usually carry the 32-bit run/idle header, T->O packets usually don't
+    pub run_idle_header: Option<RunIdleHeader>,
+    pub data: T,
+}
+
+// ======= Start of ImplicitIoPacket impl ========
+
+impl<T> WriteEndian for ImplicitIoPacket<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl<T> BinWrite for ImplicitIoPacket<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        // Step 1: Serialize the connected data item contents
+        let mut temp_buffer = Vec::new();
+        let mut temp_writer = std::io::Cursor::new(&mut temp_buffer);
+
+        self.sequence_count
+            .write_options(&mut temp_writer, endian, args)?;
+        self.run_idle_header
+            .write_options(&mut temp_writer, endian, args)?;
+        self.data.write_options(&mut temp_writer, endian, args)?;
+
+        // Step 2: Write the full common packet list
+        CommonPacketList::new(vec![
+            CommonPacketItem::SequencedAddress(self.sequenced_address),
+            CommonPacketItem::ConnectedTransportPacket(Some(temp_buffer)),
+        ])
+        .write_options(writer, endian, (None,))
+    }
+}
+
+impl<T> BinRead for ImplicitIoPacket<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    // Whether the connected data starts with the 32-bit run/idle header
+    type Args<'a> = (bool,);
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        let (has_run_idle_header,) = args;
+
+        // Step 1: Read the common packet list
+        let packet_list = CommonPacketList::read_options(reader, endian, ())?;
+
+        let (Some(sequenced_address), Some(transport_bytes)) = (
+            packet_list.sequenced_address(),
+            packet_list.connected_transport_packet(),
+        ) else {
+            return Err(binrw::Error::AssertFail {
+                pos: 0,
+                message: "Implicit I/O packets need a sequenced address and connected data item"
+                    .to_string(),
+            });
+        };
+
+        // Step 2: Read the sequence count, run/idle header and data out of the connected data item
+        let mut transport_reader = std::io::Cursor::new(transport_bytes);
+
+        let sequence_count = CipUint::read_options(&mut transport_reader, endian, ())?;
+
+        let run_idle_header = match has_run_idle_header {
+            true => Some(RunIdleHeader::read_options(
+                &mut transport_reader,
+                endian,
+                (),
+            )?),
+            false => None,
+        };
+
+        let data = T::read_options(&mut transport_reader, endian, ())?;
+
+        Ok(ImplicitIoPacket {
+            sequenced_address,
+            sequence_count,
+            run_idle_header,
+            data,
+        })
+    }
+}
+
+// ^^^^^^^^ End of ImplicitIoPacket impl ^^^^^^^^
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use crate::cip::types::CipUsint;
+
+// Multipliers above 7 are reserved
+const MAX_CONNECTION_TIMEOUT_MULTIPLIER: CipUsint = 7;
+
+/// The inactivity timeout of a connection: the RPI multiplied by 4, 8, 16, ... 512 for the
+///  connection timeout multiplier values 0 through 7
+pub fn connection_timeout(rpi: Duration, connection_timeout_multiplier: CipUsint) -> Duration {
+    let multiplier_value = connection_timeout_multiplier.min(MAX_CONNECTION_TIMEOUT_MULTIPLIER);
+
+    rpi * (4u32 << multiplier_value)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConnectionEvent {
+    // No packets were consumed within the connection timeout
+    TimedOut,
+    // A new Forward_Open replaced the timed out connection
+    Reconnected,
+    ReconnectFailed,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ConnectionWatchdog {
+    timeout: Duration,
+    last_activity: Instant,
+}
+
+// ======= Start of ConnectionWatchdog impl ========
+
+impl ConnectionWatchdog {
+    // The timeout also applies to the first consumed packet after the connection opens
+    pub fn new(timeout: Duration, opened_at: Instant) -> Self {
+        ConnectionWatchdog {
+            timeout,
+            last_activity: opened_at,
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn packet_consumed(&mut self, consumed_at: Instant) {
+        self.last_activity = self.last_activity.max(consumed_at);
+    }
+
+    /// When the connection times out unless another packet is consumed
+    pub fn deadline(&self) -> Instant {
+        self.last_activity + self.timeout
+    }
+
+    pub fn is_timed_out(&self, now: Instant) -> bool {
+        now >= self.deadline()
+    }
+}
+
+// ^^^^^^^^ End of ConnectionWatchdog impl ^^^^^^^^
@@ -1,3 +1,12 @@
+pub mod blocking_client;
 pub mod cip;
+#[cfg(feature = "tokio")]
+pub mod client;
+pub mod discovery;
 pub mod eip;
+pub mod error;
+pub mod implicit;
 pub mod object_assembly;
+pub mod session;
+#[cfg(feature = "tokio")]
+pub mod transport;
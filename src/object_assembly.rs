@@ -1,16 +1,38 @@
-use binrw::meta::WriteEndian;
+use binrw::meta::{ReadEndian, WriteEndian};
 use binrw::{
-    binread,
     BinRead,
+    BinResult,
     BinWrite, // trait for writing
 };
 
+use crate::cip::connection_manager::{
+    check_unconnected_send_status, ForwardCloseRequest, ForwardOpenRequest,
+    LargeForwardOpenRequest, UnconnectedSendRequest, CONNECTION_MANAGER_CLASS_ID,
+};
 use crate::cip::message::{
     request::MessageRouterRequest, response::MessageRouterResponse, shared::ServiceCode,
 };
 use crate::cip::path::CipPath;
-use crate::cip::types::CipUdint;
+use crate::cip::route::Route;
+use crate::cip::types::{CipUdint, CipUint};
+use crate::eip::description::CommonPacketList;
 use crate::eip::packet::EnIpPacketDescription;
+use crate::error::{require_reply_data, EipResult};
+
+// The packet description alone can be read without the data item payloads, a reply can't
+fn require_item_payloads(packet_description: &EnIpPacketDescription, pos: u64) -> BinResult<()> {
+    match packet_description
+        .command_specific_data
+        .packet_items()
+        .is_some_and(|packet_items| packet_items.has_missing_payload())
+    {
+        true => Err(binrw::Error::AssertFail {
+            pos,
+            message: "Missing the payload of a data item".to_string(),
+        }),
+        false => Ok(()),
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub struct RequestObjectAssembly<T>
@@ -19,6 +41,9 @@ where
 {
     pub packet_description: EnIpPacketDescription,
     pub cip_message: Option<MessageRouterRequest<T>>,
+    // A target behind a bridge is sent `cip_message` through the bridge's Unconnected_Send, with
+    //  the message addressed from the end of the route
+    pub route: Route,
 }
 
 // ======= Start of RequestObjectAssembly impl ========
@@ -42,28 +67,27 @@ where
         endian: binrw::Endian,
         args: Self::Args<'_>,
     ) -> binrw::BinResult<()> {
-        // Step 1: Serialize the `cip_message` field
+        // Step 1: Serialize the `cip_message` field, embedded in an Unconnected_Send for a route
         let mut temp_buffer = Vec::new();
         let mut temp_writer = std::io::Cursor::new(&mut temp_buffer);
 
-        let cip_message_write_result =
-            self.cip_message
-                .write_options(&mut temp_writer, endian, args);
-
-        if let Err(write_err) = cip_message_write_result {
-            return Err(write_err);
+        match &self.cip_message {
+            Some(cip_message) if !self.route.is_empty() => {
+                MessageRouterRequest::new_unconnected_send(UnconnectedSendRequest::from_request(
+                    &self.route,
+                    cip_message,
+                )?)
+                .write_options(&mut temp_writer, endian, args)?
+            }
+            cip_message => cip_message.write_options(&mut temp_writer, endian, args)?,
         }
 
         // Step 2: Calculate the packet size
         let packet_byte_size = temp_buffer.len() as u16;
 
         // Step 3: Write the full packet
-        if let Err(write_err) =
-            self.packet_description
-                .write_options(writer, endian, (packet_byte_size,))
-        {
-            return Err(write_err);
-        }
+        self.packet_description
+            .write_options(writer, endian, (packet_byte_size,))?;
 
         if let Err(write_err) = writer.write(&temp_buffer) {
             return Err(binrw::Error::Io(write_err));
@@ -75,26 +99,195 @@ where
 
 // ^^^^^^^^ End of RequestObjectAssembly impl ^^^^^^^^
 
-#[binread]
-#[brw(little)]
 #[derive(Debug, PartialEq)]
 pub struct ResponseObjectAssembly<T>
 where
     T: for<'a> BinRead<Args<'a> = ()>,
 {
     pub packet_description: EnIpPacketDescription,
-
-    // TODO: Validate that the size of the EnIpPacketDescription correctly matches the remaining bytes
-    //  * If the remaining bytes are 0, don't serialize the next step (otherwise do)
-    #[br(try)]
     pub cip_message: Option<MessageRouterResponse<T>>,
 }
 
+// ======= Start of ResponseObjectAssembly impl ========
+
+impl<T> ReadEndian for ResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl<T> BinRead for ResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        // Step 1: Read the packet description (including every common packet item)
+        let packet_description = EnIpPacketDescription::read_options(reader, endian, args)?;
+
+        require_item_payloads(&packet_description, reader.stream_position()?)?;
+
+        // Step 2: Only commands that carry an unconnected message have a CIP message to read
+        let unconnected_message = packet_description
+            .command_specific_data
+            .packet_items()
+            .and_then(|packet_items| packet_items.unconnected_message());
+
+        // Step 3: Read the CIP message out of the unconnected message item. A failed Unconnected_Send
+        //  reply (see `check_routing_status`) reads as an error reply without any `T` data
+        let cip_message = match unconnected_message {
+            Some(message_bytes) => Some(MessageRouterResponse::<T>::read_options(
+                &mut std::io::Cursor::new(message_bytes),
+                endian,
+                (),
+            )?),
+            None => None,
+        };
+
+        Ok(ResponseObjectAssembly {
+            packet_description,
+            cip_message,
+        })
+    }
+}
+
+impl<T> ResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    /// Converts the response into its CIP data, or the encapsulation or CIP error that
+    ///  rejected the request sent to `request_path`
+    pub fn into_result(self, request_path: CipPath) -> EipResult<Option<T>> {
+        self.packet_description.check_status()?;
+        self.check_routing_status()?;
+
+        match self.cip_message {
+            Some(cip_message) => cip_message.into_result(request_path),
+            None => Ok(None),
+        }
+    }
+
+    /// The whole message router reply, for services that succeed with a non-success status
+    pub fn into_message_router_response(self) -> EipResult<MessageRouterResponse<T>> {
+        self.packet_description.check_status()?;
+        self.check_routing_status()?;
+
+        require_reply_data(self.cip_message)
+    }
+
+    // A bridge that couldn't deliver a routed request replies with an Unconnected_Send error
+    //  instead of the target's reply
+    fn check_routing_status(&self) -> EipResult<()> {
+        match self
+            .packet_description
+            .command_specific_data
+            .packet_items()
+            .and_then(|packet_items| packet_items.unconnected_message())
+        {
+            Some(message_bytes) => check_unconnected_send_status(message_bytes),
+            None => Ok(()),
+        }
+    }
+}
+
+// ^^^^^^^^ End of ResponseObjectAssembly impl ^^^^^^^^
+
+// For services whose reply data depends on the general status (e.g. Forward_Open), `R` reads the
+//  entire message router reply instead of only the successful reply data
+#[derive(Debug, PartialEq)]
+pub struct ServiceReplyObjectAssembly<R>
+where
+    R: for<'a> BinRead<Args<'a> = ()>,
+{
+    pub packet_description: EnIpPacketDescription,
+    pub cip_reply: Option<R>,
+}
+
+// ======= Start of ServiceReplyObjectAssembly impl ========
+
+impl<R> ReadEndian for ServiceReplyObjectAssembly<R>
+where
+    R: for<'a> BinRead<Args<'a> = ()>,
+{
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl<R> BinRead for ServiceReplyObjectAssembly<R>
+where
+    R: for<'a> BinRead<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn read_options<Reader: std::io::Read + std::io::Seek>(
+        reader: &mut Reader,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        let packet_description = EnIpPacketDescription::read_options(reader, endian, args)?;
+        require_item_payloads(&packet_description, reader.stream_position()?)?;
+
+        let unconnected_message = packet_description
+            .command_specific_data
+            .packet_items()
+            .and_then(|packet_items| packet_items.unconnected_message());
+
+        let cip_reply = match unconnected_message {
+            Some(message_bytes) => Some(R::read_options(
+                &mut std::io::Cursor::new(message_bytes),
+                endian,
+                (),
+            )?),
+            None => None,
+        };
+
+        Ok(ServiceReplyObjectAssembly {
+            packet_description,
+            cip_reply,
+        })
+    }
+}
+
+impl<R> ServiceReplyObjectAssembly<R>
+where
+    R: for<'a> BinRead<Args<'a> = ()>,
+{
+    /// The whole service reply, or the encapsulation error that rejected the request
+    pub fn into_reply(self) -> EipResult<R> {
+        self.packet_description.check_status()?;
+
+        require_reply_data(self.cip_reply)
+    }
+
+    /// The whole service reply and the common packet items it was sent in, for replies with
+    ///  extra items (e.g. the socket address items of a Forward_Open)
+    pub fn into_reply_with_items(self) -> EipResult<(R, CommonPacketList)> {
+        self.packet_description.check_status()?;
+
+        let packet_items = self
+            .packet_description
+            .command_specific_data
+            .packet_items()
+            .cloned()
+            .unwrap_or_else(|| CommonPacketList::new(Vec::new()));
+
+        Ok((require_reply_data(self.cip_reply)?, packet_items))
+    }
+}
+
+// ^^^^^^^^ End of ServiceReplyObjectAssembly impl ^^^^^^^^
+
 impl RequestObjectAssembly<u8> {
     pub fn new_registration() -> Self {
         RequestObjectAssembly {
             packet_description: EnIpPacketDescription::new_registration_description(),
             cip_message: None,
+            route: Route::default(),
         }
     }
 
@@ -104,6 +297,23 @@ impl RequestObjectAssembly<u8> {
                 session_handle,
             ),
             cip_message: None,
+            route: Route::default(),
+        }
+    }
+
+    pub fn new_list_services() -> Self {
+        RequestObjectAssembly {
+            packet_description: EnIpPacketDescription::new_list_services_description(),
+            cip_message: None,
+            route: Route::default(),
+        }
+    }
+
+    pub fn new_list_interfaces() -> Self {
+        RequestObjectAssembly {
+            packet_description: EnIpPacketDescription::new_list_interfaces_description(),
+            cip_message: None,
+            route: Route::default(),
         }
     }
 
@@ -117,10 +327,48 @@ impl RequestObjectAssembly<u8> {
     }
 }
 
+impl RequestObjectAssembly<ForwardOpenRequest> {
+    pub fn new_forward_open(session_handle: CipUdint, forward_open: ForwardOpenRequest) -> Self {
+        Self::new_service_request(
+            session_handle,
+            CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            ServiceCode::ForwardOpen,
+            Some(forward_open),
+        )
+    }
+}
+
+impl RequestObjectAssembly<LargeForwardOpenRequest> {
+    pub fn new_large_forward_open(
+        session_handle: CipUdint,
+        large_forward_open: LargeForwardOpenRequest,
+    ) -> Self {
+        Self::new_service_request(
+            session_handle,
+            CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            ServiceCode::LargeForwardOpen,
+            Some(large_forward_open),
+        )
+    }
+}
+
+impl RequestObjectAssembly<ForwardCloseRequest> {
+    pub fn new_forward_close(session_handle: CipUdint, forward_close: ForwardCloseRequest) -> Self {
+        Self::new_service_request(
+            session_handle,
+            CipPath::new(CONNECTION_MANAGER_CLASS_ID, 0x1),
+            ServiceCode::ForwardClose,
+            Some(forward_close),
+        )
+    }
+}
+
 impl<T> RequestObjectAssembly<T>
 where
     T: for<'a> BinWrite<Args<'a> = ()>,
 {
+    /// A request path that starts with a route (see `CipPath::with_route`) is sent to the target
+    ///  at its end through the Connection Manager's Unconnected_Send service
     pub fn new_service_request(
         session_handle: CipUdint,
         request_path: CipPath,
@@ -131,9 +379,240 @@ where
             packet_description: EnIpPacketDescription::new_cip_description(session_handle, 0),
             cip_message: Some(MessageRouterRequest::new_data(
                 service_code,
-                request_path,
+                request_path.target(),
                 data,
             )),
+            route: request_path.route(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConnectedRequestObjectAssembly<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    pub packet_description: EnIpPacketDescription,
+    pub sequence_count: CipUint,
+    pub cip_message: MessageRouterRequest<T>,
+}
+
+// ======= Start of ConnectedRequestObjectAssembly impl ========
+
+impl<T> WriteEndian for ConnectedRequestObjectAssembly<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl<T> BinWrite for ConnectedRequestObjectAssembly<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        // Step 1: Serialize the `sequence_count` and `cip_message` fields (the connected transport packet)
+        let mut temp_buffer = Vec::new();
+        let mut temp_writer = std::io::Cursor::new(&mut temp_buffer);
+
+        self.sequence_count
+            .write_options(&mut temp_writer, endian, args)?;
+        self.cip_message
+            .write_options(&mut temp_writer, endian, args)?;
+
+        // Step 2: Calculate the packet size
+        let packet_byte_size = temp_buffer.len() as u16;
+
+        // Step 3: Write the full packet
+        self.packet_description
+            .write_options(writer, endian, (packet_byte_size,))?;
+
+        if let Err(write_err) = writer.write(&temp_buffer) {
+            return Err(binrw::Error::Io(write_err));
         }
+
+        Ok(())
     }
 }
+
+impl<T> ConnectedRequestObjectAssembly<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    pub fn new_service_request(
+        session_handle: CipUdint,
+        connection_identifier: CipUdint,
+        sequence_count: CipUint,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> Self {
+        Self {
+            packet_description: EnIpPacketDescription::new_connected_description(
+                session_handle,
+                connection_identifier,
+            ),
+            sequence_count,
+            cip_message: MessageRouterRequest::new_data(service_code, request_path, data),
+        }
+    }
+}
+
+// ^^^^^^^^ End of ConnectedRequestObjectAssembly impl ^^^^^^^^
+
+#[derive(Debug, PartialEq)]
+pub struct ConnectedResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    pub packet_description: EnIpPacketDescription,
+    pub sequence_count: CipUint,
+    pub cip_message: MessageRouterResponse<T>,
+}
+
+// ======= Start of ConnectedResponseObjectAssembly impl ========
+
+impl<T> ReadEndian for ConnectedResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    const ENDIAN: binrw::meta::EndianKind = binrw::meta::EndianKind::Endian(binrw::Endian::Little);
+}
+
+impl<T> BinRead for ConnectedResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        // Step 1: Read the packet description (including every common packet item)
+        let packet_description = EnIpPacketDescription::read_options(reader, endian, args)?;
+
+        // Step 2: A connected response always carries a connected transport packet
+        let Some(transport_bytes) = packet_description
+            .command_specific_data
+            .packet_items()
+            .and_then(|packet_items| packet_items.connected_transport_packet())
+        else {
+            return Err(binrw::Error::AssertFail {
+                pos: 0,
+                message: "Missing the connected transport packet".to_string(),
+            });
+        };
+
+        // Step 3: Read the sequence count and CIP message out of the connected transport packet
+        let mut transport_reader = std::io::Cursor::new(transport_bytes);
+
+        let sequence_count = CipUint::read_options(&mut transport_reader, endian, ())?;
+        let cip_message =
+            MessageRouterResponse::<T>::read_options(&mut transport_reader, endian, ())?;
+
+        Ok(ConnectedResponseObjectAssembly {
+            packet_description,
+            sequence_count,
+            cip_message,
+        })
+    }
+}
+
+impl<T> ConnectedResponseObjectAssembly<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    pub fn connection_identifier(&self) -> Option<CipUdint> {
+        self.packet_description
+            .command_specific_data
+            .packet_items()
+            .and_then(|packet_items| packet_items.connected_address())
+    }
+
+    /// Converts the response into its CIP data, or the encapsulation or CIP error that
+    ///  rejected the request sent to `request_path`
+    pub fn into_result(self, request_path: CipPath) -> EipResult<Option<T>> {
+        self.packet_description.check_status()?;
+
+        self.cip_message.into_result(request_path)
+    }
+}
+
+// ^^^^^^^^ End of ConnectedResponseObjectAssembly impl ^^^^^^^^
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExplicitConnection {
+    pub session_handle: CipUdint,
+    // Requests are sent with the O->T connection ID and replies come back with the T->O connection ID
+    pub o2t_connection_id: CipUdint,
+    pub t2o_connection_id: CipUdint,
+    sequence_count: CipUint,
+}
+
+// ======= Start of ExplicitConnection impl ========
+
+impl ExplicitConnection {
+    pub fn new(
+        session_handle: CipUdint,
+        o2t_connection_id: CipUdint,
+        t2o_connection_id: CipUdint,
+    ) -> Self {
+        ExplicitConnection {
+            session_handle,
+            o2t_connection_id,
+            t2o_connection_id,
+            sequence_count: 0,
+        }
+    }
+
+    /// The sequence count of the most recently created request
+    pub fn sequence_count(&self) -> CipUint {
+        self.sequence_count
+    }
+
+    /// Creates the next request on the connection, incrementing the sequence count
+    pub fn new_service_request<T>(
+        &mut self,
+        request_path: CipPath,
+        service_code: ServiceCode,
+        data: Option<T>,
+    ) -> ConnectedRequestObjectAssembly<T>
+    where
+        T: for<'a> BinWrite<Args<'a> = ()>,
+    {
+        self.sequence_count = self.sequence_count.wrapping_add(1);
+
+        ConnectedRequestObjectAssembly::new_service_request(
+            self.session_handle,
+            self.o2t_connection_id,
+            self.sequence_count,
+            request_path,
+            service_code,
+            data,
+        )
+    }
+
+    /// Whether the response was sent on this connection in reply to the request with `sequence_count`
+    pub fn is_reply_to<T>(
+        &self,
+        response: &ConnectedResponseObjectAssembly<T>,
+        sequence_count: CipUint,
+    ) -> bool
+    where
+        T: for<'a> BinRead<Args<'a> = ()>,
+    {
+        response.connection_identifier() == Some(self.t2o_connection_id)
+            && response.sequence_count == sequence_count
+    }
+}
+
+// ^^^^^^^^ End of ExplicitConnection impl ^^^^^^^^
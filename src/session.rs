@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use binrw::BinWrite;
+
+use crate::cip::connection_manager::{ForwardOpenReply, OpenedConnection};
+use crate::cip::message::shared::ServiceCode;
+use crate::eip::description::CommonPacketList;
+use crate::eip::framing::DEFAULT_MAX_FRAME_SIZE;
+use crate::error::{EipError, EipResult};
+
+// Session management shared by the async `EipClient` and the `BlockingEipClient`
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    // Retries after the first attempt, so a request is sent at most `max_retries + 1` times
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            retry_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 2,
+            retry_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EipClientConfig {
+    // Bounds connecting, registering and each attempt of a request
+    pub request_timeout: Duration,
+    // Only applied to idempotent services (see `ServiceCode::is_idempotent`)
+    pub retry_policy: RetryPolicy,
+    // Reconnect and register a new session when the connection drops or the session is rejected
+    pub reconnect: bool,
+    // Larger reply frames fail the requests waiting on the connection
+    pub max_frame_size: usize,
+}
+
+impl Default for EipClientConfig {
+    fn default() -> Self {
+        EipClientConfig {
+            request_timeout: Duration::from_secs(5),
+            retry_policy: RetryPolicy::default(),
+            reconnect: true,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+}
+
+// Whether a request that failed with `reply_err` may be sent again
+pub(crate) fn is_retryable(reply_err: &EipError, service_code: ServiceCode) -> bool {
+    match reply_err {
+        // The device rejected the session before looking at the request
+        EipError::SessionInvalid(_) => true,
+        EipError::Io(_) | EipError::Timeout => service_code.is_idempotent(),
+        _ => false,
+    }
+}
+
+// After these errors the session is unusable, so the next request has to open a new one. A timed
+//  out request may still be answered later, so its session isn't trusted either
+pub(crate) fn discards_session(reply_err: &EipError) -> bool {
+    matches!(
+        reply_err,
+        EipError::SessionInvalid(_) | EipError::Io(_) | EipError::Timeout
+    )
+}
+
+// Serialized once so the same data can be resent on a retry
+pub(crate) fn request_data_bytes<T>(data: Option<&T>) -> EipResult<Option<Vec<u8>>>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    match data {
+        Some(data) => {
+            let mut data_bytes: Vec<u8> = Vec::new();
+            data.write_le(&mut std::io::Cursor::new(&mut data_bytes))?;
+            Ok(Some(data_bytes))
+        }
+        None => Ok(None),
+    }
+}
+
+// The connection a Forward_Open reply opened, with the socket address items sent next to it
+pub(crate) fn opened_connection(
+    forward_open_reply: ForwardOpenReply,
+    packet_items: &CommonPacketList,
+) -> EipResult<OpenedConnection> {
+    Ok(OpenedConnection {
+        forward_open: forward_open_reply.into_result()?,
+        o2t_socket_address: packet_items.o2t_socket_address(),
+        t2o_socket_address: packet_items.t2o_socket_address(),
+    })
+}
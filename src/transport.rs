@@ -0,0 +1,67 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+pub type ConnectFuture<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + Send + 'a>>;
+
+// A byte stream that encapsulation messages are exchanged over (e.g. TCP, TLS or an in-memory
+//  duplex in tests)
+pub trait Transport: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<T> Transport for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+// Opens a new transport to the device. The client connects once for the first session and again
+//  every time it has to reconnect
+pub trait Connector: Send + Sync + 'static {
+    type Transport: Transport;
+
+    fn connect(&self) -> ConnectFuture<'_, Self::Transport>;
+}
+
+// Any closure that returns a future of a transport is a connector
+impl<F, Fut, T> Connector for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = io::Result<T>> + Send + 'static,
+    T: Transport,
+{
+    type Transport = T;
+
+    fn connect(&self) -> ConnectFuture<'_, Self::Transport> {
+        Box::pin(self())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcpConnector {
+    socket_addresses: Vec<SocketAddr>,
+}
+
+// ======= Start of TcpConnector impl ========
+
+impl TcpConnector {
+    /// Resolves the address once, so reconnecting doesn't depend on name resolution
+    pub async fn new<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let socket_addresses = tokio::net::lookup_host(address).await?.collect();
+
+        Ok(TcpConnector { socket_addresses })
+    }
+
+    pub fn socket_addresses(&self) -> &[SocketAddr] {
+        &self.socket_addresses
+    }
+}
+
+impl Connector for TcpConnector {
+    type Transport = TcpStream;
+
+    fn connect(&self) -> ConnectFuture<'_, Self::Transport> {
+        Box::pin(TcpStream::connect(&self.socket_addresses[..]))
+    }
+}
+
+// ^^^^^^^^ End of TcpConnector impl ^^^^^^^^
@@ -0,0 +1,238 @@
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::message::attribute_list::{
+    AttributeResponse, AttributeSetting, AttributeStatus, AttributeStatusCode, AttributeTypeMap,
+    AttributeValue, CipDataType, GetAttributeListReply, SetAttributeListReply,
+};
+use eipscanne_rs::cip::message::request::MessageRouterRequest;
+use eipscanne_rs::cip::message::response::{MessageRouterResponse, ResponseStatusCode};
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::{CipByte, CipRevision};
+
+fn identity_type_map() -> AttributeTypeMap {
+    AttributeTypeMap::from([
+        (0x1, CipDataType::Uint),
+        (0x4, CipDataType::Revision),
+        (0x8, CipDataType::Usint),
+    ])
+}
+
+#[test]
+fn test_serialize_get_attribute_list_request() {
+    /*
+    Common Industrial Protocol
+    Service: Get Attribute List (Request)
+        0... .... = Request/Response: Request (0x0)
+        .000 0011 = Service: Get Attribute List (0x03)
+    Request Path Size: 4 words
+    Request Path: Identity, Instance: 0x0001
+    Get Attribute List (Request)
+        Attribute Count: 3
+        Attribute List
+            Attribute: 1 (Vendor ID)
+            Attribute: 4 (Revision)
+            Attribute: 8 (State)
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   03 04 21 00 01 00 25 00 01 00 03 00 01 00 04 00
+    0010   08 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x03, 0x04, 0x21, 0x00, 0x01, 0x00, 0x25, 0x00, 0x01, 0x00, 0x03, 0x00, 0x01, 0x00, 0x04,
+        0x00, 0x08, 0x00,
+    ];
+
+    let message_router_request =
+        MessageRouterRequest::new_get_attribute_list(CipPath::new(0x1, 0x1), vec![0x1, 0x4, 0x8]);
+
+    let mut message_router_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut message_router_bytes);
+
+    message_router_request.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, message_router_bytes);
+}
+
+#[test]
+fn test_deserialize_get_attribute_list_reply() {
+    /*
+    Common Industrial Protocol
+    Service: Get Attribute List (Response)
+        1... .... = Request/Response: Response (0x1)
+        .000 0011 = Service: Get Attribute List (0x03)
+    Status: Attribute list error:
+        General Status: Attribute list error (0x0a)
+        Additional Status Size: 0 words
+    Get Attribute List (Response)
+        Attribute Count: 3
+        Attribute List
+            Attribute: 1 (Vendor ID)
+                Status: Success (0x0000)
+                Vendor ID: Teknic, Inc. (0x01a8)
+            Attribute: 4 (Revision)
+                Status: Success (0x0000)
+                Major Revision: 2
+                Minor Revision: 93
+            Attribute: 8 (State)
+                Status: Attribute not supported (0x0014)
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   83 00 0a 00 03 00 01 00 00 00 a8 01 04 00 00 00
+    0010   02 5d 08 00 14 00
+
+    */
+    let raw_byte_array: Vec<CipByte> = vec![
+        0x83, 0x00, 0x0a, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0xa8, 0x01, 0x04, 0x00, 0x00,
+        0x00, 0x02, 0x5d, 0x08, 0x00, 0x14, 0x00,
+    ];
+
+    let message_router_response = MessageRouterResponse::<GetAttributeListReply>::read(
+        &mut std::io::Cursor::new(raw_byte_array),
+    )
+    .unwrap();
+
+    let attribute_responses = message_router_response
+        .into_get_attribute_list_reply(CipPath::new(0x1, 0x1))
+        .unwrap()
+        .decode(&identity_type_map())
+        .unwrap();
+
+    let expected_attribute_responses = vec![
+        AttributeResponse {
+            attribute_id: 0x1,
+            status: AttributeStatusCode(0x0),
+            value: Some(AttributeValue::Uint(0x01a8)),
+        },
+        AttributeResponse {
+            attribute_id: 0x4,
+            status: AttributeStatusCode(0x0),
+            value: Some(AttributeValue::Revision(CipRevision {
+                major_revision: 2,
+                minor_revision: 93,
+            })),
+        },
+        AttributeResponse {
+            attribute_id: 0x8,
+            status: AttributeStatusCode(0x14),
+            value: None,
+        },
+    ];
+
+    assert_eq!(expected_attribute_responses, attribute_responses);
+
+    // Attribute statuses are general status codes, which never go above 0xff
+    assert_eq!(
+        Ok(ResponseStatusCode::AttributeNotSupported),
+        ResponseStatusCode::try_from(attribute_responses[2].status)
+    );
+    assert_eq!(
+        Err(AttributeStatusCode(0x100)),
+        ResponseStatusCode::try_from(AttributeStatusCode(0x100))
+    );
+}
+
+#[test]
+fn test_decode_get_attribute_list_reply_missing_type() {
+    // Attribute 2 succeeded, but the type map doesn't say how to read its value
+    let raw_byte_array: Vec<CipByte> = vec![0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x2b, 0x00];
+
+    let get_attribute_list_reply =
+        GetAttributeListReply::read(&mut std::io::Cursor::new(raw_byte_array)).unwrap();
+
+    assert!(get_attribute_list_reply
+        .decode(&identity_type_map())
+        .is_err());
+}
+
+#[test]
+fn test_serialize_set_attribute_list_request() {
+    /*
+    Common Industrial Protocol
+    Service: Set Attribute List (Request)
+        0... .... = Request/Response: Request (0x0)
+        .000 0100 = Service: Set Attribute List (0x04)
+    Request Path Size: 4 words
+    Request Path: TCP/IP Interface, Instance: 0x0001
+    Set Attribute List (Request)
+        Attribute Count: 1
+        Attribute List
+            Attribute: 3 (Configuration Control)
+                Configuration Control: 0x00000002
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 04 21 00 f5 00 25 00 01 00 01 00 03 00 02 00
+    0010   00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x04, 0x04, 0x21, 0x00, 0xf5, 0x00, 0x25, 0x00, 0x01, 0x00, 0x01, 0x00, 0x03, 0x00, 0x02,
+        0x00, 0x00, 0x00,
+    ];
+
+    let message_router_request = MessageRouterRequest::new_set_attribute_list(
+        CipPath::new(0xf5, 0x1),
+        vec![AttributeSetting {
+            attribute_id: 0x3,
+            value: AttributeValue::Dword(0x2),
+        }],
+    );
+
+    let mut message_router_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut message_router_bytes);
+
+    message_router_request.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, message_router_bytes);
+}
+
+#[test]
+fn test_deserialize_set_attribute_list_reply() {
+    /*
+    Common Industrial Protocol
+    Service: Set Attribute List (Response)
+        1... .... = Request/Response: Response (0x1)
+        .000 0100 = Service: Set Attribute List (0x04)
+    Status: Success:
+        General Status: Success (0x00)
+        Additional Status Size: 0 words
+    Set Attribute List (Response)
+        Attribute Count: 1
+        Attribute List
+            Attribute: 3 (Configuration Control)
+                Status: Success (0x0000)
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   84 00 00 00 01 00 03 00 00 00
+
+    */
+    let raw_byte_array: Vec<CipByte> =
+        vec![0x84, 0x00, 0x00, 0x00, 0x01, 0x00, 0x03, 0x00, 0x00, 0x00];
+
+    let message_router_response = MessageRouterResponse::<SetAttributeListReply>::read(
+        &mut std::io::Cursor::new(raw_byte_array),
+    )
+    .unwrap();
+
+    let set_attribute_list_reply = message_router_response
+        .into_set_attribute_list_reply(CipPath::new(0xf5, 0x1))
+        .unwrap();
+
+    assert_eq!(
+        vec![AttributeStatus {
+            attribute_id: 0x3,
+            status: AttributeStatusCode(0x0),
+        }],
+        set_attribute_list_reply.attribute_statuses
+    );
+}
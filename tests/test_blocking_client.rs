@@ -0,0 +1,327 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::blocking_client::BlockingEipClient;
+use eipscanne_rs::cip::message::attribute_list::{AttributeTypeMap, AttributeValue, CipDataType};
+use eipscanne_rs::cip::message::response::ResponseStatusCode;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::{CipByte, CipUint};
+use eipscanne_rs::eip::framing::DEFAULT_MAX_FRAME_SIZE;
+use eipscanne_rs::error::EipError;
+use eipscanne_rs::session::{EipClientConfig, RetryPolicy};
+
+const REGISTER_SESSION_RESPONSE: [CipByte; 28] = [
+    0x65, 0x00, 0x04, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+const UNREGISTER_SESSION_REQUEST: [CipByte; 24] = [
+    0x66, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+// Devices echo the sender context of the request in their reply
+fn reply_with_sender_context(request: &[CipByte], reply: &[CipByte]) -> Vec<CipByte> {
+    let mut reply = reply.to_vec();
+    reply[12..20].copy_from_slice(&request[12..20]);
+
+    reply
+}
+
+fn accept_registration(listener: &TcpListener) -> TcpStream {
+    let (mut adapter_stream, _) = listener.accept().unwrap();
+
+    let mut registration_request = [0u8; 28];
+    adapter_stream
+        .read_exact(&mut registration_request)
+        .unwrap();
+    adapter_stream
+        .write_all(&reply_with_sender_context(
+            &registration_request,
+            &REGISTER_SESSION_RESPONSE,
+        ))
+        .unwrap();
+
+    adapter_stream
+}
+
+#[test]
+fn test_blocking_client_session_lifecycle() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 22
+        ...
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Success:
+        Data: 3412
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 16 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 06 00 8e 00 00 00 34 12
+
+    */
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = std::thread::spawn(move || {
+        let mut adapter_stream = accept_registration(&listener);
+
+        let mut get_attribute_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .unwrap();
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .unwrap();
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST, unregistration_request);
+    });
+
+    let mut client = BlockingEipClient::connect(adapter_address).unwrap();
+    assert_eq!(0x06, client.session_handle());
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    client.close().unwrap();
+
+    fake_adapter.join().unwrap();
+}
+
+#[test]
+fn test_blocking_client_get_attribute_list() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 38
+        ...
+    Common Industrial Protocol
+        Service: Get Attribute List (Response)
+        Status: Attribute list error:
+        Get Attribute List (Response)
+            Attribute Count: 3
+            Attribute: 1 (Vendor ID), Status: Success, Vendor ID: 0x01a8
+            Attribute: 4 (Revision), Status: Success, Revision: 2.93
+            Attribute: 8 (State), Status: Attribute not supported
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 26 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 16 00 83 00 0a 00 03 00 01 00
+    0030   00 00 a8 01 04 00 00 00 02 5d 08 00 14 00
+
+    */
+    let get_attribute_list_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x26, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x16, 0x00, 0x83, 0x00, 0x0a, 0x00, 0x03,
+        0x00, 0x01, 0x00, 0x00, 0x00, 0xa8, 0x01, 0x04, 0x00, 0x00, 0x00, 0x02, 0x5d, 0x08, 0x00,
+        0x14, 0x00,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = std::thread::spawn(move || {
+        let mut adapter_stream = accept_registration(&listener);
+
+        let mut get_attribute_list_request = [0u8; 58];
+        adapter_stream
+            .read_exact(&mut get_attribute_list_request)
+            .unwrap();
+        assert_eq!(
+            [0x03, 0x00, 0x01, 0x00, 0x04, 0x00, 0x08, 0x00],
+            get_attribute_list_request[50..]
+        );
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_list_request,
+                &get_attribute_list_response,
+            ))
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .unwrap();
+    });
+
+    let mut client = BlockingEipClient::connect(adapter_address).unwrap();
+
+    let type_map = AttributeTypeMap::from([
+        (0x1, CipDataType::Uint),
+        (0x4, CipDataType::Revision),
+        (0x8, CipDataType::Usint),
+    ]);
+    let attribute_responses = client
+        .get_attribute_list(CipPath::new(0x1, 0x1), vec![0x1, 0x4, 0x8], &type_map)
+        .unwrap();
+
+    assert_eq!(3, attribute_responses.len());
+    assert_eq!(
+        Some(AttributeValue::Uint(0x01a8)),
+        attribute_responses[0].value
+    );
+    assert!(attribute_responses[1].is_success());
+    assert_eq!(
+        Ok(ResponseStatusCode::AttributeNotSupported),
+        ResponseStatusCode::try_from(attribute_responses[2].status)
+    );
+
+    client.close().unwrap();
+
+    fake_adapter.join().unwrap();
+}
+
+#[test]
+fn test_blocking_client_read_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = std::thread::spawn(move || {
+        let mut adapter_stream = accept_registration(&listener);
+
+        // Never reply to the request. The client drops the session it timed out on, so the
+        //  connection closes without an unregistration
+        let mut get_attribute_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .unwrap();
+
+        let mut unregistration_request = Vec::new();
+        adapter_stream
+            .read_to_end(&mut unregistration_request)
+            .unwrap();
+        assert!(unregistration_request.is_empty());
+    });
+
+    let client_config = EipClientConfig {
+        request_timeout: Duration::from_millis(100),
+        retry_policy: RetryPolicy::none(),
+        reconnect: true,
+        max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+    };
+    let mut client =
+        BlockingEipClient::connect_with_config(adapter_address, client_config).unwrap();
+
+    let get_result = client.get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3));
+    assert!(matches!(get_result, Err(EipError::Timeout)));
+
+    drop(client);
+
+    fake_adapter.join().unwrap();
+}
+
+#[test]
+fn test_blocking_client_ignores_late_reply() {
+    /*
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Success:
+        Data: 7856
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 16 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 06 00 8e 00 00 00 78 56
+
+    */
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x78,
+        0x56,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = std::thread::spawn(move || {
+        let mut adapter_stream = accept_registration(&listener);
+
+        // Answer the first request only after the client gave up on it
+        let mut late_request = [0u8; 48];
+        adapter_stream.read_exact(&mut late_request).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        let _ = adapter_stream.write_all(&reply_with_sender_context(
+            &late_request,
+            &get_attribute_response,
+        ));
+
+        // The retry comes on a new session
+        let mut adapter_stream = accept_registration(&listener);
+
+        let mut get_attribute_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .unwrap();
+
+        // A stray reply to another request comes first and has to be skipped
+        let mut stray_response = get_attribute_response.clone();
+        stray_response[44] = 0xff;
+        adapter_stream.write_all(&stray_response).unwrap();
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .unwrap();
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST, unregistration_request);
+    });
+
+    let client_config = EipClientConfig {
+        request_timeout: Duration::from_millis(100),
+        retry_policy: RetryPolicy {
+            max_retries: 1,
+            retry_delay: Duration::from_millis(300),
+        },
+        reconnect: true,
+        max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+    };
+    let mut client =
+        BlockingEipClient::connect_with_config(adapter_address, client_config).unwrap();
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .unwrap();
+    assert_eq!(0x5678, attribute_value);
+
+    client.close().unwrap();
+
+    fake_adapter.join().unwrap();
+}
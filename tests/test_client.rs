@@ -0,0 +1,1140 @@
+#![cfg(feature = "tokio")]
+
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use bilge::prelude::{u4, u9};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::connection_manager::{
+    ConnectionPriority, ConnectionTriad, ConnectionType, ForwardOpenRequest,
+    LargeForwardOpenRequest, NetworkConnectionParameters, PriorityTimeTick, ProductionTrigger,
+    TransportClassTrigger,
+};
+use eipscanne_rs::cip::message::multiple_service::MultipleServiceRequest;
+use eipscanne_rs::cip::message::request::MessageRouterRequest;
+use eipscanne_rs::cip::message::response::ResponseStatusCode;
+use eipscanne_rs::cip::message::shared::ServiceCode;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::{CipByte, CipUint};
+use eipscanne_rs::client::{EipClient, EipClientConfig, RetryPolicy};
+use eipscanne_rs::eip::framing::DEFAULT_MAX_FRAME_SIZE;
+use eipscanne_rs::error::EipError;
+use eipscanne_rs::object_assembly::ExplicitConnection;
+
+const REGISTER_SESSION_REQUEST: [CipByte; 28] = [
+    0x65, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+const REGISTER_SESSION_RESPONSE: [CipByte; 28] = [
+    0x65, 0x00, 0x04, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+const UNREGISTER_SESSION_REQUEST: [CipByte; 24] = [
+    0x66, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+// Devices echo the sender context of the request in their reply
+fn reply_with_sender_context(request: &[CipByte], reply: &[CipByte]) -> Vec<CipByte> {
+    let mut reply = reply.to_vec();
+    reply[12..20].copy_from_slice(&request[12..20]);
+
+    reply
+}
+
+async fn accept_registration(listener: &TcpListener) -> TcpStream {
+    let (mut adapter_stream, _) = listener.accept().await.unwrap();
+
+    let mut registration_request = [0u8; 28];
+    adapter_stream
+        .read_exact(&mut registration_request)
+        .await
+        .unwrap();
+    // Everything but the sender context of a re-registration matches the first registration
+    assert_eq_hex!(
+        reply_with_sender_context(&registration_request, &REGISTER_SESSION_REQUEST),
+        registration_request.to_vec()
+    );
+
+    adapter_stream
+        .write_all(&reply_with_sender_context(
+            &registration_request,
+            &REGISTER_SESSION_RESPONSE,
+        ))
+        .await
+        .unwrap();
+
+    adapter_stream
+}
+
+#[tokio::test]
+async fn test_client_session_lifecycle() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 22
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 6
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Success:
+        Data: 3412
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 16 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 06 00 8e 00 00 00 34 12
+
+    */
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        let mut get_attribute_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .await
+            .unwrap();
+        // Get Attribute Single service
+        assert_eq!(0x0e, get_attribute_request[40]);
+
+        let get_attribute_response =
+            reply_with_sender_context(&get_attribute_request, &get_attribute_response);
+
+        // Split the reply so the client has to wait for the rest of the frame
+        adapter_stream
+            .write_all(&get_attribute_response[..30])
+            .await
+            .unwrap();
+        adapter_stream.flush().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        adapter_stream
+            .write_all(&get_attribute_response[30..])
+            .await
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .await
+            .unwrap();
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST, unregistration_request);
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+    assert_eq!(0x06, client.session_handle());
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_returns_cip_error() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 20
+        ...
+    Common Industrial Protocol
+        Service: Set Attribute Single (Response)
+        Status: Path destination unknown:
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 14 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 04 00 90 00 05 00
+
+    */
+    let set_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x14, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x04, 0x00, 0x90, 0x00, 0x05, 0x00,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        // Set_Attribute_Single of a 16 bit value
+        let mut set_attribute_request = [0u8; 50];
+        adapter_stream
+            .read_exact(&mut set_attribute_request)
+            .await
+            .unwrap();
+
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &set_attribute_request,
+                &set_attribute_response,
+            ))
+            .await
+            .unwrap();
+
+        // Dropping the client unregisters the session
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .await
+            .unwrap();
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST, unregistration_request);
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+
+    let set_result = client
+        .set_attribute_single(CipPath::new_full(0x4, 0x71, 0x3), 0x1234 as CipUint)
+        .await;
+
+    match set_result {
+        Err(EipError::Cip {
+            service,
+            path,
+            general_status,
+            ..
+        }) => {
+            assert_eq!(ServiceCode::SetAttributeSingle, service);
+            assert_eq!(Some(CipPath::new_full(0x4, 0x71, 0x3)), path);
+            assert_eq!(ResponseStatusCode::PathDestinationUnknown, general_status);
+        }
+        other_result => panic!("expected a CIP error, got {:?}", other_result),
+    }
+
+    drop(client);
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_pipelines_requests() {
+    // Replies to Get_Attribute_Single of 0x1234 and 0x5678
+    let first_get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+    let mut second_get_attribute_response = first_get_attribute_response.clone();
+    second_get_attribute_response[44..].copy_from_slice(&[0x78, 0x56]);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        // Both requests are sent before either reply arrives
+        let mut first_request = [0u8; 48];
+        let mut second_request = [0u8; 48];
+        adapter_stream.read_exact(&mut first_request).await.unwrap();
+        adapter_stream
+            .read_exact(&mut second_request)
+            .await
+            .unwrap();
+
+        assert_ne!(first_request[12..20], second_request[12..20]);
+
+        // Instance 0x70 gets 0x1234 and instance 0x71 gets 0x5678, whatever order they were sent in
+        let (instance_70_request, instance_71_request) = match first_request[45] {
+            0x70 => (first_request, second_request),
+            _ => (second_request, first_request),
+        };
+
+        // Reply in the opposite order
+        let mut replies =
+            reply_with_sender_context(&instance_71_request, &second_get_attribute_response);
+        replies.extend(reply_with_sender_context(
+            &instance_70_request,
+            &first_get_attribute_response,
+        ));
+        adapter_stream.write_all(&replies).await.unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .await
+            .unwrap();
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+
+    let (instance_70_value, instance_71_value) = tokio::join!(
+        client.get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3)),
+        client.get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x71, 0x3)),
+    );
+
+    assert_eq!(0x1234, instance_70_value.unwrap());
+    assert_eq!(0x5678, instance_71_value.unwrap());
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_sends_multiple_service_packet() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 36
+        ...
+    Common Industrial Protocol
+        Service: Multiple Service Packet (Response)
+        Status: Embedded service error:
+        Multiple Service Packet (Response)
+            Number of Services: 2
+            Get Attribute Single (Response)
+                Status: Success:
+                Data: 0100
+            Get Attribute Single (Response)
+                Status: Attribute not supported:
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 24 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 14 00 8a 00 1e 00 02 00 06 00
+    0030   0c 00 8e 00 00 00 01 00 8e 00 14 00
+
+    */
+    let multiple_service_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x24, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x14, 0x00, 0x8a, 0x00, 0x1e, 0x00, 0x02,
+        0x00, 0x06, 0x00, 0x0c, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x01, 0x00, 0x8e, 0x00, 0x14, 0x00,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        let mut multiple_service_request = [0u8; 72];
+        adapter_stream
+            .read_exact(&mut multiple_service_request)
+            .await
+            .unwrap();
+        // Multiple_Service_Packet to the Message Router
+        assert_eq!(
+            [0x0a, 0x04, 0x21, 0x00, 0x02, 0x00, 0x25, 0x00, 0x01, 0x00],
+            multiple_service_request[40..50]
+        );
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &multiple_service_request,
+                &multiple_service_response,
+            ))
+            .await
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_stream
+            .read_exact(&mut unregistration_request)
+            .await
+            .unwrap();
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+
+    let mut multiple_service_request = MultipleServiceRequest::new();
+    multiple_service_request
+        .add_request(&MessageRouterRequest::<u8>::new(
+            ServiceCode::GetAttributeSingle,
+            CipPath::new_full(0x1, 0x1, 0x1),
+        ))
+        .unwrap()
+        .add_request(&MessageRouterRequest::<u8>::new(
+            ServiceCode::GetAttributeSingle,
+            CipPath::new_full(0x1, 0x1, 0x7),
+        ))
+        .unwrap();
+
+    let multiple_service_reply = client
+        .send_multiple_service(multiple_service_request)
+        .await
+        .unwrap();
+
+    let responses = multiple_service_reply.responses::<CipUint>().unwrap();
+    assert_eq!(Some(0x1), responses[0].response_data.data);
+    assert_eq!(
+        ResponseStatusCode::AttributeNotSupported,
+        responses[1].response_data.status
+    );
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_reregisters_invalid_session() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 0
+        Session Handle: 0x00000006
+        Status: Invalid Session Handle (0x00000064)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 00 00 06 00 00 00 64 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00
+
+    */
+    let invalid_session_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        // The adapter restarted, so it no longer knows the session
+        let mut first_adapter_stream = accept_registration(&listener).await;
+
+        let mut get_attribute_request = [0u8; 48];
+        first_adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .await
+            .unwrap();
+        first_adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &invalid_session_response,
+            ))
+            .await
+            .unwrap();
+
+        // The client opens a new connection and session, then resends the request
+        let mut second_adapter_stream = accept_registration(&listener).await;
+
+        second_adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .await
+            .unwrap();
+        second_adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_retries_timed_out_request() {
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut lost_adapter_stream = accept_registration(&listener).await;
+
+        // The first request is lost, so the client gives up on the session and retries on a new one
+        let mut lost_request = [0u8; 48];
+        lost_adapter_stream
+            .read_exact(&mut lost_request)
+            .await
+            .unwrap();
+
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        let mut retried_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut retried_request)
+            .await
+            .unwrap();
+        assert_eq!(lost_request[24..], retried_request[24..]);
+
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &retried_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+    });
+
+    let client_config = EipClientConfig {
+        request_timeout: Duration::from_millis(100),
+        retry_policy: RetryPolicy {
+            max_retries: 1,
+            retry_delay: Duration::from_millis(10),
+        },
+        reconnect: true,
+        max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+    };
+
+    let client = EipClient::connect_with_config(adapter_address, client_config)
+        .await
+        .unwrap();
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_reconnects_after_dropped_connection() {
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let adapter_address = listener.local_addr().unwrap();
+
+    let (connection_dropped_sender, connection_dropped_receiver) = tokio::sync::oneshot::channel();
+
+    let fake_adapter = tokio::spawn(async move {
+        // The adapter power cycles right after the session is registered
+        drop(accept_registration(&listener).await);
+        connection_dropped_sender.send(()).unwrap();
+
+        let mut adapter_stream = accept_registration(&listener).await;
+
+        let mut get_attribute_request = [0u8; 48];
+        adapter_stream
+            .read_exact(&mut get_attribute_request)
+            .await
+            .unwrap();
+        adapter_stream
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+    });
+
+    let client = EipClient::connect(adapter_address).await.unwrap();
+    connection_dropped_receiver.await.unwrap();
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_over_in_memory_transport() {
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    // Every connect hands the other end of a new duplex to the fake adapter
+    let (adapter_end_sender, mut adapter_end_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let duplex_connector = move || {
+        let (client_end, adapter_end) = tokio::io::duplex(1024);
+        let _ = adapter_end_sender.send(adapter_end);
+
+        async move { Ok(client_end) }
+    };
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_end = adapter_end_receiver.recv().await.unwrap();
+
+        let mut registration_request = [0u8; 28];
+        adapter_end
+            .read_exact(&mut registration_request)
+            .await
+            .unwrap();
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &registration_request,
+                &REGISTER_SESSION_RESPONSE,
+            ))
+            .await
+            .unwrap();
+
+        let mut get_attribute_request = [0u8; 48];
+        adapter_end
+            .read_exact(&mut get_attribute_request)
+            .await
+            .unwrap();
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+
+        let mut unregistration_request = [0u8; 24];
+        adapter_end
+            .read_exact(&mut unregistration_request)
+            .await
+            .unwrap();
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST, unregistration_request);
+    });
+
+    let client = EipClient::with_connector(duplex_connector, EipClientConfig::default())
+        .await
+        .unwrap();
+    assert!(format!("{:?}", client).contains("session_handle: 6"));
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+// Reads one encapsulated frame, whatever its length
+async fn read_frame<S: tokio::io::AsyncRead + Unpin>(adapter_end: &mut S) -> Vec<CipByte> {
+    let mut frame = vec![0u8; 24];
+    adapter_end.read_exact(&mut frame).await.unwrap();
+
+    let data_length = u16::from_le_bytes([frame[2], frame[3]]) as usize;
+    frame.resize(24 + data_length, 0);
+    adapter_end.read_exact(&mut frame[24..]).await.unwrap();
+
+    frame
+}
+
+#[tokio::test]
+async fn test_client_large_forward_open_falls_back() {
+    /*
+    Common Industrial Protocol
+        Service: Large Forward Open (Response)
+        Status: Service not supported:
+            General Status: Service not supported (0x08)
+            Additional Status Size: 0 words
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 14 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 04 00 db 00 08 00
+
+    */
+    let unsupported_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x14, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x04, 0x00, 0xdb, 0x00, 0x08, 0x00,
+    ];
+
+    /*
+    Common Industrial Protocol
+        Service: Forward Open (Response)
+        Status: Success:
+    CIP Connection Manager
+        O->T Network Connection ID: 0x0a0b0c01
+        T->O Network Connection ID: 0x7b3a0001
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        O->T API: 100.000ms
+        T->O API: 100.000ms
+        Application Reply Size: 0 words
+        Reserved: 0x00
+    Type ID: Socket Address Info T->O (0x8001)
+        Length: 16
+        sin_family: 2
+        sin_port: 2222
+        sin_addr: 239.192.1.1
+        sin_zero: 0000000000000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 42 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 03 00
+    0020   00 00 00 00 b2 00 1e 00 d4 00 00 00 01 0c 0b 0a
+    0030   01 00 3a 7b 34 12 a8 01 ee ff c0 00 a0 86 01 00
+    0040   a0 86 01 00 00 00 01 80 10 00 00 02 08 ae ef c0
+    0050   01 01 00 00 00 00 00 00 00 00
+
+    */
+    let forward_open_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x42, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x1e, 0x00, 0xd4, 0x00, 0x00, 0x00, 0x01,
+        0x0c, 0x0b, 0x0a, 0x01, 0x00, 0x3a, 0x7b, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00,
+        0xa0, 0x86, 0x01, 0x00, 0xa0, 0x86, 0x01, 0x00, 0x00, 0x00, 0x01, 0x80, 0x10, 0x00, 0x00,
+        0x02, 0x08, 0xae, 0xef, 0xc0, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let forward_open = ForwardOpenRequest {
+        priority_time_tick: PriorityTimeTick::new(u4::new(0xa), false),
+        timeout_ticks: 0x0e,
+        o2t_connection_id: 0x00000000,
+        t2o_connection_id: 0x7b3a0001,
+        connection_triad: ConnectionTriad {
+            connection_serial_number: 0x1234,
+            originator_vendor_id: 0x01a8,
+            originator_serial_number: 0x00c0ffee,
+        },
+        connection_timeout_multiplier: 0x01,
+        o2t_rpi: 100000,
+        o2t_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(32),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::PointToPoint,
+            false,
+        ),
+        t2o_rpi: 100000,
+        t2o_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(26),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::Multicast,
+            false,
+        ),
+        transport_class_trigger: TransportClassTrigger::new(
+            u4::new(0x1),
+            ProductionTrigger::Cyclic,
+            false,
+        ),
+        connection_path: CipPath::default()
+            .with_class(0x04)
+            .with_instance(0x80)
+            .with_connection_point(0x96)
+            .with_connection_point(0x64),
+    };
+
+    let (adapter_end_sender, mut adapter_end_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let duplex_connector = move || {
+        let (client_end, adapter_end) = tokio::io::duplex(1024);
+        let _ = adapter_end_sender.send(adapter_end);
+
+        async move { Ok(client_end) }
+    };
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_end = adapter_end_receiver.recv().await.unwrap();
+
+        let registration_request = read_frame(&mut adapter_end).await;
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &registration_request,
+                &REGISTER_SESSION_RESPONSE,
+            ))
+            .await
+            .unwrap();
+
+        let large_forward_open_request = read_frame(&mut adapter_end).await;
+        // Large Forward Open service
+        assert_eq!(0x5b, large_forward_open_request[40]);
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &large_forward_open_request,
+                &unsupported_response,
+            ))
+            .await
+            .unwrap();
+
+        let forward_open_request = read_frame(&mut adapter_end).await;
+        // Forward Open service
+        assert_eq!(0x54, forward_open_request[40]);
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &forward_open_request,
+                &forward_open_response,
+            ))
+            .await
+            .unwrap();
+
+        let unregistration_request = read_frame(&mut adapter_end).await;
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST.to_vec(), unregistration_request);
+    });
+
+    let client = EipClient::with_connector(duplex_connector, EipClientConfig::default())
+        .await
+        .unwrap();
+
+    let opened_connection = client
+        .large_forward_open(&LargeForwardOpenRequest::from(forward_open))
+        .await
+        .unwrap();
+    assert_eq!(0x0a0b0c01, opened_connection.forward_open.o2t_connection_id);
+    assert_eq!(0x7b3a0001, opened_connection.forward_open.t2o_connection_id);
+
+    // The T->O socket address item is kept with the connection
+    assert_eq!(None, opened_connection.o2t_socket_address);
+    assert_eq!(
+        Some("239.192.1.1:2222".parse().unwrap()),
+        opened_connection.t2o_socket_address
+    );
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_sends_routed_request_through_unconnected_send() {
+    /*
+    Common Industrial Protocol
+        Service: Unconnected Send (Request)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+        CIP Connection Manager
+            Command Specific Data
+                Priority/Time_tick: 0x0a
+                Time-out_ticks: 5
+                Message Request Size: 8
+                CIP Embedded Message Request
+                    Common Industrial Protocol
+                        Service: Get Attribute Single (Request)
+                        Request Path Size: 3 words
+                        Request Path: Assembly, Instance: 0x70, Attribute: 0x03
+                Route Path Size: 1 words
+                Reserved: 0x00
+                Route Path: Port: Backplane, Address: 0
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 2a 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 1a 00 52 04 21 00 06 00 25 00
+    0030   01 00 0a 05 08 00 0e 03 20 04 24 70 30 03 01 00
+    0040   01 00
+
+    */
+    let expected_unconnected_send_data: Vec<CipByte> = vec![
+        0xb2, 0x00, 0x1a, 0x00, 0x52, 0x04, 0x21, 0x00, 0x06, 0x00, 0x25, 0x00, 0x01, 0x00, 0x0a,
+        0x05, 0x08, 0x00, 0x0e, 0x03, 0x20, 0x04, 0x24, 0x70, 0x30, 0x03, 0x01, 0x00, 0x01, 0x00,
+    ];
+
+    // The target behind the bridge answers with its own reply
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x34,
+        0x12,
+    ];
+
+    let (adapter_end_sender, mut adapter_end_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let duplex_connector = move || {
+        let (client_end, adapter_end) = tokio::io::duplex(1024);
+        let _ = adapter_end_sender.send(adapter_end);
+
+        async move { Ok(client_end) }
+    };
+
+    let fake_bridge = tokio::spawn(async move {
+        let mut bridge_end = adapter_end_receiver.recv().await.unwrap();
+
+        let registration_request = read_frame(&mut bridge_end).await;
+        bridge_end
+            .write_all(&reply_with_sender_context(
+                &registration_request,
+                &REGISTER_SESSION_RESPONSE,
+            ))
+            .await
+            .unwrap();
+
+        let unconnected_send_request = read_frame(&mut bridge_end).await;
+        // Unconnected Send service
+        assert_eq!(0x52, unconnected_send_request[40]);
+        assert_eq!(
+            expected_unconnected_send_data,
+            unconnected_send_request[36..].to_vec()
+        );
+        bridge_end
+            .write_all(&reply_with_sender_context(
+                &unconnected_send_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+
+        let unregistration_request = read_frame(&mut bridge_end).await;
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST.to_vec(), unregistration_request);
+    });
+
+    let client = EipClient::with_connector(duplex_connector, EipClientConfig::default())
+        .await
+        .unwrap();
+
+    let attribute_value = client
+        .get_attribute_single::<CipUint>(
+            CipPath::new_full(0x4, 0x70, 0x3).with_route(&"1,0".parse().unwrap()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(0x1234, attribute_value);
+
+    client.close().await.unwrap();
+
+    fake_bridge.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_sends_connected_requests() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send Unit Data
+    Encapsulation Header
+        Command: Send Unit Data (0x0070)
+        Length: 28
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Connected Address Item (0x00a1)
+                Length: 4
+                Connection ID: 0x7b3a0001
+            Type ID: Connected Data Item (0x00b1)
+                Length: 8
+                CIP Sequence Count: 1
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Success:
+        Data: 3412
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   70 00 1c 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   a1 00 04 00 01 00 3a 7b b1 00 08 00 01 00 8e 00
+    0030   00 00 34 12
+
+    */
+    let connected_response: Vec<CipByte> = vec![
+        0x70, 0x00, 0x1c, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0xa1, 0x00, 0x04, 0x00, 0x01, 0x00, 0x3a, 0x7b, 0xb1, 0x00, 0x08, 0x00, 0x01,
+        0x00, 0x8e, 0x00, 0x00, 0x00, 0x34, 0x12,
+    ];
+
+    // The same reply for sequence count 2, with the data 0x5678
+    let mut second_connected_response = connected_response.clone();
+    second_connected_response[44] = 0x02;
+    second_connected_response[50] = 0x78;
+    second_connected_response[51] = 0x56;
+
+    let (adapter_end_sender, mut adapter_end_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let duplex_connector = move || {
+        let (client_end, adapter_end) = tokio::io::duplex(1024);
+        let _ = adapter_end_sender.send(adapter_end);
+
+        async move { Ok(client_end) }
+    };
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_end = adapter_end_receiver.recv().await.unwrap();
+
+        let registration_request = read_frame(&mut adapter_end).await;
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &registration_request,
+                &REGISTER_SESSION_RESPONSE,
+            ))
+            .await
+            .unwrap();
+
+        // Requests go out as SendUnitData on the O->T connection ID, numbered in order
+        let first_request = read_frame(&mut adapter_end).await;
+        assert_eq!([0x70, 0x00], first_request[..2]);
+        assert_eq!([0x01, 0x0c, 0x0b, 0x0a], first_request[36..40]);
+        assert_eq!(0x01, first_request[44]);
+        adapter_end.write_all(&connected_response).await.unwrap();
+
+        // A repeated reply to the first request comes before the reply to the second one
+        let second_request = read_frame(&mut adapter_end).await;
+        assert_eq!(0x02, second_request[44]);
+        adapter_end.write_all(&connected_response).await.unwrap();
+        adapter_end
+            .write_all(&second_connected_response)
+            .await
+            .unwrap();
+
+        let unregistration_request = read_frame(&mut adapter_end).await;
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST.to_vec(), unregistration_request);
+    });
+
+    let client = EipClient::with_connector(duplex_connector, EipClientConfig::default())
+        .await
+        .unwrap();
+
+    let mut explicit_connection = ExplicitConnection::new(0x06, 0x0a0b0c01, 0x7b3a0001);
+
+    for expected_value in [0x1234, 0x5678] {
+        let attribute_value = client
+            .send_connected_service::<u8, CipUint>(
+                &mut explicit_connection,
+                CipPath::new_full(0x4, 0x70, 0x3),
+                ServiceCode::GetAttributeSingle,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(Some(expected_value), attribute_value);
+    }
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_reports_oversized_reply_frame() {
+    // A 6 byte attribute value makes the reply larger than the request
+    let get_attribute_response: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x1a, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x0a, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x01,
+        0x02, 0x03, 0x04, 0x05, 0x06,
+    ];
+
+    let (adapter_end_sender, mut adapter_end_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let duplex_connector = move || {
+        let (client_end, adapter_end) = tokio::io::duplex(1024);
+        let _ = adapter_end_sender.send(adapter_end);
+
+        async move { Ok(client_end) }
+    };
+
+    let fake_adapter = tokio::spawn(async move {
+        let mut adapter_end = adapter_end_receiver.recv().await.unwrap();
+
+        let registration_request = read_frame(&mut adapter_end).await;
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &registration_request,
+                &REGISTER_SESSION_RESPONSE,
+            ))
+            .await
+            .unwrap();
+
+        let get_attribute_request = read_frame(&mut adapter_end).await;
+        adapter_end
+            .write_all(&reply_with_sender_context(
+                &get_attribute_request,
+                &get_attribute_response,
+            ))
+            .await
+            .unwrap();
+
+        let unregistration_request = read_frame(&mut adapter_end).await;
+        assert_eq_hex!(UNREGISTER_SESSION_REQUEST.to_vec(), unregistration_request);
+    });
+
+    let client_config = EipClientConfig {
+        max_frame_size: 48,
+        ..EipClientConfig::default()
+    };
+    let client = EipClient::with_connector(duplex_connector, client_config)
+        .await
+        .unwrap();
+
+    // The waiting request gets the framing error instead of a closed connection
+    let get_result = client
+        .get_attribute_single::<[u8; 6]>(CipPath::new_full(0x4, 0x70, 0x3))
+        .await;
+    assert!(
+        matches!(
+            get_result,
+            Err(EipError::FrameTooLarge {
+                frame_size: 50,
+                max_frame_size: 48,
+            })
+        ),
+        "Expected a frame size error, got {:?}",
+        get_result
+    );
+
+    client.close().await.unwrap();
+
+    fake_adapter.await.unwrap();
+}
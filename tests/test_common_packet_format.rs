@@ -0,0 +1,228 @@
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::description::{
+    CommonPacketItem, CommonPacketList, RawPacketItem, SequencedAddress, SockAddrInfo,
+};
+
+#[test]
+fn test_deserialize_connected_common_packet_list() {
+    /*
+    Common Packet Format: Sequenced Address Item, Connected Data Item
+        Item Count: 2
+            Type ID: Sequenced Address Item (0x8002)
+                Length: 8
+                Connection ID: 0x10002a01
+                Encapsulation Sequence Number: 7
+            Type ID: Connected Data Item (0x00b1)
+                Length: 6
+                CIP Sequence Count: 7
+                Data: 01000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   02 00 02 80 08 00 01 2a 00 10 07 00 00 00 b1 00
+    0010   06 00 07 00 01 00 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x02, 0x00, 0x02, 0x80, 0x08, 0x00, 0x01, 0x2a, 0x00, 0x10, 0x07, 0x00, 0x00, 0x00, 0xb1,
+        0x00, 0x06, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x00,
+    ];
+
+    let packet_list =
+        CommonPacketList::read_le(&mut std::io::Cursor::new(raw_bytes.clone())).unwrap();
+
+    let expected_packet_list = CommonPacketList::new(vec![
+        CommonPacketItem::SequencedAddress(SequencedAddress {
+            connection_identifier: 0x10002a01,
+            encapsulation_sequence_number: 7,
+        }),
+        CommonPacketItem::ConnectedTransportPacket(Some(vec![0x07, 0x00, 0x01, 0x00, 0x00, 0x00])),
+    ]);
+
+    assert_eq!(expected_packet_list, packet_list);
+    assert_eq!(
+        Some(&vec![0x07, 0x00, 0x01, 0x00, 0x00, 0x00]),
+        packet_list.connected_transport_packet()
+    );
+    assert_eq!(None, packet_list.unconnected_message());
+
+    // Writing the list back out should reproduce the same bytes
+    let mut packet_list_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut packet_list_bytes);
+
+    packet_list.write_le(&mut writer).unwrap();
+
+    assert_eq_hex!(raw_bytes, packet_list_bytes);
+}
+
+#[test]
+fn test_deserialize_unknown_common_packet_item() {
+    /*
+    Common Packet Format: Connected Address Item, Unknown Item
+        Item Count: 2
+            Type ID: Connected Address Item (0x00a1)
+                Length: 4
+                Connection ID: 0x00000042
+            Type ID: Unknown (0x9000)
+                Length: 2
+                Data: beef
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   02 00 a1 00 04 00 42 00 00 00 00 90 02 00 be ef
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x02, 0x00, 0xa1, 0x00, 0x04, 0x00, 0x42, 0x00, 0x00, 0x00, 0x00, 0x90, 0x02, 0x00, 0xbe,
+        0xef,
+    ];
+
+    let packet_list =
+        CommonPacketList::read_le(&mut std::io::Cursor::new(raw_bytes.clone())).unwrap();
+
+    let expected_packet_list = CommonPacketList::new(vec![
+        CommonPacketItem::ConnectedAddress(0x42),
+        CommonPacketItem::Unknown(RawPacketItem {
+            type_id: 0x9000,
+            data: vec![0xbe, 0xef],
+        }),
+    ]);
+
+    assert_eq!(expected_packet_list, packet_list);
+
+    let mut packet_list_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut packet_list_bytes);
+
+    packet_list.write_le(&mut writer).unwrap();
+
+    assert_eq_hex!(raw_bytes, packet_list_bytes);
+}
+
+#[test]
+fn test_deserialize_malformed_connected_address_item() {
+    // The connected address item only has 2 of its 4 bytes
+    let raw_bytes: Vec<CipByte> = vec![0x01, 0x00, 0xa1, 0x00, 0x02, 0x00, 0x42, 0x00];
+
+    let packet_list_result = CommonPacketList::read_le(&mut std::io::Cursor::new(raw_bytes));
+
+    assert!(packet_list_result.is_err());
+}
+
+#[test]
+fn test_serialize_separately_written_data_item() {
+    let expected_byte_array: Vec<CipByte> =
+        vec![0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x0a, 0x00];
+
+    let packet_list = CommonPacketList::new(vec![
+        CommonPacketItem::NullAddress,
+        CommonPacketItem::UnconnectedMessage(None),
+    ]);
+
+    let mut packet_list_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut packet_list_bytes);
+
+    // The 10 byte unconnected message is written after the list
+    packet_list
+        .write_options(&mut writer, binrw::Endian::Little, (Some(10),))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, packet_list_bytes);
+}
+
+#[test]
+fn test_serialize_sock_addr_info() {
+    /*
+    Type ID: Socket Address Info T->O (0x8001)
+        Length: 16
+        sin_family: 2
+        sin_port: 2222
+        sin_addr: 239.192.1.32
+        sin_zero: 0000000000000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   01 80 10 00 00 02 08 ae ef c0 01 20 00 00 00 00
+    0010   00 00 00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x01, 0x80, 0x10, 0x00, 0x00, 0x02, 0x08, 0xae, 0xef, 0xc0, 0x01, 0x20, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let multicast_address = SocketAddrV4::new(Ipv4Addr::new(239, 192, 1, 32), 2222);
+
+    let t2o_item = CommonPacketItem::T2OSockAddrInfo(SockAddrInfo::from(multicast_address));
+
+    let mut t2o_item_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut t2o_item_bytes);
+
+    t2o_item.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, t2o_item_bytes);
+}
+
+#[test]
+fn test_deserialize_forward_open_reply_sock_addr_items() {
+    /*
+    Common Packet Format: Null Address Item, Unconnected Data Item, Socket Address Info O->T, Socket Address Info T->O
+        Item Count: 4
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 4
+                Data: d4000000
+            Type ID: Socket Address Info O->T (0x8000)
+                Length: 16
+                sin_family: 2
+                sin_port: 2222
+                sin_addr: 172.31.19.10
+                sin_zero: 0000000000000000
+            Type ID: Socket Address Info T->O (0x8001)
+                Length: 16
+                sin_family: 2
+                sin_port: 2222
+                sin_addr: 239.192.1.32
+                sin_zero: 0000000000000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 00 00 00 00 00 b2 00 04 00 d4 00 00 00 00 80
+    0010   10 00 00 02 08 ae ac 1f 13 0a 00 00 00 00 00 00
+    0020   00 00 01 80 10 00 00 02 08 ae ef c0 01 20 00 00
+    0030   00 00 00 00 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x04, 0x00, 0xd4, 0x00, 0x00, 0x00, 0x00,
+        0x80, 0x10, 0x00, 0x00, 0x02, 0x08, 0xae, 0xac, 0x1f, 0x13, 0x0a, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x80, 0x10, 0x00, 0x00, 0x02, 0x08, 0xae, 0xef, 0xc0, 0x01,
+        0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let packet_list = CommonPacketList::read_le(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(4, packet_list.items.len());
+    assert_eq!(
+        Some(&vec![0xd4, 0x00, 0x00, 0x00]),
+        packet_list.unconnected_message()
+    );
+    assert_eq!(
+        Some(SocketAddrV4::new(Ipv4Addr::new(172, 31, 19, 10), 2222)),
+        packet_list.o2t_socket_address()
+    );
+    assert_eq!(
+        Some(SocketAddrV4::new(Ipv4Addr::new(239, 192, 1, 32), 2222)),
+        packet_list.t2o_socket_address()
+    );
+}
@@ -0,0 +1,187 @@
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::message::response::{
+    MessageRouterResponse, ResponseData, ResponseStatusCode,
+};
+use eipscanne_rs::cip::message::shared::{ServiceCode, ServiceContainer};
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::command::{CommandSpecificData, EnIpCommand};
+use eipscanne_rs::object_assembly::{ConnectedResponseObjectAssembly, ExplicitConnection};
+
+/*
+EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send Unit Data
+    Encapsulation Header
+        Command: Send Unit Data (0x0070)
+        Length: 28
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Connected Address Item (0x00a1)
+                Length: 4
+                Connection ID: 0x7b3a0001
+            Type ID: Connected Data Item (0x00b1)
+                Length: 8
+                CIP Sequence Count: 1
+Common Industrial Protocol
+    Service: Get Attribute Single (Response)
+    Status: Success:
+    Data: 3412
+
+-------------------------------------
+Hex Dump:
+
+0000   70 00 1c 00 06 00 00 00 00 00 00 00 00 00 00 00
+0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+0020   a1 00 04 00 01 00 3a 7b b1 00 08 00 01 00 8e 00
+0030   00 00 34 12
+
+*/
+const CONNECTED_RESPONSE_BYTES: [CipByte; 52] = [
+    0x70, 0x00, 0x1c, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00,
+    0xa1, 0x00, 0x04, 0x00, 0x01, 0x00, 0x3a, 0x7b, 0xb1, 0x00, 0x08, 0x00, 0x01, 0x00, 0x8e, 0x00,
+    0x00, 0x00, 0x34, 0x12,
+];
+
+#[test]
+fn test_serialize_connected_get_attribute_single_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send Unit Data
+    Encapsulation Header
+        Command: Send Unit Data (0x0070)
+        Length: 30
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Connected Address Item (0x00a1)
+                Length: 4
+                Connection ID: 0x10002a01
+            Type ID: Connected Data Item (0x00b1)
+                Length: 10
+                CIP Sequence Count: 1
+    Common Industrial Protocol
+        Service: Get Attribute Single (Request)
+        Request Path Size: 3 words
+        Request Path: Assembly, Instance: 0x70, Attribute: 0x03
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   70 00 1e 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   a1 00 04 00 01 2a 00 10 b1 00 0a 00 01 00 0e 03
+    0030   20 04 24 70 30 03
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x70, 0x00, 0x1e, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0xa1, 0x00, 0x04, 0x00, 0x01, 0x2a, 0x00, 0x10, 0xb1, 0x00, 0x0a, 0x00, 0x01,
+        0x00, 0x0e, 0x03, 0x20, 0x04, 0x24, 0x70, 0x30, 0x03,
+    ];
+
+    let mut explicit_connection = ExplicitConnection::new(0x06, 0x10002a01, 0x7b3a0001);
+
+    let connected_request = explicit_connection.new_service_request::<u8>(
+        CipPath::new_full(0x04, 0x70, 0x03),
+        ServiceCode::GetAttributeSingle,
+        None,
+    );
+
+    assert_eq!(1, connected_request.sequence_count);
+
+    let mut connected_request_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut connected_request_byte_array);
+
+    connected_request.write(&mut writer).unwrap();
+
+    // Assert equality
+    assert_eq_hex!(expected_byte_array, connected_request_byte_array);
+}
+
+#[test]
+fn test_deserialize_connected_get_attribute_single_response() {
+    let byte_cursor = std::io::Cursor::new(CONNECTED_RESPONSE_BYTES);
+    let mut buf_reader = std::io::BufReader::new(byte_cursor);
+
+    let connected_response = ConnectedResponseObjectAssembly::<u16>::read(&mut buf_reader).unwrap();
+
+    let expected_cip_message = MessageRouterResponse {
+        service_container: ServiceContainer::new(ServiceCode::GetAttributeSingle, true),
+        response_data: ResponseData {
+            status: ResponseStatusCode::Success,
+            additional_status_size: 0x0,
+            additional_status: vec![],
+            data: Some(0x1234),
+        },
+    };
+
+    assert_eq!(
+        EnIpCommand::SendUnitData,
+        connected_response.packet_description.header.command
+    );
+    assert!(matches!(
+        connected_response.packet_description.command_specific_data,
+        CommandSpecificData::SendUnitData(_)
+    ));
+    assert_eq!(Some(0x7b3a0001), connected_response.connection_identifier());
+    assert_eq!(1, connected_response.sequence_count);
+    assert_eq!(expected_cip_message, connected_response.cip_message);
+}
+
+#[test]
+fn test_match_connected_reply_by_sequence_count() {
+    let mut explicit_connection = ExplicitConnection::new(0x06, 0x10002a01, 0x7b3a0001);
+
+    let connected_response = ConnectedResponseObjectAssembly::<u16>::read(
+        &mut std::io::Cursor::new(CONNECTED_RESPONSE_BYTES),
+    )
+    .unwrap();
+
+    let first_request = explicit_connection.new_service_request::<u8>(
+        CipPath::new_full(0x04, 0x70, 0x03),
+        ServiceCode::GetAttributeSingle,
+        None,
+    );
+    assert!(explicit_connection.is_reply_to(&connected_response, first_request.sequence_count));
+
+    let second_request = explicit_connection.new_service_request::<u8>(
+        CipPath::new_full(0x04, 0x70, 0x03),
+        ServiceCode::GetAttributeSingle,
+        None,
+    );
+    assert_eq!(2, explicit_connection.sequence_count());
+    assert!(!explicit_connection.is_reply_to(&connected_response, second_request.sequence_count));
+
+    // Replies on a different connection never match
+    let other_connection = ExplicitConnection::new(0x06, 0x10002a02, 0x7b3a0002);
+    assert!(!other_connection.is_reply_to(&connected_response, 1));
+}
+
+#[test]
+fn test_deserialize_connected_response_without_transport_packet() {
+    // A SendUnitData reply that only carries the connected address item
+    let raw_bytes: Vec<CipByte> = vec![
+        0x70, 0x00, 0x12, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0xa1, 0x00, 0x04, 0x00, 0x01, 0x00, 0x3a, 0x7b,
+    ];
+
+    let connected_response_result =
+        ConnectedResponseObjectAssembly::<u16>::read(&mut std::io::Cursor::new(raw_bytes));
+
+    assert!(connected_response_result.is_err());
+}
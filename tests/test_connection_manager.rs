@@ -0,0 +1,510 @@
+use binrw::{BinRead, BinWrite};
+
+use bilge::prelude::{u4, u9};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::connection_manager::{
+    ConnectionFailure, ConnectionPriority, ConnectionTriad, ConnectionType, ForwardCloseReply,
+    ForwardCloseSuccess, ForwardOpenReply, ForwardOpenRequest, ForwardOpenSuccess,
+    LargeForwardOpenRequest, LargeNetworkConnectionParameters, NetworkConnectionParameters,
+    PriorityTimeTick, ProductionTrigger, TransportClassTrigger,
+};
+use eipscanne_rs::cip::message::request::MessageRouterRequest;
+use eipscanne_rs::cip::message::response::ResponseStatusCode;
+use eipscanne_rs::cip::message::shared::{ServiceCode, ServiceContainer};
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::object_assembly::{RequestObjectAssembly, ServiceReplyObjectAssembly};
+
+fn clearlink_connection_triad() -> ConnectionTriad {
+    ConnectionTriad {
+        connection_serial_number: 0x1234,
+        originator_vendor_id: 0x01a8,
+        originator_serial_number: 0x00c0ffee,
+    }
+}
+
+fn clearlink_forward_open_request() -> ForwardOpenRequest {
+    ForwardOpenRequest {
+        priority_time_tick: PriorityTimeTick::new(u4::new(0xa), false),
+        timeout_ticks: 0x0e,
+        o2t_connection_id: 0x00000000,
+        t2o_connection_id: 0x7b3a0001,
+        connection_triad: clearlink_connection_triad(),
+        connection_timeout_multiplier: 0x01,
+        o2t_rpi: 100000,
+        o2t_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(32),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::PointToPoint,
+            false,
+        ),
+        t2o_rpi: 100000,
+        t2o_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(26),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::Multicast,
+            false,
+        ),
+        transport_class_trigger: TransportClassTrigger::new(
+            u4::new(0x1),
+            ProductionTrigger::Cyclic,
+            false,
+        ),
+        connection_path: CipPath::default()
+            .with_class(0x04)
+            .with_instance(0x80)
+            .with_connection_point(0x96)
+            .with_connection_point(0x64),
+    }
+}
+
+#[test]
+fn test_serialize_forward_open_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 70
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 54
+    Common Industrial Protocol
+        Service: Forward Open (Request)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+    CIP Connection Manager
+        Priority/Time_tick: 0x0a
+        Time-out_ticks: 14
+        O->T Network Connection ID: 0x00000000
+        T->O Network Connection ID: 0x7b3a0001
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        Connection Timeout Multiplier: *8 (1)
+        Reserved: 000000
+        O->T RPI: 100.000ms
+        O->T Network Connection Parameters: 0x4820
+            0... .... .... .... = Owner: Exclusive (0)
+            .10. .... .... .... = Connection Type: Point to Point (2)
+            .... 10.. .... .... = Priority: Scheduled (2)
+            .... ..0. .... .... = Connection Size Type: Fixed (0)
+            .... ...0 0010 0000 = Connection Size: 32
+        T->O RPI: 100.000ms
+        T->O Network Connection Parameters: 0x281a
+            0... .... .... .... = Owner: Exclusive (0)
+            .01. .... .... .... = Connection Type: Multicast (1)
+            .... 10.. .... .... = Priority: Scheduled (2)
+            .... ..0. .... .... = Connection Size Type: Fixed (0)
+            .... ...0 0001 1010 = Connection Size: 26
+        Transport Type/Trigger: 0x01, Direction: Client, Trigger: Cyclic, Class: 1
+        Connection Path Size: 4 (words)
+        Connection Path: Assembly, Instance: 0x80, Connection Point: 0x96, Connection Point: 0x64
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 46 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 36 00 54 04 21 00 06 00 25 00
+    0030   01 00 0a 0e 00 00 00 00 01 00 3a 7b 34 12 a8 01
+    0040   ee ff c0 00 01 00 00 00 a0 86 01 00 20 48 a0 86
+    0050   01 00 1a 28 01 04 20 04 24 80 2c 96 2c 64
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x46, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x36, 0x00, 0x54, 0x04, 0x21, 0x00, 0x06,
+        0x00, 0x25, 0x00, 0x01, 0x00, 0x0a, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x3a, 0x7b,
+        0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x01, 0x00, 0x00, 0x00, 0xa0, 0x86, 0x01,
+        0x00, 0x20, 0x48, 0xa0, 0x86, 0x01, 0x00, 0x1a, 0x28, 0x01, 0x04, 0x20, 0x04, 0x24, 0x80,
+        0x2c, 0x96, 0x2c, 0x64,
+    ];
+
+    let forward_open_request =
+        RequestObjectAssembly::new_forward_open(0x06, clearlink_forward_open_request());
+
+    let mut forward_open_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut forward_open_byte_array);
+
+    forward_open_request.write(&mut writer).unwrap();
+
+    // Assert equality
+    assert_eq_hex!(expected_byte_array, forward_open_byte_array);
+}
+
+#[test]
+fn test_deserialize_forward_open_success_reply() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 46
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 30
+    Common Industrial Protocol
+        Service: Forward Open (Response)
+        Status: Success:
+    CIP Connection Manager
+        O->T Network Connection ID: 0x0a0b0c01
+        T->O Network Connection ID: 0x7b3a0001
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        O->T API: 100.000ms
+        T->O API: 100.000ms
+        Application Reply Size: 0 (words)
+        Reserved: 0x00
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 2e 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 1e 00 d4 00 00 00 01 0c 0b 0a
+    0030   01 00 3a 7b 34 12 a8 01 ee ff c0 00 a0 86 01 00
+    0040   a0 86 01 00 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x2e, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x1e, 0x00, 0xd4, 0x00, 0x00, 0x00, 0x01,
+        0x0c, 0x0b, 0x0a, 0x01, 0x00, 0x3a, 0x7b, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00,
+        0xa0, 0x86, 0x01, 0x00, 0xa0, 0x86, 0x01, 0x00, 0x00, 0x00,
+    ];
+
+    let byte_cursor = std::io::Cursor::new(raw_bytes);
+    let mut buf_reader = std::io::BufReader::new(byte_cursor);
+
+    let forward_open_response =
+        ServiceReplyObjectAssembly::<ForwardOpenReply>::read(&mut buf_reader).unwrap();
+
+    let expected_forward_open_reply = ForwardOpenReply {
+        service_container: ServiceContainer::new(ServiceCode::ForwardOpen, true),
+        general_status: ResponseStatusCode::Success,
+        additional_status: vec![],
+        success: Some(ForwardOpenSuccess {
+            o2t_connection_id: 0x0a0b0c01,
+            t2o_connection_id: 0x7b3a0001,
+            connection_triad: clearlink_connection_triad(),
+            o2t_api: 100000,
+            t2o_api: 100000,
+            application_reply: vec![],
+        }),
+        failure: None,
+    };
+
+    assert_eq!(
+        Some(expected_forward_open_reply),
+        forward_open_response.cip_reply
+    );
+}
+
+#[test]
+fn test_deserialize_forward_open_failure_reply() {
+    /*
+    Common Industrial Protocol
+        Service: Forward Open (Response)
+        Status: Connection failure: Connection in use or duplicate forward open
+            General Status: Connection failure (0x01)
+            Additional Status Size: 1 words
+            Extended Status: 0x0100
+    CIP Connection Manager
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        Remaining Path Size: 0
+        Reserved: 0x00
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   d4 00 01 01 00 01 34 12 a8 01 ee ff c0 00 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0xd4, 0x00, 0x01, 0x01, 0x00, 0x01, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x00,
+        0x00,
+    ];
+
+    let forward_open_reply =
+        ForwardOpenReply::read_le(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(
+        ResponseStatusCode::ConnectionFailure,
+        forward_open_reply.general_status
+    );
+    assert_eq!(Some(0x0100), forward_open_reply.extended_status());
+    assert_eq!(None, forward_open_reply.success);
+    assert_eq!(
+        Some(ConnectionFailure {
+            connection_triad: clearlink_connection_triad(),
+            remaining_path_size: 0,
+        }),
+        forward_open_reply.failure
+    );
+
+    // An unsupported service reply has no failure data at all
+    let unsupported_bytes: Vec<CipByte> = vec![0xd4, 0x00, 0x08, 0x00];
+
+    let unsupported_reply =
+        ForwardOpenReply::read_le(&mut std::io::Cursor::new(unsupported_bytes)).unwrap();
+
+    assert_eq!(
+        ResponseStatusCode::ServiceNotSupported,
+        unsupported_reply.general_status
+    );
+    assert_eq!(None, unsupported_reply.extended_status());
+    assert_eq!(None, unsupported_reply.failure);
+}
+
+#[test]
+fn test_serialize_forward_close_request() {
+    /*
+    Common Industrial Protocol
+        Service: Forward Close (Request)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+    CIP Connection Manager
+        Priority/Time_tick: 0x0a
+        Time-out_ticks: 14
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        Connection Path Size: 4 (words)
+        Reserved: 0x00
+        Connection Path: Assembly, Instance: 0x80, Connection Point: 0x96, Connection Point: 0x64
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   4e 04 21 00 06 00 25 00 01 00 0a 0e 34 12 a8 01
+    0010   ee ff c0 00 04 00 20 04 24 80 2c 96 2c 64
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x4e, 0x04, 0x21, 0x00, 0x06, 0x00, 0x25, 0x00, 0x01, 0x00, 0x0a, 0x0e, 0x34, 0x12, 0xa8,
+        0x01, 0xee, 0xff, 0xc0, 0x00, 0x04, 0x00, 0x20, 0x04, 0x24, 0x80, 0x2c, 0x96, 0x2c, 0x64,
+    ];
+
+    let forward_close_message = MessageRouterRequest::new_data(
+        ServiceCode::ForwardClose,
+        CipPath::new(0x06, 0x01),
+        Some(clearlink_forward_open_request().close_request()),
+    );
+
+    let mut forward_close_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut forward_close_byte_array);
+
+    forward_close_message.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, forward_close_byte_array);
+}
+
+#[test]
+fn test_serialize_routed_forward_close_request() {
+    /*
+    Common Industrial Protocol
+        Service: Forward Close (Request)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+    CIP Connection Manager
+        Priority/Time_tick: 0x0a
+        Time-out_ticks: 14
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        Connection Path Size: 5 (words)
+        Reserved: 0x00
+        Connection Path: Port: Backplane, Address: 2, Assembly, Instance: 0x80, Connection Point: 0x96, Connection Point: 0x64
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   4e 04 21 00 06 00 25 00 01 00 0a 0e 34 12 a8 01
+    0010   ee ff c0 00 05 00 01 02 20 04 24 80 2c 96 2c 64
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x4e, 0x04, 0x21, 0x00, 0x06, 0x00, 0x25, 0x00, 0x01, 0x00, 0x0a, 0x0e, 0x34, 0x12, 0xa8,
+        0x01, 0xee, 0xff, 0xc0, 0x00, 0x05, 0x00, 0x01, 0x02, 0x20, 0x04, 0x24, 0x80, 0x2c, 0x96,
+        0x2c, 0x64,
+    ];
+
+    // The module in slot 2 of the chassis owns the connection
+    let routed_forward_open = clearlink_forward_open_request().with_route(&"1,2".parse().unwrap());
+
+    let forward_close_message = MessageRouterRequest::new_data(
+        ServiceCode::ForwardClose,
+        CipPath::new(0x06, 0x01),
+        Some(routed_forward_open.close_request()),
+    );
+
+    let mut forward_close_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut forward_close_byte_array);
+
+    forward_close_message.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, forward_close_byte_array);
+}
+
+#[test]
+fn test_deserialize_forward_close_success_reply() {
+    let raw_bytes: Vec<CipByte> = vec![
+        0xce, 0x00, 0x00, 0x00, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x00, 0x00,
+    ];
+
+    let forward_close_reply =
+        ForwardCloseReply::read_le(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let expected_forward_close_reply = ForwardCloseReply {
+        service_container: ServiceContainer::new(ServiceCode::ForwardClose, true),
+        general_status: ResponseStatusCode::Success,
+        additional_status: vec![],
+        success: Some(ForwardCloseSuccess {
+            connection_triad: clearlink_connection_triad(),
+            application_reply: vec![],
+        }),
+        failure: None,
+    };
+
+    assert_eq!(expected_forward_close_reply, forward_close_reply);
+}
+
+fn clearlink_large_forward_open_request() -> LargeForwardOpenRequest {
+    let mut large_forward_open = LargeForwardOpenRequest::from(clearlink_forward_open_request());
+
+    // The output assembly plus the configuration data no longer fits in 511 bytes
+    large_forward_open.o2t_network_connection_parameters = LargeNetworkConnectionParameters::new(
+        600,
+        false,
+        ConnectionPriority::Scheduled,
+        ConnectionType::PointToPoint,
+        false,
+    );
+
+    large_forward_open
+}
+
+#[test]
+fn test_serialize_large_forward_open_request() {
+    /*
+    Common Industrial Protocol
+        Service: Large Forward Open (Request)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+    CIP Connection Manager
+        Priority/Time_tick: 0x0a
+        Time-out_ticks: 14
+        O->T Network Connection ID: 0x00000000
+        T->O Network Connection ID: 0x7b3a0001
+        Connection Serial Number: 0x1234
+        Originator Vendor ID: Teknic, Inc. (0x01a8)
+        Originator Serial Number: 0x00c0ffee
+        Connection Timeout Multiplier: *8 (1)
+        Reserved: 000000
+        O->T RPI: 100.000ms
+        O->T Network Connection Parameters: 0x48000258
+            0... .... .... .... .... .... .... .... = Owner: Exclusive (0)
+            .10. .... .... .... .... .... .... .... = Connection Type: Point to Point (2)
+            .... 10.. .... .... .... .... .... .... = Priority: Scheduled (2)
+            .... ..0. .... .... .... .... .... .... = Connection Size Type: Fixed (0)
+            .... .... .... .... 0000 0010 0101 1000 = Connection Size: 600
+        T->O RPI: 100.000ms
+        T->O Network Connection Parameters: 0x2800001a
+            0... .... .... .... .... .... .... .... = Owner: Exclusive (0)
+            .01. .... .... .... .... .... .... .... = Connection Type: Multicast (1)
+            .... 10.. .... .... .... .... .... .... = Priority: Scheduled (2)
+            .... ..0. .... .... .... .... .... .... = Connection Size Type: Fixed (0)
+            .... .... .... .... 0000 0000 0001 1010 = Connection Size: 26
+        Transport Type/Trigger: 0x01, Direction: Client, Trigger: Cyclic, Class: 1
+        Connection Path Size: 4 (words)
+        Connection Path: Assembly, Instance: 0x80, Connection Point: 0x96, Connection Point: 0x64
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   5b 04 21 00 06 00 25 00 01 00 0a 0e 00 00 00 00
+    0010   01 00 3a 7b 34 12 a8 01 ee ff c0 00 01 00 00 00
+    0020   a0 86 01 00 58 02 00 48 a0 86 01 00 1a 00 00 28
+    0030   01 04 20 04 24 80 2c 96 2c 64
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x5b, 0x04, 0x21, 0x00, 0x06, 0x00, 0x25, 0x00, 0x01, 0x00, 0x0a, 0x0e, 0x00, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0x3a, 0x7b, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0xa0, 0x86, 0x01, 0x00, 0x58, 0x02, 0x00, 0x48, 0xa0, 0x86, 0x01, 0x00, 0x1a,
+        0x00, 0x00, 0x28, 0x01, 0x04, 0x20, 0x04, 0x24, 0x80, 0x2c, 0x96, 0x2c, 0x64,
+    ];
+
+    let large_forward_open_request =
+        RequestObjectAssembly::new_large_forward_open(0x06, clearlink_large_forward_open_request());
+
+    let mut large_forward_open_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut large_forward_open_byte_array);
+
+    large_forward_open_request
+        .cip_message
+        .unwrap()
+        .write(&mut writer)
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, large_forward_open_byte_array);
+}
+
+#[test]
+fn test_large_forward_open_fallback_request() {
+    // General Status: Service not supported (0x08)
+    let unsupported_reply =
+        ForwardOpenReply::read_le(&mut std::io::Cursor::new(vec![0xdb, 0x00, 0x08, 0x00])).unwrap();
+
+    // Sizes that fit in the standard parameters fall back to the original Forward_Open
+    let small_large_forward_open = LargeForwardOpenRequest::from(clearlink_forward_open_request());
+
+    assert_eq!(
+        Some(clearlink_forward_open_request()),
+        small_large_forward_open.fallback_request(&unsupported_reply)
+    );
+
+    // There is no standard Forward_Open that can carry a 600 byte connection
+    assert_eq!(
+        None,
+        clearlink_large_forward_open_request().fallback_request(&unsupported_reply)
+    );
+
+    // Any other failure is not a reason to fall back
+    let connection_in_use_reply = ForwardOpenReply::read_le(&mut std::io::Cursor::new(vec![
+        0xdb, 0x00, 0x01, 0x01, 0x00, 0x01, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x00,
+        0x00,
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        None,
+        small_large_forward_open.fallback_request(&connection_in_use_reply)
+    );
+}
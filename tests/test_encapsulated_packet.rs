@@ -8,7 +8,9 @@ use eipscanne_rs::cip::types::{CipByte, CipUint};
 use eipscanne_rs::eip::command::{
     CommandSpecificData, EnIpCommand, EncapsStatusCode, RRPacketData,
 };
+use eipscanne_rs::eip::description::CommonPacketItem;
 use eipscanne_rs::eip::packet::{EnIpPacketDescription, EncapsulationHeader};
+use eipscanne_rs::object_assembly::ResponseObjectAssembly;
 
 #[test]
 fn test_cast_encaps_command() {
@@ -185,10 +187,17 @@ fn test_deserialize_identity_object_response_encapsulated_packet() {
             sender_context: [0x00; 8],
             options: 0x00,
         },
-        command_specific_data: CommandSpecificData::SendRrData(RRPacketData::test_with_size(
+        command_specific_data: CommandSpecificData::SendRrData(RRPacketData::with_items(
             0x0,
             0,
-            Some(28),
+            vec![
+                CommonPacketItem::NullAddress,
+                CommonPacketItem::UnconnectedMessage(Some(vec![
+                    0x81, 0x00, 0x00, 0x00, 0xa8, 0x01, 0x2b, 0x00, 0x01, 0x00, 0x02, 0x5d, 0x00,
+                    0x00, 0x32, 0x3d, 0xff, 0x01, 0x09, 0x43, 0x6c, 0x65, 0x61, 0x72, 0x4c, 0x69,
+                    0x6e, 0x6b,
+                ])),
+            ],
         )),
     };
 
@@ -232,9 +241,10 @@ fn test_deserialize_identity_object_response() {
         0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x1c, 0x00,
     ];
 
-    let byte_cursor = std::io::Cursor::new(raw_bytes);
+    let byte_cursor = std::io::Cursor::new(raw_bytes.clone());
     let mut buf_reader = std::io::BufReader::new(byte_cursor);
 
+    // Only the header of the unconnected data item is here, its 28 bytes are read separately
     let packet_description = EnIpPacketDescription::read(&mut buf_reader).unwrap();
 
     let expected_packaet_description = EnIpPacketDescription {
@@ -246,12 +256,11 @@ fn test_deserialize_identity_object_response() {
             sender_context: [0x00; 8],
             options: 0x00,
         },
-        command_specific_data: CommandSpecificData::SendRrData(RRPacketData::test_with_size(
-            0x0,
-            0,
-            Some(28),
-        )),
+        command_specific_data: CommandSpecificData::SendRrData(RRPacketData::new(0x0, 0)),
     };
 
     assert_eq!(expected_packaet_description, packet_description);
+
+    // The reply can't be decoded without the payload
+    assert!(ResponseObjectAssembly::<u8>::read(&mut std::io::Cursor::new(raw_bytes)).is_err());
 }
@@ -0,0 +1,223 @@
+use std::io::ErrorKind;
+
+use binrw::BinRead;
+
+use eipscanne_rs::cip::connection_manager::ForwardOpenReply;
+use eipscanne_rs::cip::message::response::ResponseStatusCode;
+use eipscanne_rs::cip::message::shared::ServiceCode;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::{CipByte, CipUdint};
+use eipscanne_rs::eip::command::EncapsStatusCode;
+use eipscanne_rs::eip::packet::EnIpPacketDescription;
+use eipscanne_rs::error::{EipError, EipResult};
+use eipscanne_rs::object_assembly::ResponseObjectAssembly;
+
+#[test]
+fn test_rejected_set_attribute_single_error() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 20
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 4
+    Common Industrial Protocol
+        Service: Set Attribute Single (Response)
+        Status: Attribute not settable:
+            General Status: Attribute not settable (0x0e)
+            Additional Status Size: 0 words
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 14 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 04 00 90 00 0e 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x14, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x04, 0x00, 0x90, 0x00, 0x0e, 0x00,
+    ];
+
+    let set_attribute_response =
+        ResponseObjectAssembly::<u8>::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let set_attribute_result =
+        set_attribute_response.into_result(CipPath::new_full(0x4, 0x70, 0x3));
+
+    let Err(EipError::Cip {
+        service,
+        path,
+        general_status,
+        additional_status,
+    }) = set_attribute_result
+    else {
+        panic!("Expected a CIP error, got {:?}", set_attribute_result);
+    };
+
+    assert_eq!(ServiceCode::SetAttributeSingle, service);
+    assert_eq!(Some(CipPath::new_full(0x4, 0x70, 0x3)), path);
+    assert_eq!(ResponseStatusCode::AttributeNotSettable, general_status);
+    assert!(additional_status.is_empty());
+}
+
+#[test]
+fn test_invalid_session_error() {
+    // An Unregister Session reply with the Invalid Session Handle (0x64) encapsulation status
+    let raw_bytes: Vec<CipByte> = vec![
+        0x66, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let unregister_response =
+        EnIpPacketDescription::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert!(matches!(
+        unregister_response.check_status(),
+        Err(EipError::SessionInvalid(0x06))
+    ));
+
+    assert!(matches!(
+        EipError::from_encapsulation_status(EncapsStatusCode::UnsupportedCommand, 0x06),
+        Some(EipError::Encapsulation(
+            EncapsStatusCode::UnsupportedCommand
+        ))
+    ));
+}
+
+#[test]
+fn test_unknown_encapsulation_status_error() {
+    // An Unregister Session reply with a vendor specific (0x0123) encapsulation status
+    let raw_bytes: Vec<CipByte> = vec![
+        0x66, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x23, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let unregister_response =
+        EnIpPacketDescription::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let status_result = unregister_response.check_status();
+    assert!(matches!(
+        status_result,
+        Err(EipError::Encapsulation(EncapsStatusCode::Unknown(0x0123)))
+    ));
+    assert_eq!(
+        "encapsulation status 0x00000123",
+        status_result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_forward_open_failure_error() {
+    let raw_bytes: Vec<CipByte> = vec![
+        0xd4, 0x00, 0x01, 0x01, 0x00, 0x01, 0x34, 0x12, 0xa8, 0x01, 0xee, 0xff, 0xc0, 0x00, 0x00,
+        0x00,
+    ];
+
+    let forward_open_reply =
+        ForwardOpenReply::read_le(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let forward_open_err = forward_open_reply.into_result().unwrap_err();
+
+    assert_eq!(Some(0x0100), forward_open_err.extended_status());
+    assert!(matches!(
+        forward_open_err,
+        EipError::Cip {
+            service: ServiceCode::ForwardOpen,
+            general_status: ResponseStatusCode::ConnectionFailure,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_network_and_parse_errors() {
+    fn read_truncated_packet() -> EipResult<EnIpPacketDescription> {
+        let truncated_bytes: Vec<CipByte> = vec![0x6f, 0x00, 0x14, 0x00];
+
+        Ok(EnIpPacketDescription::read(&mut std::io::Cursor::new(
+            truncated_bytes,
+        ))?)
+    }
+
+    assert!(matches!(
+        read_truncated_packet(),
+        Err(EipError::MalformedFrame(_))
+    ));
+
+    // Dropped connections and elapsed read timeouts are told apart
+    assert!(matches!(
+        EipError::from(std::io::Error::from(ErrorKind::ConnectionReset)),
+        EipError::Io(_)
+    ));
+    assert!(matches!(
+        EipError::from(std::io::Error::from(ErrorKind::TimedOut)),
+        EipError::Timeout
+    ));
+}
+
+#[test]
+fn test_truncated_reply_data_error() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000006, Send RR Data
+    Encapsulation Header
+        Command: Send RR Data (0x006f)
+        Length: 22
+        Session Handle: 0x00000006
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Interface Handle: CIP (0x00000000)
+        Timeout: 0
+        Item Count: 2
+            Type ID: Null Address Item (0x0000)
+                Length: 0
+            Type ID: Unconnected Data Item (0x00b2)
+                Length: 6
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Success:
+            General Status: Success (0x00)
+            Additional Status Size: 0 words
+        Get Attribute Single (Response)
+            Data: 0102 (only 2 of the 4 bytes of a UDINT)
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 16 00 06 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 06 00 8e 00 00 00 01 02
+
+    */
+    fn read_truncated_reply(raw_bytes: Vec<CipByte>) -> EipResult<Option<CipUdint>> {
+        ResponseObjectAssembly::<CipUdint>::read(&mut std::io::Cursor::new(raw_bytes))?
+            .into_result(CipPath::new_full(0x1, 0x1, 0x6))
+    }
+
+    let raw_bytes: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x16, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x06, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x01,
+        0x02,
+    ];
+
+    // A successful reply with unreadable data is an error, not a reply without data
+    assert!(matches!(
+        read_truncated_reply(raw_bytes),
+        Err(EipError::MalformedFrame(_))
+    ));
+}
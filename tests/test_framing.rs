@@ -0,0 +1,151 @@
+#![cfg(feature = "tokio")]
+
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::framing::{read_frame, EncapsulationCodec, DEFAULT_MAX_FRAME_SIZE};
+use eipscanne_rs::error::EipError;
+use eipscanne_rs::object_assembly::RequestObjectAssembly;
+
+/*
+EtherNet/IP (Industrial Protocol), Session: 0x00000006, Register Session
+Encapsulation Header
+    Command: Register Session (0x0065)
+    Length: 4
+    Session Handle: 0x00000006
+    Status: Success (0x00000000)
+    Sender Context: 0000000000000000
+    Options: 0x00000000
+Command Specific Data
+    Protocol Version: 1
+    Option Flags: 0x0000
+
+-------------------------------------
+Hex Dump:
+
+0000   65 00 04 00 06 00 00 00 00 00 00 00 00 00 00 00
+0010   00 00 00 00 00 00 00 00 01 00 00 00
+
+*/
+const REGISTER_SESSION_RESPONSE: [CipByte; 28] = [
+    0x65, 0x00, 0x04, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+/*
+EtherNet/IP (Industrial Protocol), Session: 0x00000006, Unregister Session
+Encapsulation Header
+    Command: Unregister Session (0x0066)
+    Length: 0
+    Session Handle: 0x00000006
+    Status: Success (0x00000000)
+    Sender Context: 0000000000000000
+    Options: 0x00000000
+
+-------------------------------------
+Hex Dump:
+
+0000   66 00 00 00 06 00 00 00 00 00 00 00 00 00 00 00
+0010   00 00 00 00 00 00 00 00
+
+*/
+const UNREGISTER_SESSION_REQUEST: [CipByte; 24] = [
+    0x66, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_decode_partial_frame() {
+    let mut codec = EncapsulationCodec::new();
+
+    // Only part of the header has arrived
+    let mut read_buffer = BytesMut::from(&REGISTER_SESSION_RESPONSE[..10]);
+    assert_eq!(None, codec.decode(&mut read_buffer).unwrap());
+
+    // The header has arrived, but not the payload it announces
+    read_buffer.extend_from_slice(&REGISTER_SESSION_RESPONSE[10..26]);
+    assert_eq!(None, codec.decode(&mut read_buffer).unwrap());
+
+    read_buffer.extend_from_slice(&REGISTER_SESSION_RESPONSE[26..]);
+    let frame = codec.decode(&mut read_buffer).unwrap().unwrap();
+
+    assert_eq_hex!(REGISTER_SESSION_RESPONSE.to_vec(), frame.to_vec());
+    assert!(read_buffer.is_empty());
+}
+
+#[test]
+fn test_decode_several_frames_in_one_read() {
+    let mut codec = EncapsulationCodec::new();
+
+    let mut read_buffer = BytesMut::new();
+    read_buffer.extend_from_slice(&REGISTER_SESSION_RESPONSE);
+    read_buffer.extend_from_slice(&UNREGISTER_SESSION_REQUEST);
+    read_buffer.extend_from_slice(&REGISTER_SESSION_RESPONSE[..4]);
+
+    let first_frame = codec.decode(&mut read_buffer).unwrap().unwrap();
+    let second_frame = codec.decode(&mut read_buffer).unwrap().unwrap();
+
+    assert_eq_hex!(REGISTER_SESSION_RESPONSE.to_vec(), first_frame.to_vec());
+    assert_eq!(UNREGISTER_SESSION_REQUEST.to_vec(), second_frame.to_vec());
+
+    // The start of the third frame is kept for the next read
+    assert_eq!(None, codec.decode(&mut read_buffer).unwrap());
+    assert_eq!(4, read_buffer.len());
+}
+
+#[test]
+fn test_decode_frame_too_large() {
+    let mut codec = EncapsulationCodec::with_max_frame_size(26);
+
+    let mut read_buffer = BytesMut::from(&REGISTER_SESSION_RESPONSE[..]);
+
+    match codec.decode(&mut read_buffer) {
+        Err(EipError::FrameTooLarge {
+            frame_size,
+            max_frame_size,
+        }) => {
+            assert_eq!(28, frame_size);
+            assert_eq!(26, max_frame_size);
+        }
+        other_result => panic!("expected FrameTooLarge, got {:?}", other_result),
+    }
+}
+
+#[test]
+fn test_encode_request() {
+    let mut codec = EncapsulationCodec::new();
+
+    let mut write_buffer = BytesMut::new();
+    codec
+        .encode(
+            RequestObjectAssembly::new_unregistration(0x06),
+            &mut write_buffer,
+        )
+        .unwrap();
+
+    assert_eq_hex!(UNREGISTER_SESSION_REQUEST.to_vec(), write_buffer.to_vec());
+}
+
+#[test]
+fn test_blocking_read_frame() {
+    let mut stream_bytes = REGISTER_SESSION_RESPONSE.to_vec();
+    stream_bytes.extend_from_slice(&UNREGISTER_SESSION_REQUEST);
+
+    let mut reader = std::io::Cursor::new(stream_bytes);
+
+    let first_frame = read_frame(&mut reader, DEFAULT_MAX_FRAME_SIZE).unwrap();
+    let second_frame = read_frame(&mut reader, DEFAULT_MAX_FRAME_SIZE).unwrap();
+
+    assert_eq_hex!(REGISTER_SESSION_RESPONSE.to_vec(), first_frame);
+    assert_eq!(UNREGISTER_SESSION_REQUEST.to_vec(), second_frame);
+
+    // The stream ends in the middle of the next header
+    let mut truncated_reader = std::io::Cursor::new(REGISTER_SESSION_RESPONSE[..10].to_vec());
+    assert!(matches!(
+        read_frame(&mut truncated_reader, DEFAULT_MAX_FRAME_SIZE),
+        Err(EipError::Io(_))
+    ));
+}
@@ -13,6 +13,7 @@ use eipscanne_rs::cip::types::{CipByte, CipShortString};
 use eipscanne_rs::eip::command::{
     CommandSpecificData, EnIpCommand, EncapsStatusCode, RRPacketData,
 };
+use eipscanne_rs::eip::description::CommonPacketItem;
 use eipscanne_rs::eip::packet::{EnIpPacketDescription, EncapsulationHeader};
 use eipscanne_rs::object_assembly::{RequestObjectAssembly, ResponseObjectAssembly};
 
@@ -250,6 +251,7 @@ fn test_deserialize_cip_identity_response() {
         response_data: ResponseData {
             status: ResponseStatusCode::Success,
             additional_status_size: 0x0,
+            additional_status: vec![],
             data: Some(IdentityResponse {
                 vendor_id: VendorId::TeknicInc,
                 device_type: DeviceType::GenericDevice,
@@ -373,59 +375,66 @@ fn test_deserialize_full_identity_response() {
         0x6c, 0x65, 0x61, 0x72, 0x4c, 0x69, 0x6e, 0x6b,
     ];
 
-    let byte_cursor = std::io::Cursor::new(identity_response_bytes);
+    let byte_cursor = std::io::Cursor::new(identity_response_bytes.clone());
     let mut buf_reader = std::io::BufReader::new(byte_cursor);
 
     let identity_response =
         ResponseObjectAssembly::<IdentityResponse>::read(&mut buf_reader).unwrap();
 
-    let expected_identity_response =
-        ResponseObjectAssembly {
-            packet_description: EnIpPacketDescription {
-                header: EncapsulationHeader {
-                    command: EnIpCommand::SendRrData,
-                    length: Some(44),
-                    session_handle: 0x06,
-                    status_code: EncapsStatusCode::Success,
-                    sender_context: [0x00; 8],
-                    options: 0x00,
-                },
-                command_specific_data: CommandSpecificData::SendRrData(
-                    RRPacketData::test_with_size(0x0, 0x0, Some(28)),
-                ),
+    let expected_identity_response = ResponseObjectAssembly {
+        packet_description: EnIpPacketDescription {
+            header: EncapsulationHeader {
+                command: EnIpCommand::SendRrData,
+                length: Some(44),
+                session_handle: 0x06,
+                status_code: EncapsStatusCode::Success,
+                sender_context: [0x00; 8],
+                options: 0x00,
             },
-            cip_message: Some(MessageRouterResponse {
-                service_container: ServiceContainer::new(ServiceCode::GetAttributeAll, true).into(),
-                response_data: ResponseData {
-                    status: ResponseStatusCode::Success,
-                    additional_status_size: 0x0,
-                    data: Some(IdentityResponse {
-                        vendor_id: VendorId::TeknicInc,
-                        device_type: DeviceType::GenericDevice,
-                        product_code: 0x1,
-                        revision: Revision {
-                            major: 2,
-                            minor: 93,
-                        },
-                        status: IdentityStatusBits::new(
-                            false,
-                            false,
-                            false,
-                            false,
-                            u4::new(0x0),
-                            false,
-                            false,
-                            false,
-                            false,
-                            u4::new(0x0),
-                        )
-                        .into(),
-                        serial_number: 0x01ff3d32,
-                        product_name: CipShortString::from("ClearLink".to_string()),
-                    }),
-                },
-            }),
-        };
+            command_specific_data: CommandSpecificData::SendRrData(RRPacketData::with_items(
+                0x0,
+                0x0,
+                vec![
+                    CommonPacketItem::NullAddress,
+                    CommonPacketItem::UnconnectedMessage(Some(
+                        identity_response_bytes[40..].to_vec(),
+                    )),
+                ],
+            )),
+        },
+        cip_message: Some(MessageRouterResponse {
+            service_container: ServiceContainer::new(ServiceCode::GetAttributeAll, true),
+            response_data: ResponseData {
+                status: ResponseStatusCode::Success,
+                additional_status_size: 0x0,
+                additional_status: vec![],
+                data: Some(IdentityResponse {
+                    vendor_id: VendorId::TeknicInc,
+                    device_type: DeviceType::GenericDevice,
+                    product_code: 0x1,
+                    revision: Revision {
+                        major: 2,
+                        minor: 93,
+                    },
+                    status: IdentityStatusBits::new(
+                        false,
+                        false,
+                        false,
+                        false,
+                        u4::new(0x0),
+                        false,
+                        false,
+                        false,
+                        false,
+                        u4::new(0x0),
+                    )
+                    .into(),
+                    serial_number: 0x01ff3d32,
+                    product_name: CipShortString::from("ClearLink".to_string()),
+                }),
+            },
+        }),
+    };
 
     // Assert equality
     assert_eq!(expected_identity_response, identity_response);
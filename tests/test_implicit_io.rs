@@ -0,0 +1,536 @@
+#![cfg(feature = "tokio")]
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use binrw::{BinRead, BinWrite};
+
+use bilge::prelude::{u4, u9};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::connection_manager::{
+    ConnectionPriority, ConnectionTriad, ConnectionType, ForwardOpenRequest, ForwardOpenSuccess,
+    NetworkConnectionParameters, OpenedConnection, PriorityTimeTick, ProductionTrigger,
+    TransportClassTrigger,
+};
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::description::SequencedAddress;
+use eipscanne_rs::implicit::connection::{ImplicitConnection, ImplicitConnectionConfig};
+use eipscanne_rs::implicit::packet::{ImplicitIoPacket, RunIdleHeader};
+use eipscanne_rs::implicit::watchdog::{self, ConnectionEvent, ConnectionWatchdog};
+
+/*
+Common Packet Format: Sequenced Address Item, Connected Data Item
+    Item Count: 2
+        Type ID: Sequenced Address Item (0x8002)
+            Length: 8
+            Connection ID: 0x7b3a0001
+            Encapsulation Sequence Number: 3
+        Type ID: Connected Data Item (0x00b1)
+            Length: 6
+            CIP Sequence Count: 3
+            Data: 78563412
+
+-------------------------------------
+Hex Dump:
+
+0000   02 00 02 80 08 00 01 00 3a 7b 03 00 00 00 b1 00
+0010   06 00 03 00 78 56 34 12
+
+*/
+const T2O_PACKET_BYTES: [CipByte; 24] = [
+    0x02, 0x00, 0x02, 0x80, 0x08, 0x00, 0x01, 0x00, 0x3a, 0x7b, 0x03, 0x00, 0x00, 0x00, 0xb1, 0x00,
+    0x06, 0x00, 0x03, 0x00, 0x78, 0x56, 0x34, 0x12,
+];
+
+fn clearlink_connection_triad() -> ConnectionTriad {
+    ConnectionTriad {
+        connection_serial_number: 0x1234,
+        originator_vendor_id: 0x01a8,
+        originator_serial_number: 0x00c0ffee,
+    }
+}
+
+// Times out after four missed APIs, the smallest connection timeout
+fn clearlink_forward_open_request() -> ForwardOpenRequest {
+    ForwardOpenRequest {
+        priority_time_tick: PriorityTimeTick::new(u4::new(0xa), false),
+        timeout_ticks: 0x0e,
+        o2t_connection_id: 0x00000000,
+        t2o_connection_id: 0x7b3a0001,
+        connection_triad: clearlink_connection_triad(),
+        connection_timeout_multiplier: 0x00,
+        o2t_rpi: 10000,
+        o2t_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(32),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::PointToPoint,
+            false,
+        ),
+        t2o_rpi: 10000,
+        t2o_network_connection_parameters: NetworkConnectionParameters::new(
+            u9::new(26),
+            false,
+            ConnectionPriority::Scheduled,
+            ConnectionType::Multicast,
+            false,
+        ),
+        transport_class_trigger: TransportClassTrigger::new(
+            u4::new(0x1),
+            ProductionTrigger::Cyclic,
+            false,
+        ),
+        connection_path: CipPath::default()
+            .with_class(0x04)
+            .with_instance(0x80)
+            .with_connection_point(0x96)
+            .with_connection_point(0x64),
+    }
+}
+
+fn clearlink_forward_open_success() -> ForwardOpenSuccess {
+    ForwardOpenSuccess {
+        o2t_connection_id: 0x0a0b0c01,
+        t2o_connection_id: 0x7b3a0001,
+        connection_triad: clearlink_connection_triad(),
+        o2t_api: 10000,
+        t2o_api: 10000,
+        application_reply: vec![],
+    }
+}
+
+#[test]
+fn test_serialize_o2t_implicit_packet() {
+    /*
+    Common Packet Format: Sequenced Address Item, Connected Data Item
+        Item Count: 2
+            Type ID: Sequenced Address Item (0x8002)
+                Length: 8
+                Connection ID: 0x0a0b0c01
+                Encapsulation Sequence Number: 7
+            Type ID: Connected Data Item (0x00b1)
+                Length: 10
+                CIP Sequence Count: 7
+                32-bit Header: 0x00000001
+                    .... .... .... .... .... .... .... ...1 = Run/Idle: Run (1)
+                Data: 01000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   02 00 02 80 08 00 01 0c 0b 0a 07 00 00 00 b1 00
+    0010   0a 00 07 00 01 00 00 00 01 00 00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x02, 0x00, 0x02, 0x80, 0x08, 0x00, 0x01, 0x0c, 0x0b, 0x0a, 0x07, 0x00, 0x00, 0x00, 0xb1,
+        0x00, 0x0a, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+    ];
+
+    let o2t_packet = ImplicitIoPacket {
+        sequenced_address: SequencedAddress {
+            connection_identifier: 0x0a0b0c01,
+            encapsulation_sequence_number: 7,
+        },
+        sequence_count: 7,
+        run_idle_header: Some(RunIdleHeader::new(true)),
+        data: 0x00000001u32,
+    };
+
+    let mut o2t_packet_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut o2t_packet_bytes);
+
+    o2t_packet.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, o2t_packet_bytes);
+}
+
+#[test]
+fn test_deserialize_t2o_implicit_packet() {
+    let t2o_packet = ImplicitIoPacket::<u32>::read_le_args(
+        &mut std::io::Cursor::new(T2O_PACKET_BYTES),
+        (false,),
+    )
+    .unwrap();
+
+    let expected_t2o_packet = ImplicitIoPacket {
+        sequenced_address: SequencedAddress {
+            connection_identifier: 0x7b3a0001,
+            encapsulation_sequence_number: 3,
+        },
+        sequence_count: 3,
+        run_idle_header: None,
+        data: 0x12345678u32,
+    };
+
+    assert_eq!(expected_t2o_packet, t2o_packet);
+}
+
+#[test]
+fn test_implicit_connection_config_from_forward_open() {
+    let mut forward_open_request = clearlink_forward_open_request();
+    forward_open_request.connection_timeout_multiplier = 0x01;
+
+    let connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::new(172, 31, 19, 10),
+        &forward_open_request,
+        &clearlink_forward_open_success(),
+    );
+
+    assert_eq!(
+        "172.31.19.10:2222".parse::<SocketAddr>().unwrap(),
+        connection_config.target_address
+    );
+    assert_eq!(
+        "0.0.0.0:2222".parse::<SocketAddr>().unwrap(),
+        connection_config.local_address
+    );
+    assert_eq!(Duration::from_millis(10), connection_config.o2t_api);
+    assert!(connection_config.o2t_run_idle_header);
+    assert!(!connection_config.t2o_run_idle_header);
+
+    // The timeout the target enforces comes from the multiplier in the request
+    assert_eq!(0x01, connection_config.connection_timeout_multiplier);
+    assert_eq!(
+        Duration::from_millis(80),
+        connection_config.connection_timeout()
+    );
+}
+
+#[tokio::test]
+async fn test_implicit_connection_on_loopback() {
+    let fake_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    let mut connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::LOCALHOST,
+        &clearlink_forward_open_request(),
+        &clearlink_forward_open_success(),
+    );
+    connection_config.local_address = "127.0.0.1:0".parse().unwrap();
+    connection_config.target_address = fake_adapter.local_addr().unwrap();
+    // Keep the connection from timing out while the adapter is quiet
+    connection_config.connection_timeout_multiplier = 7;
+
+    let implicit_connection: ImplicitConnection<u32, u32> =
+        ImplicitConnection::start(connection_config, 0x00000001)
+            .await
+            .unwrap();
+
+    // The outputs are produced every API with the run/idle header
+    let mut o2t_buffer = [0u8; 64];
+    let (o2t_bytes_read, scanner_address) = fake_adapter.recv_from(&mut o2t_buffer).await.unwrap();
+
+    let o2t_packet = ImplicitIoPacket::<u32>::read_le_args(
+        &mut std::io::Cursor::new(&o2t_buffer[..o2t_bytes_read]),
+        (true,),
+    )
+    .unwrap();
+
+    assert_eq!(implicit_connection.local_address(), scanner_address);
+    assert_eq!(
+        0x0a0b0c01,
+        o2t_packet.sequenced_address.connection_identifier
+    );
+    assert_eq!(Some(RunIdleHeader::new(true)), o2t_packet.run_idle_header);
+    assert_eq!(0x00000001, o2t_packet.data);
+
+    // A consumed T->O packet becomes the latest input
+    let mut input_receiver = implicit_connection.input_receiver();
+    fake_adapter
+        .send_to(&T2O_PACKET_BYTES, scanner_address)
+        .await
+        .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(1), input_receiver.changed())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(Some(0x12345678), implicit_connection.latest_input());
+
+    // The next production carries the new output assembly
+    implicit_connection.write_output(0x00000002);
+
+    let updated_output = tokio::time::timeout(Duration::from_secs(1), async {
+        loop {
+            let (o2t_bytes_read, _) = fake_adapter.recv_from(&mut o2t_buffer).await.unwrap();
+
+            let o2t_packet = ImplicitIoPacket::<u32>::read_le_args(
+                &mut std::io::Cursor::new(&o2t_buffer[..o2t_bytes_read]),
+                (true,),
+            )
+            .unwrap();
+
+            if o2t_packet.data == 0x00000002 {
+                return o2t_packet.data;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(0x00000002, updated_output);
+}
+
+#[test]
+fn test_connection_timeout_multiplier() {
+    let rpi = Duration::from_millis(10);
+
+    assert_eq!(
+        Duration::from_millis(40),
+        watchdog::connection_timeout(rpi, 0)
+    );
+    assert_eq!(
+        Duration::from_millis(80),
+        watchdog::connection_timeout(rpi, 1)
+    );
+    assert_eq!(
+        Duration::from_millis(5120),
+        watchdog::connection_timeout(rpi, 7)
+    );
+
+    // Reserved multipliers are treated as the largest one
+    assert_eq!(
+        Duration::from_millis(5120),
+        watchdog::connection_timeout(rpi, 9)
+    );
+}
+
+#[test]
+fn test_connection_watchdog_deadline() {
+    let opened_at = Instant::now();
+    let mut connection_watchdog = ConnectionWatchdog::new(Duration::from_millis(40), opened_at);
+
+    assert!(!connection_watchdog.is_timed_out(opened_at + Duration::from_millis(39)));
+    assert!(connection_watchdog.is_timed_out(opened_at + Duration::from_millis(40)));
+
+    // Every consumed packet restarts the timeout
+    connection_watchdog.packet_consumed(opened_at + Duration::from_millis(30));
+
+    assert_eq!(
+        opened_at + Duration::from_millis(70),
+        connection_watchdog.deadline()
+    );
+    assert!(!connection_watchdog.is_timed_out(opened_at + Duration::from_millis(40)));
+
+    // A late reported packet never moves the deadline backwards
+    connection_watchdog.packet_consumed(opened_at + Duration::from_millis(10));
+
+    assert_eq!(
+        opened_at + Duration::from_millis(70),
+        connection_watchdog.deadline()
+    );
+}
+
+#[tokio::test]
+async fn test_silent_adapter_times_out() {
+    let fake_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    let mut connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::LOCALHOST,
+        &clearlink_forward_open_request(),
+        &clearlink_forward_open_success(),
+    );
+    connection_config.local_address = "127.0.0.1:0".parse().unwrap();
+    connection_config.target_address = fake_adapter.local_addr().unwrap();
+
+    let implicit_connection: ImplicitConnection<u32, u32> =
+        ImplicitConnection::start(connection_config, 0x00000001)
+            .await
+            .unwrap();
+    let mut connection_events = implicit_connection.events();
+
+    assert!(!implicit_connection.is_timed_out());
+
+    // The adapter never produces any T->O packets
+    let connection_event = tokio::time::timeout(Duration::from_secs(1), connection_events.recv())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(ConnectionEvent::TimedOut, connection_event);
+    assert!(implicit_connection.is_timed_out());
+}
+
+#[tokio::test]
+async fn test_timed_out_connection_reconnects() {
+    let fake_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    let mut connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::LOCALHOST,
+        &clearlink_forward_open_request(),
+        &clearlink_forward_open_success(),
+    );
+    connection_config.local_address = "127.0.0.1:0".parse().unwrap();
+    connection_config.target_address = fake_adapter.local_addr().unwrap();
+
+    // The replacement Forward_Open returns a new O->T connection ID
+    let implicit_connection: ImplicitConnection<u32, u32> =
+        ImplicitConnection::start_with_reconnect(
+            connection_config,
+            0x00000001,
+            Some(Box::new(|| {
+                Box::pin(async {
+                    let mut forward_open = clearlink_forward_open_success();
+                    forward_open.o2t_connection_id = 0x0a0b0c02;
+                    Some(OpenedConnection {
+                        forward_open,
+                        o2t_socket_address: None,
+                        t2o_socket_address: None,
+                    })
+                })
+            })),
+        )
+        .await
+        .unwrap();
+    let mut connection_events = implicit_connection.events();
+
+    for expected_event in [ConnectionEvent::TimedOut, ConnectionEvent::Reconnected] {
+        let connection_event =
+            tokio::time::timeout(Duration::from_secs(1), connection_events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(expected_event, connection_event);
+    }
+
+    assert_eq!(0x0a0b0c02, implicit_connection.config().o2t_connection_id);
+
+    // The outputs are now produced on the new connection
+    let mut o2t_buffer = [0u8; 64];
+    let reconnected_identifier = tokio::time::timeout(Duration::from_secs(1), async {
+        loop {
+            let (o2t_bytes_read, _) = fake_adapter.recv_from(&mut o2t_buffer).await.unwrap();
+
+            let o2t_packet = ImplicitIoPacket::<u32>::read_le_args(
+                &mut std::io::Cursor::new(&o2t_buffer[..o2t_bytes_read]),
+                (true,),
+            )
+            .unwrap();
+
+            if o2t_packet.sequenced_address.connection_identifier != 0x0a0b0c01 {
+                return o2t_packet.sequenced_address.connection_identifier;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(0x0a0b0c02, reconnected_identifier);
+}
+
+#[tokio::test]
+async fn test_implicit_connection_ignores_other_sources() {
+    let fake_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let other_device = tokio::net::UdpSocket::bind("127.0.0.2:0").await.unwrap();
+
+    let mut connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::LOCALHOST,
+        &clearlink_forward_open_request(),
+        &clearlink_forward_open_success(),
+    );
+    connection_config.local_address = "127.0.0.1:0".parse().unwrap();
+    connection_config.target_address = fake_adapter.local_addr().unwrap();
+    connection_config.connection_timeout_multiplier = 7;
+
+    let implicit_connection: ImplicitConnection<u32, u32> =
+        ImplicitConnection::start(connection_config, 0x00000001)
+            .await
+            .unwrap();
+    let scanner_address = implicit_connection.local_address();
+
+    // A packet for this connection from a device other than the target isn't consumed
+    other_device
+        .send_to(&T2O_PACKET_BYTES, scanner_address)
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(None, implicit_connection.latest_input());
+
+    let mut input_receiver = implicit_connection.input_receiver();
+    fake_adapter
+        .send_to(&T2O_PACKET_BYTES, scanner_address)
+        .await
+        .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(1), input_receiver.changed())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(Some(0x12345678), implicit_connection.latest_input());
+}
+
+#[tokio::test]
+async fn test_reconnect_applies_socket_address_items() {
+    let fake_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let replacement_adapter = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let replacement_address = match replacement_adapter.local_addr().unwrap() {
+        SocketAddr::V4(replacement_address) => replacement_address,
+        SocketAddr::V6(_) => unreachable!(),
+    };
+
+    let mut connection_config = ImplicitConnectionConfig::new(
+        Ipv4Addr::LOCALHOST,
+        &clearlink_forward_open_request(),
+        &clearlink_forward_open_success(),
+    );
+    connection_config.local_address = "127.0.0.1:0".parse().unwrap();
+    connection_config.target_address = fake_adapter.local_addr().unwrap();
+
+    // The replacement Forward_Open reply names another O->T address
+    let implicit_connection: ImplicitConnection<u32, u32> =
+        ImplicitConnection::start_with_reconnect(
+            connection_config,
+            0x00000001,
+            Some(Box::new(move || {
+                Box::pin(async move {
+                    Some(OpenedConnection {
+                        forward_open: clearlink_forward_open_success(),
+                        o2t_socket_address: Some(replacement_address),
+                        t2o_socket_address: None,
+                    })
+                })
+            })),
+        )
+        .await
+        .unwrap();
+    let mut connection_events = implicit_connection.events();
+
+    for expected_event in [ConnectionEvent::TimedOut, ConnectionEvent::Reconnected] {
+        let connection_event =
+            tokio::time::timeout(Duration::from_secs(1), connection_events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(expected_event, connection_event);
+    }
+
+    assert_eq!(
+        SocketAddr::V4(replacement_address),
+        implicit_connection.config().target_address
+    );
+
+    // The outputs of the new connection go to the address from the reply
+    let mut o2t_buffer = [0u8; 64];
+    let (o2t_bytes_read, _) = tokio::time::timeout(
+        Duration::from_secs(1),
+        replacement_adapter.recv_from(&mut o2t_buffer),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let o2t_packet = ImplicitIoPacket::<u32>::read_le_args(
+        &mut std::io::Cursor::new(&o2t_buffer[..o2t_bytes_read]),
+        (true,),
+    )
+    .unwrap();
+    assert_eq!(0x00000001, o2t_packet.data);
+}
@@ -0,0 +1,265 @@
+use std::net::{SocketAddrV4, UdpSocket};
+use std::time::Duration;
+
+use binrw::{BinRead, BinWrite};
+
+use bilge::prelude::u4;
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::identity::{DeviceType, IdentityStatusBits, Revision, VendorId};
+use eipscanne_rs::cip::types::{CipByte, CipShortString};
+use eipscanne_rs::discovery;
+use eipscanne_rs::eip::command::{
+    CommandSpecificData, EnIpCommand, EncapsStatusCode, ListIdentityData, ListIdentityItem,
+};
+use eipscanne_rs::eip::description::SockAddrInfo;
+use eipscanne_rs::eip::packet::{EnIpPacketDescription, EncapsulationHeader};
+
+/*
+EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Identity
+    Encapsulation Header
+        Command: List Identity (0x0063)
+        Length: 49
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 1
+            Type ID: CIP Identity (0x000c)
+                Length: 43
+                Encapsulation Protocol Version: 1
+                Socket Address
+                    sin_family: 2
+                    sin_port: 44818
+                    sin_addr: 172.31.19.10
+                    sin_zero: 0000000000000000
+                Vendor ID: Teknic, Inc. (0x01a8)
+                Device Type: Generic Device (keyable) (0x002b)
+                Product Code: 1
+                Revision: 2.93
+                Status: 0x0000
+                Serial Number: 0x01ff3d32
+                Product Name Length: 9
+                Product Name: ClearLink
+                State: 0xff
+
+-------------------------------------
+Hex Dump:
+
+0000   63 00 31 00 00 00 00 00 00 00 00 00 00 00 00 00
+0010   00 00 00 00 00 00 00 00 01 00 0c 00 2b 00 01 00
+0020   00 02 af 12 ac 1f 13 0a 00 00 00 00 00 00 00 00
+0030   a8 01 2b 00 01 00 02 5d 00 00 32 3d ff 01 09 43
+0040   6c 65 61 72 4c 69 6e 6b ff
+
+*/
+const LIST_IDENTITY_RESPONSE_BYTES: [CipByte; 73] = [
+    0x63, 0x00, 0x31, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0c, 0x00, 0x2b, 0x00, 0x01, 0x00,
+    0x00, 0x02, 0xaf, 0x12, 0xac, 0x1f, 0x13, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xa8, 0x01, 0x2b, 0x00, 0x01, 0x00, 0x02, 0x5d, 0x00, 0x00, 0x32, 0x3d, 0xff, 0x01, 0x09, 0x43,
+    0x6c, 0x65, 0x61, 0x72, 0x4c, 0x69, 0x6e, 0x6b, 0xff,
+];
+
+fn expected_clearlink_identity_item() -> ListIdentityItem {
+    ListIdentityItem {
+        encapsulation_version: 1,
+        socket_address: SockAddrInfo {
+            sin_family: 2,
+            sin_port: 44818,
+            sin_addr: 0xac1f130a,
+            sin_zero: [0x00; 8],
+        },
+        vendor_id: VendorId::TeknicInc,
+        device_type: DeviceType::GenericDevice,
+        product_code: 0x1,
+        revision: Revision {
+            major: 2,
+            minor: 93,
+        },
+        status: IdentityStatusBits::new(
+            false,
+            false,
+            false,
+            false,
+            u4::new(0x0),
+            false,
+            false,
+            false,
+            false,
+            u4::new(0x0),
+        )
+        .into(),
+        serial_number: 0x01ff3d32,
+        product_name: CipShortString::from("ClearLink".to_string()),
+        state: 0xff,
+    }
+}
+
+#[test]
+fn test_serialize_list_identity_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Identity
+    Encapsulation Header
+        Command: List Identity (0x0063)
+        Length: 0
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   63 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x63, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let list_identity_packet = EnIpPacketDescription::new_list_identity_description();
+
+    let mut list_identity_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_identity_byte_array);
+
+    list_identity_packet.write(&mut writer).unwrap();
+
+    // Assert equality
+    assert_eq_hex!(expected_byte_array, list_identity_byte_array);
+}
+
+#[test]
+fn test_deserialize_list_identity_request() {
+    let raw_bytes: Vec<CipByte> = vec![
+        0x63, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let list_identity_packet =
+        EnIpPacketDescription::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(
+        CommandSpecificData::ListIdentity(None),
+        list_identity_packet.command_specific_data
+    );
+}
+
+#[test]
+fn test_deserialize_list_identity_response() {
+    let byte_cursor = std::io::Cursor::new(LIST_IDENTITY_RESPONSE_BYTES);
+    let mut buf_reader = std::io::BufReader::new(byte_cursor);
+
+    let list_identity_response = EnIpPacketDescription::read(&mut buf_reader).unwrap();
+
+    let expected_list_identity_response = EnIpPacketDescription {
+        header: EncapsulationHeader {
+            command: EnIpCommand::ListIdentity,
+            length: Some(49),
+            session_handle: 0x00,
+            status_code: EncapsStatusCode::Success,
+            sender_context: [0x00; 8],
+            options: 0x00,
+        },
+        command_specific_data: CommandSpecificData::ListIdentity(Some(ListIdentityData {
+            identity_items: vec![expected_clearlink_identity_item()],
+        })),
+    };
+
+    assert_eq!(expected_list_identity_response, list_identity_response);
+}
+
+#[test]
+fn test_serialize_list_identity_item() {
+    let list_identity_data = ListIdentityData {
+        identity_items: vec![expected_clearlink_identity_item()],
+    };
+
+    let mut list_identity_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_identity_byte_array);
+
+    list_identity_data.write(&mut writer).unwrap();
+
+    // Everything after the encapsulation header
+    assert_eq_hex!(
+        LIST_IDENTITY_RESPONSE_BYTES[24..].to_vec(),
+        list_identity_byte_array
+    );
+}
+
+#[test]
+fn test_deserialize_list_identity_item_with_trailing_bytes() {
+    // The ClearLink item from the reply above (type ID onwards) with the item length raised from
+    //  43 to 46 and 3 vendor bytes after the state, followed by the unchanged item
+    let identity_item_bytes = &LIST_IDENTITY_RESPONSE_BYTES[26..];
+
+    let mut raw_bytes: Vec<CipByte> = vec![0x02, 0x00];
+    raw_bytes.extend_from_slice(&[0x0c, 0x00, 0x2e, 0x00]);
+    raw_bytes.extend_from_slice(&identity_item_bytes[4..]);
+    raw_bytes.extend_from_slice(&[0xaa, 0xbb, 0xcc]);
+    raw_bytes.extend_from_slice(identity_item_bytes);
+
+    let list_identity_data = ListIdentityData::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(
+        vec![
+            expected_clearlink_identity_item(),
+            expected_clearlink_identity_item()
+        ],
+        list_identity_data.identity_items
+    );
+
+    // An item length too short for the name it carries is rejected
+    let mut short_item_bytes = identity_item_bytes.to_vec();
+    short_item_bytes[2] = 0x2a;
+
+    let mut raw_bytes: Vec<CipByte> = vec![0x01, 0x00];
+    raw_bytes.extend_from_slice(&short_item_bytes);
+
+    assert!(ListIdentityData::read(&mut std::io::Cursor::new(raw_bytes)).is_err());
+}
+
+#[test]
+fn test_sock_addr_info_to_socket_address() {
+    let socket_address = SocketAddrV4::from(expected_clearlink_identity_item().socket_address);
+
+    assert_eq!("172.31.19.10:44818".parse(), Ok(socket_address));
+}
+
+#[test]
+fn test_discover_on_loopback() {
+    let fake_adapter = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let fake_adapter_address = fake_adapter.local_addr().unwrap();
+
+    let adapter_thread = std::thread::spawn(move || {
+        let mut request_buffer = [0u8; 64];
+        let (request_bytes_read, scanner_address) =
+            fake_adapter.recv_from(&mut request_buffer).unwrap();
+
+        let request_packet = EnIpPacketDescription::read(&mut std::io::Cursor::new(
+            &request_buffer[..request_bytes_read],
+        ))
+        .unwrap();
+        assert_eq!(EnIpCommand::ListIdentity, request_packet.header.command);
+
+        fake_adapter
+            .send_to(&LIST_IDENTITY_RESPONSE_BYTES, scanner_address)
+            .unwrap();
+    });
+
+    let discovered_devices =
+        discovery::discover_on(fake_adapter_address, Duration::from_millis(500)).unwrap();
+
+    adapter_thread.join().unwrap();
+
+    assert_eq!(1, discovered_devices.len());
+    assert_eq!(fake_adapter_address, discovered_devices[0].source_address);
+    assert_eq!(
+        expected_clearlink_identity_item(),
+        discovered_devices[0].identity
+    );
+}
@@ -0,0 +1,137 @@
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::command::{CommandSpecificData, ListInterfacesData};
+use eipscanne_rs::eip::description::RawPacketItem;
+use eipscanne_rs::eip::packet::EnIpPacketDescription;
+use eipscanne_rs::object_assembly::RequestObjectAssembly;
+
+#[test]
+fn test_serialize_list_interfaces_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Interfaces
+    Encapsulation Header
+        Command: List Interfaces (0x0064)
+        Length: 0
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   64 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let list_interfaces_request = RequestObjectAssembly::new_list_interfaces();
+
+    let mut list_interfaces_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_interfaces_byte_array);
+
+    list_interfaces_request.write(&mut writer).unwrap();
+
+    // Assert equality
+    assert_eq_hex!(expected_byte_array, list_interfaces_byte_array);
+}
+
+#[test]
+fn test_deserialize_empty_list_interfaces_response() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Interfaces
+    Encapsulation Header
+        Command: List Interfaces (0x0064)
+        Length: 2
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 0
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   64 00 02 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x64, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let list_interfaces_response =
+        EnIpPacketDescription::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(
+        CommandSpecificData::ListInterfaces(Some(ListInterfacesData {
+            interface_items: vec![],
+        })),
+        list_interfaces_response.command_specific_data
+    );
+}
+
+#[test]
+fn test_deserialize_vendor_list_interfaces_response() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Interfaces
+    Encapsulation Header
+        Command: List Interfaces (0x0064)
+        Length: 10
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 1
+            Type ID: Unknown (0x1234)
+                Length: 4
+                Data: deadbeef
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   64 00 0a 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 01 00 34 12 04 00 de ad
+    0020   be ef
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x64, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x34, 0x12, 0x04, 0x00,
+        0xde, 0xad, 0xbe, 0xef,
+    ];
+
+    let list_interfaces_response =
+        EnIpPacketDescription::read(&mut std::io::Cursor::new(raw_bytes.clone())).unwrap();
+
+    let expected_command_specific_data =
+        CommandSpecificData::ListInterfaces(Some(ListInterfacesData {
+            interface_items: vec![RawPacketItem {
+                type_id: 0x1234,
+                data: vec![0xde, 0xad, 0xbe, 0xef],
+            }],
+        }));
+
+    assert_eq!(
+        expected_command_specific_data,
+        list_interfaces_response.command_specific_data
+    );
+
+    // Writing the response back out should reproduce the same bytes
+    let mut list_interfaces_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_interfaces_byte_array);
+
+    list_interfaces_response.write(&mut writer).unwrap();
+
+    assert_eq_hex!(raw_bytes, list_interfaces_byte_array);
+}
@@ -0,0 +1,292 @@
+use binrw::{BinRead, BinWrite};
+
+use bilge::prelude::{u2, u5, u7};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::eip::command::{
+    CommandSpecificData, EnIpCommand, EncapsStatusCode, ListServicesData, ListServicesItem,
+    ServiceCapabilityFlags, ServiceItem,
+};
+use eipscanne_rs::eip::description::RawPacketItem;
+use eipscanne_rs::eip::packet::{EnIpPacketDescription, EncapsulationHeader};
+use eipscanne_rs::object_assembly::{RequestObjectAssembly, ResponseObjectAssembly};
+
+#[test]
+fn test_serialize_list_services_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Services
+    Encapsulation Header
+        Command: List Services (0x0004)
+        Length: 0
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let list_services_request = RequestObjectAssembly::new_list_services();
+
+    let mut list_services_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_services_byte_array);
+
+    list_services_request.write(&mut writer).unwrap();
+
+    // Assert equality
+    assert_eq_hex!(expected_byte_array, list_services_byte_array);
+}
+
+#[test]
+fn test_deserialize_list_services_response() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Services
+    Encapsulation Header
+        Command: List Services (0x0004)
+        Length: 26
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 1
+            Type ID: List Services Response (0x0100)
+                Length: 20
+                Encapsulation Protocol Version: 1
+                Capability Flags: 0x0120, Supports CIP Encapsulation via TCP, Supports CIP Class 0 or 1 via UDP
+                    .... .... ..1. .... = Supports CIP Encapsulation via TCP: 1
+                    .... ...1 .... .... = Supports CIP Class 0 or 1 via UDP: 1
+                Name of Service: Communications
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 00 1a 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 01 00 00 01 14 00 01 00
+    0020   20 01 43 6f 6d 6d 75 6e 69 63 61 74 69 6f 6e 73
+    0030   00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x04, 0x00, 0x1a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x14, 0x00,
+        0x01, 0x00, 0x20, 0x01, 0x43, 0x6f, 0x6d, 0x6d, 0x75, 0x6e, 0x69, 0x63, 0x61, 0x74, 0x69,
+        0x6f, 0x6e, 0x73, 0x00, 0x00,
+    ];
+
+    let byte_cursor = std::io::Cursor::new(raw_bytes);
+    let mut buf_reader = std::io::BufReader::new(byte_cursor);
+
+    let list_services_response = ResponseObjectAssembly::<u8>::read(&mut buf_reader).unwrap();
+
+    let expected_packet_description = EnIpPacketDescription {
+        header: EncapsulationHeader {
+            command: EnIpCommand::ListServices,
+            length: Some(26),
+            session_handle: 0x00,
+            status_code: EncapsStatusCode::Success,
+            sender_context: [0x00; 8],
+            options: 0x00,
+        },
+        command_specific_data: CommandSpecificData::ListServices(Some(ListServicesData {
+            service_items: vec![ServiceItem::Communications(ListServicesItem {
+                encapsulation_version: 1,
+                capability_flags: ServiceCapabilityFlags::new(
+                    u5::new(0x0),
+                    true,
+                    u2::new(0x0),
+                    true,
+                    u7::new(0x0),
+                ),
+                service_name: *b"Communications\0\0",
+            })],
+        })),
+    };
+
+    // Assert equality
+    assert_eq!(
+        expected_packet_description,
+        list_services_response.packet_description
+    );
+    assert_eq!(None, list_services_response.cip_message);
+}
+
+#[test]
+fn test_list_services_item_capabilities() {
+    let tcp_only_item = ListServicesItem {
+        encapsulation_version: 1,
+        capability_flags: ServiceCapabilityFlags::from(0x0020),
+        service_name: *b"Communications\0\0",
+    };
+
+    assert_eq!("Communications", tcp_only_item.name());
+    assert!(tcp_only_item.supports_cip_over_tcp());
+    assert!(!tcp_only_item.supports_implicit_io());
+
+    let mut list_services_byte_array: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut list_services_byte_array);
+
+    tcp_only_item.write(&mut writer).unwrap();
+
+    assert_eq_hex!(
+        vec![
+            0x00, 0x01, 0x14, 0x00, 0x01, 0x00, 0x20, 0x00, 0x43, 0x6f, 0x6d, 0x6d, 0x75, 0x6e,
+            0x69, 0x63, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x00, 0x00,
+        ],
+        list_services_byte_array
+    );
+}
+
+#[test]
+fn test_deserialize_list_services_unknown_item() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Services
+    Encapsulation Header
+        Command: List Services (0x0004)
+        Length: 34
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 2
+            Type ID: Unknown (0x0200)
+                Length: 4
+                Data: deadbeef
+            Type ID: List Services Response (0x0100)
+                Length: 20
+                Encapsulation Protocol Version: 1
+                Capability Flags: 0x0020, Supports CIP Encapsulation via TCP
+                Name of Service: Communications
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 00 22 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 02 00 00 02 04 00 de ad
+    0020   be ef 00 01 14 00 01 00 20 00 43 6f 6d 6d 75 6e
+    0030   69 63 61 74 69 6f 6e 73 00 00
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x04, 0x00, 0x22, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x02, 0x04, 0x00,
+        0xde, 0xad, 0xbe, 0xef, 0x00, 0x01, 0x14, 0x00, 0x01, 0x00, 0x20, 0x00, 0x43, 0x6f, 0x6d,
+        0x6d, 0x75, 0x6e, 0x69, 0x63, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x00, 0x00,
+    ];
+
+    let list_services_response =
+        ResponseObjectAssembly::<u8>::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let CommandSpecificData::ListServices(Some(list_services_data)) = list_services_response
+        .packet_description
+        .command_specific_data
+    else {
+        panic!("Expected ListServices data");
+    };
+
+    // Items the scanner doesn't know are kept instead of failing the whole reply
+    assert_eq!(
+        ServiceItem::Unknown(RawPacketItem {
+            type_id: 0x0200,
+            data: vec![0xde, 0xad, 0xbe, 0xef],
+        }),
+        list_services_data.service_items[0]
+    );
+
+    let communications_item = list_services_data.communications().unwrap();
+    assert_eq!("Communications", communications_item.name());
+    assert!(communications_item.supports_cip_over_tcp());
+    assert!(!communications_item.supports_implicit_io());
+}
+
+#[test]
+fn test_deserialize_list_services_item_with_trailing_bytes() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000000, List Services
+    Encapsulation Header
+        Command: List Services (0x0004)
+        Length: 36
+        Session Handle: 0x00000000
+        Status: Success (0x00000000)
+        Sender Context: 0000000000000000
+        Options: 0x00000000
+    Command Specific Data
+        Item Count: 2
+            Type ID: List Services Response (0x0100)
+                Length: 24
+                Encapsulation Protocol Version: 1
+                Capability Flags: 0x0120, Supports CIP Encapsulation via TCP, Supports CIP Class 0 or 1 via UDP
+                Name of Service: Communications
+                Vendor Data: aabbccdd
+            Type ID: Unknown (0x0200)
+                Length: 2
+                Data: 1234
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   04 00 24 00 00 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 02 00 00 01 18 00 01 00
+    0020   20 01 43 6f 6d 6d 75 6e 69 63 61 74 69 6f 6e 73
+    0030   00 00 aa bb cc dd 00 02 02 00 12 34
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x04, 0x00, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x01, 0x18, 0x00,
+        0x01, 0x00, 0x20, 0x01, 0x43, 0x6f, 0x6d, 0x6d, 0x75, 0x6e, 0x69, 0x63, 0x61, 0x74, 0x69,
+        0x6f, 0x6e, 0x73, 0x00, 0x00, 0xaa, 0xbb, 0xcc, 0xdd, 0x00, 0x02, 0x02, 0x00, 0x12, 0x34,
+    ];
+
+    let list_services_response =
+        ResponseObjectAssembly::<u8>::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let CommandSpecificData::ListServices(Some(list_services_data)) = list_services_response
+        .packet_description
+        .command_specific_data
+    else {
+        panic!("Expected ListServices data");
+    };
+
+    // The vendor bytes past the service name don't shift the next item
+    let communications_item = list_services_data.communications().unwrap();
+    assert_eq!("Communications", communications_item.name());
+    assert!(communications_item.supports_implicit_io());
+
+    assert_eq!(
+        ServiceItem::Unknown(RawPacketItem {
+            type_id: 0x0200,
+            data: vec![0x12, 0x34],
+        }),
+        list_services_data.service_items[1]
+    );
+}
+
+#[test]
+fn test_deserialize_list_services_item_too_short() {
+    // An item length of 4 can't hold the version, flags and name, so only its raw bytes are kept
+    let raw_bytes: Vec<CipByte> = vec![0x01, 0x00, 0x00, 0x01, 0x04, 0x00, 0x01, 0x00, 0x20, 0x00];
+
+    let list_services_data = ListServicesData::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    assert_eq!(None, list_services_data.communications());
+    assert_eq!(
+        vec![ServiceItem::Unknown(RawPacketItem {
+            type_id: 0x0100,
+            data: vec![0x01, 0x00, 0x20, 0x00],
+        })],
+        list_services_data.service_items
+    );
+}
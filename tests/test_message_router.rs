@@ -16,7 +16,7 @@ fn test_serialize_service_container() {
     let expected_byte_array: Vec<CipByte> = vec![0x01];
 
     let service_container_bits = ServiceContainer::new(ServiceCode::GetAttributeAll, false);
-    let service_container = ServiceContainer::from(service_container_bits);
+    let service_container = service_container_bits;
 
     let mut service_container_bytes: Vec<u8> = Vec::new();
     let mut writer = std::io::Cursor::new(&mut service_container_bytes);
@@ -109,13 +109,11 @@ fn test_deserialize_empty_response() {
     let message_router_response = MessageRouterResponse::<u8>::read(&mut buf_reader).unwrap();
 
     let expected_message_router_response = MessageRouterResponse {
-        service_container: ServiceContainer::from(ServiceContainer::new(
-            ServiceCode::GetAttributeAll,
-            true,
-        )),
+        service_container: ServiceContainer::new(ServiceCode::GetAttributeAll, true),
         response_data: ResponseData {
             status: ResponseStatusCode::Success,
             additional_status_size: 0x0,
+            additional_status: vec![],
             data: Some(0x4),
         },
     };
@@ -127,10 +125,7 @@ fn test_deserialize_empty_response() {
 #[test]
 fn test_message_cip_path_byte_size() {
     let message_router_request = MessageRouterRequest::<u8> {
-        service_container: ServiceContainer::from(ServiceContainer::new(
-            ServiceCode::GetAttributeAll,
-            false,
-        )),
+        service_container: ServiceContainer::new(ServiceCode::GetAttributeAll, false),
         request_data: RequestData::new(CipPath::new(0x1, 0x1), None),
     };
 
@@ -217,3 +212,86 @@ fn test_message_cip_full_path_request_byte_size() {
 
     assert_eq!(8, tmp_output_buffer.len());
 }
+
+#[test]
+fn test_deserialize_attribute_not_settable_response() {
+    /*
+    Common Industrial Protocol
+        Service: Set Attribute Single (Response)
+            1... .... = Request/Response: Response (0x1)
+            .001 0000 = Service: Set Attribute Single (0x10)
+        Status: Attribute not settable:
+            General Status: Attribute not settable (0x0e)
+            Additional Status Size: 0 words
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   90 00 0e 00
+
+    */
+    let raw_byte_array: Vec<CipByte> = vec![0x90, 0x00, 0x0e, 0x00];
+
+    let message_router_response =
+        MessageRouterResponse::<u8>::read(&mut std::io::Cursor::new(raw_byte_array)).unwrap();
+
+    let expected_message_router_response = MessageRouterResponse {
+        service_container: ServiceContainer::new(ServiceCode::SetAttributeSingle, true),
+        response_data: ResponseData {
+            status: ResponseStatusCode::AttributeNotSettable,
+            additional_status_size: 0x0,
+            additional_status: vec![],
+            data: None,
+        },
+    };
+
+    assert_eq!(expected_message_router_response, message_router_response);
+    assert!(!message_router_response.response_data.status.is_success());
+}
+
+#[test]
+fn test_deserialize_extended_status_response() {
+    /*
+    Common Industrial Protocol
+        Service: Get Attribute Single (Response)
+        Status: Vendor specific error:
+            General Status: Vendor specific error (0x1f)
+            Additional Status Size: 2 words
+            Additional Status: 0x1234
+            Additional Status: 0x0001
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   8e 00 1f 02 34 12 01 00
+
+    */
+    let raw_byte_array: Vec<CipByte> = vec![0x8e, 0x00, 0x1f, 0x02, 0x34, 0x12, 0x01, 0x00];
+
+    let message_router_response =
+        MessageRouterResponse::<u8>::read(&mut std::io::Cursor::new(raw_byte_array)).unwrap();
+
+    let response_data = message_router_response.response_data;
+
+    assert_eq!(
+        ResponseStatusCode::VendorSpecificError,
+        response_data.status
+    );
+    assert_eq!(vec![0x1234, 0x0001], response_data.additional_status);
+    assert_eq!(Some(0x1234), response_data.extended_status());
+    assert_eq!(None, response_data.data);
+}
+
+#[test]
+fn test_deserialize_unknown_status_response() {
+    let raw_byte_array: Vec<CipByte> = vec![0x90, 0x00, 0xd0, 0x00];
+
+    let message_router_response =
+        MessageRouterResponse::<u8>::read(&mut std::io::Cursor::new(raw_byte_array)).unwrap();
+
+    // Codes that aren't in the specification keep their raw value
+    assert_eq!(
+        ResponseStatusCode::Unknown(0xd0),
+        message_router_response.response_data.status
+    );
+}
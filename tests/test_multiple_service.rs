@@ -0,0 +1,156 @@
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::message::multiple_service::{MultipleServiceReply, MultipleServiceRequest};
+use eipscanne_rs::cip::message::request::MessageRouterRequest;
+use eipscanne_rs::cip::message::response::{MessageRouterResponse, ResponseStatusCode};
+use eipscanne_rs::cip::message::shared::ServiceCode;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::types::{CipByte, CipUint};
+
+#[test]
+fn test_serialize_multiple_service_request() {
+    /*
+    Common Industrial Protocol
+    Service: Multiple Service Packet (Request)
+        0... .... = Request/Response: Request (0x0)
+        .000 1010 = Service: Multiple Service Packet (0x0a)
+    Request Path Size: 4 words
+    Request Path: Message Router, Instance: 0x0001
+    Multiple Service Packet (Request)
+        Number of Services: 2
+        Offset List
+            Offset: 6
+            Offset: 14
+        Get Attribute Single (Request)
+            Request Path: Identity, Instance: 0x01, Attribute: 0x01
+        Get Attribute Single (Request)
+            Request Path: Identity, Instance: 0x01, Attribute: 0x07
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   0a 04 21 00 02 00 25 00 01 00 02 00 06 00 0e 00
+    0010   0e 03 20 01 24 01 30 01 0e 03 20 01 24 01 30 07
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x0a, 0x04, 0x21, 0x00, 0x02, 0x00, 0x25, 0x00, 0x01, 0x00, 0x02, 0x00, 0x06, 0x00, 0x0e,
+        0x00, 0x0e, 0x03, 0x20, 0x01, 0x24, 0x01, 0x30, 0x01, 0x0e, 0x03, 0x20, 0x01, 0x24, 0x01,
+        0x30, 0x07,
+    ];
+
+    let mut multiple_service_request = MultipleServiceRequest::new();
+    multiple_service_request
+        .add_request(&MessageRouterRequest::<u8>::new(
+            ServiceCode::GetAttributeSingle,
+            CipPath::new_full(0x1, 0x1, 0x1),
+        ))
+        .unwrap()
+        .add_request(&MessageRouterRequest::<u8>::new(
+            ServiceCode::GetAttributeSingle,
+            CipPath::new_full(0x1, 0x1, 0x7),
+        ))
+        .unwrap();
+
+    let message_router_request =
+        MessageRouterRequest::new_multiple_service(multiple_service_request);
+
+    let mut message_router_bytes: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut message_router_bytes);
+
+    message_router_request.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, message_router_bytes);
+}
+
+#[test]
+fn test_deserialize_multiple_service_reply() {
+    /*
+    Common Industrial Protocol
+    Service: Multiple Service Packet (Response)
+        1... .... = Request/Response: Response (0x1)
+        .000 1010 = Service: Multiple Service Packet (0x0a)
+    Status: Embedded service error:
+        General Status: Embedded service error (0x1e)
+        Additional Status Size: 0 words
+    Multiple Service Packet (Response)
+        Number of Services: 2
+        Offset List
+            Offset: 6
+            Offset: 12
+        Get Attribute Single (Response)
+            Status: Success:
+            Data: 0100
+        Get Attribute Single (Response)
+            Status: Attribute not supported:
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   8a 00 1e 00 02 00 06 00 0c 00 8e 00 00 00 01 00
+    0010   8e 00 14 00
+
+    */
+    let raw_byte_array: Vec<CipByte> = vec![
+        0x8a, 0x00, 0x1e, 0x00, 0x02, 0x00, 0x06, 0x00, 0x0c, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x8e, 0x00, 0x14, 0x00,
+    ];
+
+    let message_router_response = MessageRouterResponse::<MultipleServiceReply>::read(
+        &mut std::io::Cursor::new(raw_byte_array),
+    )
+    .unwrap();
+
+    let multiple_service_reply = message_router_response
+        .into_multiple_service_reply(CipPath::new(0x2, 0x1))
+        .unwrap();
+    assert_eq!(2, multiple_service_reply.len());
+
+    let vendor_id_response = multiple_service_reply.response::<CipUint>(0).unwrap();
+    assert_eq!(
+        ResponseStatusCode::Success,
+        vendor_id_response.response_data.status
+    );
+    assert_eq!(Some(0x1), vendor_id_response.response_data.data);
+
+    let product_name_response = multiple_service_reply.response::<CipUint>(1).unwrap();
+    assert_eq!(
+        ResponseStatusCode::AttributeNotSupported,
+        product_name_response.response_data.status
+    );
+    assert_eq!(None, product_name_response.response_data.data);
+
+    assert!(multiple_service_reply.response::<CipUint>(2).is_err());
+}
+
+#[test]
+fn test_deserialize_multiple_service_reply_invalid_offset() {
+    // The second offset points back into the offset table
+    let raw_byte_array: Vec<CipByte> = vec![
+        0x02, 0x00, 0x06, 0x00, 0x02, 0x00, 0x8e, 0x00, 0x00, 0x00, 0x01, 0x00,
+    ];
+
+    let multiple_service_reply =
+        MultipleServiceReply::read(&mut std::io::Cursor::new(raw_byte_array));
+
+    assert!(multiple_service_reply.is_err());
+}
+
+#[test]
+fn test_serialize_multiple_service_request_offset_overflow() {
+    // The second request would start past the largest offset the table can hold
+    let multiple_service_request = MultipleServiceRequest {
+        embedded_requests: vec![
+            vec![0x0e; 0x10000],
+            vec![0x0e, 0x02, 0x20, 0x01, 0x24, 0x01],
+        ],
+    };
+
+    let mut request_bytes: Vec<u8> = Vec::new();
+    let write_result =
+        multiple_service_request.write(&mut std::io::Cursor::new(&mut request_bytes));
+
+    assert!(matches!(write_result, Err(binrw::Error::AssertFail { .. })));
+}
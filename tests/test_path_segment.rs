@@ -2,22 +2,29 @@ use std::vec;
 
 use binrw::{BinRead, BinWrite};
 
-use bilge::prelude::u3;
-
 use hex_test_macros::prelude::*;
 
 use eipscanne_rs::cip::path::{
-    CipPath, LogicalPathSegment, LogicalSegmentFormat, LogicalSegmentType, PathData, SegmentType,
+    CipPath, EPathFormat, ElectronicKeySegment, LogicalPathSegment, LogicalSegmentFormat,
+    LogicalSegmentType, NetworkSegment, PathData, PathSegment, PortSegment, SegmentType,
 };
+use eipscanne_rs::cip::symbol::SymbolicSegment;
 use eipscanne_rs::cip::types::CipByte;
 
+fn logical_segment(cip_path: &CipPath, segment_index: usize) -> &LogicalPathSegment {
+    match &cip_path.segments[segment_index] {
+        PathSegment::Logical(logical_segment) => logical_segment,
+        path_segment => panic!("expected a logical segment, found {:?}", path_segment),
+    }
+}
+
 #[test]
 fn test_serialize_path_segment() {
     let sample_path_segment_bits = LogicalPathSegment::new_u16(LogicalSegmentType::ClassId, 0x01);
 
     let expected_bytes = vec![0x21, 0x0, 0x01, 0x0];
 
-    let logical_path_segment = LogicalPathSegment::from(sample_path_segment_bits);
+    let logical_path_segment = sample_path_segment_bits;
 
     let mut sample_path_bytes: Vec<u8> = Vec::new();
     let mut writer = std::io::Cursor::new(&mut sample_path_bytes);
@@ -155,23 +162,21 @@ fn test_deserialize_cip_path() {
     let cip_path = CipPath::read(&mut buf_reader).unwrap();
 
     // Assert equality
-    assert_eq!(cip_path.class_id_segment.data, PathData::FormatAsU16(0x1));
+    assert_eq!(2, cip_path.segments.len());
     assert_eq!(
-        cip_path
-            .class_id_segment
-            .path_definition
-            .logical_segment_type(),
+        logical_segment(&cip_path, 0).data,
+        PathData::FormatAsU16(0x1)
+    );
+    assert_eq!(
+        logical_segment(&cip_path, 0).logical_segment_type(),
         LogicalSegmentType::ClassId
     );
     assert_eq!(
-        cip_path.instance_id_segment.data,
+        logical_segment(&cip_path, 1).data,
         PathData::FormatAsU16(0x1)
     );
     assert_eq!(
-        cip_path
-            .instance_id_segment
-            .path_definition
-            .logical_segment_type(),
+        logical_segment(&cip_path, 1).logical_segment_type(),
         LogicalSegmentType::InstanceId
     );
 }
@@ -199,37 +204,26 @@ fn test_deserialize_unknown_cip_path() {
     */
     let raw_bytes: Vec<CipByte> = vec![0b10011001, 0x00, 0x01, 0x00, 0x25, 0x00, 0x01, 0x00];
 
-    let byte_cursor = std::io::Cursor::new(raw_bytes);
+    let byte_cursor = std::io::Cursor::new(raw_bytes.clone());
     let mut buf_reader = std::io::BufReader::new(byte_cursor);
 
     // Read from buffered reader
-    let cip_path = CipPath::read(&mut buf_reader).unwrap();
+    let unknown_path_segment = LogicalPathSegment::read(&mut buf_reader).unwrap();
 
     // Assert equality
     assert_eq!(
-        cip_path.class_id_segment.path_definition.segment_type(),
-        SegmentType::Unknown(u3::new(0x4))
+        unknown_path_segment.path_definition.segment_type(),
+        SegmentType::DataSegment
     );
 
-    assert_eq!(cip_path.class_id_segment.data, PathData::FormatAsU16(0x1));
-    assert_eq!(
-        cip_path
-            .class_id_segment
-            .path_definition
-            .logical_segment_type(),
-        LogicalSegmentType::Unknown(u3::new(0x6))
-    );
-    assert_eq!(
-        cip_path.instance_id_segment.data,
-        PathData::FormatAsU16(0x1)
-    );
+    assert_eq!(unknown_path_segment.data, PathData::FormatAsU16(0x1));
     assert_eq!(
-        cip_path
-            .instance_id_segment
-            .path_definition
-            .logical_segment_type(),
-        LogicalSegmentType::InstanceId
+        unknown_path_segment.logical_segment_type(),
+        LogicalSegmentType::ServiceId
     );
+
+    // A data segment that isn't a symbol can't be read as part of a path
+    assert!(CipPath::read(&mut std::io::Cursor::new(raw_bytes)).is_err());
 }
 
 #[test]
@@ -257,3 +251,328 @@ fn test_full_path_byte_size() {
     // Assert equality
     assert_eq!(6, full_cip_path_buffer.len());
 }
+
+#[test]
+fn test_serialize_padded_and_packed_member_path() {
+    /*
+    Request Path: Assembly, Instance: 0x00012345, Member: 0x0102, Attribute: 0x03
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Assembly (0x04)
+    Path Segment: 0x26 (32-Bit Instance Segment)
+        Instance: 0x00012345
+    Path Segment: 0x29 (16-Bit Member Segment)
+        Member: 0x0102
+    Path Segment: 0x30 (8-Bit Attribute Segment)
+        Attribute: 3
+
+    -------------------------------------
+    Hex Dump (padded):
+
+    0000   20 04 26 00 45 23 01 00 29 00 02 01 30 03
+
+    Hex Dump (packed):
+
+    0000   20 04 26 45 23 01 00 29 02 01 30 03
+
+    */
+    let expected_padded_bytes: Vec<CipByte> = vec![
+        0x20, 0x04, 0x26, 0x00, 0x45, 0x23, 0x01, 0x00, 0x29, 0x00, 0x02, 0x01, 0x30, 0x03,
+    ];
+    let expected_packed_bytes: Vec<CipByte> = vec![
+        0x20, 0x04, 0x26, 0x45, 0x23, 0x01, 0x00, 0x29, 0x02, 0x01, 0x30, 0x03,
+    ];
+
+    let member_path = CipPath::default()
+        .with_class(0x04)
+        .with_instance(0x12345)
+        .with_member(0x0102)
+        .with_attribute(0x03);
+
+    let mut padded_bytes: Vec<u8> = Vec::new();
+    member_path
+        .write_format(
+            &mut std::io::Cursor::new(&mut padded_bytes),
+            EPathFormat::Padded,
+        )
+        .unwrap();
+
+    let mut packed_bytes: Vec<u8> = Vec::new();
+    member_path
+        .write_format(
+            &mut std::io::Cursor::new(&mut packed_bytes),
+            EPathFormat::Packed,
+        )
+        .unwrap();
+
+    assert_eq_hex!(expected_padded_bytes, padded_bytes);
+    assert_eq!(expected_packed_bytes, packed_bytes);
+    assert_eq!(7, member_path.word_size());
+
+    let packed_path = CipPath::read_sized(
+        &mut std::io::Cursor::new(expected_packed_bytes),
+        12,
+        EPathFormat::Packed,
+    )
+    .unwrap();
+    assert_eq!(member_path, packed_path);
+    assert_eq!(Some(0x12345), packed_path.instance_id());
+}
+
+#[test]
+fn test_serialize_port_segments() {
+    /*
+    Route Path: Port: 1, Address: 0 -> Port: 2, Address: 192.168.1.5 -> Port: 18, Address: 1
+    Path Segment: 0x01 (Port Segment)
+        ...0 .... = Extended Link Address: False
+        .... 0001 = Port: 1
+        Link Address: 0
+    Path Segment: 0x12 (Port Segment)
+        ...1 .... = Extended Link Address: True
+        .... 0010 = Port: 2
+        Link Address Size: 11
+        Link Address: 192.168.1.5
+        Pad: 00
+    Path Segment: 0x0f (Port Segment)
+        ...0 .... = Extended Link Address: False
+        .... 1111 = Port Identifier: Extended (15)
+        Extended Port: 18
+        Link Address: 1
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   01 00 12 0b 31 39 32 2e 31 36 38 2e 31 2e 35 00
+    0010   0f 12 00 01
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x01, 0x00, 0x12, 0x0b, 0x31, 0x39, 0x32, 0x2e, 0x31, 0x36, 0x38, 0x2e, 0x31, 0x2e, 0x35,
+        0x00, 0x0f, 0x12, 0x00, 0x01,
+    ];
+
+    let route_path = CipPath::default()
+        .with_port(1, 0)
+        .with_segment(PortSegment::new_extended(2, b"192.168.1.5".to_vec()).unwrap())
+        .with_port(18, 1);
+
+    let mut route_path_bytes: Vec<u8> = Vec::new();
+    route_path
+        .write(&mut std::io::Cursor::new(&mut route_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, route_path_bytes);
+
+    let deserialized_route_path = CipPath::read_sized(
+        &mut std::io::Cursor::new(expected_byte_array),
+        20,
+        EPathFormat::Padded,
+    )
+    .unwrap();
+    assert_eq!(route_path, deserialized_route_path);
+}
+
+#[test]
+fn test_network_segments_round_trip() {
+    /*
+    Connection Path: Production Inhibit Time: 10ms, Production Inhibit Time: 100000us, Assembly
+    Path Segment: 0x43 (Production Inhibit Time Network Segment)
+        Production Inhibit Time: 10ms
+    Path Segment: 0x51 (Production Inhibit Time in Microseconds Network Segment)
+        Data Size: 2 (words)
+        Production Inhibit Time: 100000us
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Assembly (0x04)
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   43 0a 51 02 a0 86 01 00 20 04
+
+    */
+    let expected_byte_array: Vec<CipByte> =
+        vec![0x43, 0x0a, 0x51, 0x02, 0xa0, 0x86, 0x01, 0x00, 0x20, 0x04];
+
+    let connection_path = CipPath::default()
+        .with_segment(NetworkSegment::ProductionInhibitTime(10))
+        .with_segment(NetworkSegment::ProductionInhibitTimeMicroseconds(100000))
+        .with_class(0x04);
+
+    let mut connection_path_bytes: Vec<u8> = Vec::new();
+    connection_path
+        .write(&mut std::io::Cursor::new(&mut connection_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, connection_path_bytes);
+
+    let deserialized_connection_path =
+        CipPath::read(&mut std::io::Cursor::new(expected_byte_array)).unwrap();
+    assert_eq!(connection_path, deserialized_connection_path);
+}
+
+#[test]
+fn test_electronic_key_segment_round_trip() {
+    /*
+    Connection Path: Electronic Key, Assembly, Instance: 0x66
+    Path Segment: 0x34 (Electronic Key Segment)
+        001. .... = Path Segment Type: Logical Segment (1)
+        ...1 01.. = Logical Segment Type: Special (5)
+        .... ..00 = Logical Segment Format: 8-bit Logical Segment (0)
+        Key Format: 4
+        Vendor ID: Rockwell Automation/Allen-Bradley (0x0001)
+        Device Type: Communications Adapter (12)
+        Product Code: 56
+        1... .... = Compatibility: Set
+        .000 0010 = Major Revision: 2
+        Minor Revision: 1
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Assembly (0x04)
+    Path Segment: 0x24 (8-Bit Instance Segment)
+        Instance: 0x66
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   34 04 01 00 0c 00 38 00 82 01 20 04 24 66
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x34, 0x04, 0x01, 0x00, 0x0c, 0x00, 0x38, 0x00, 0x82, 0x01, 0x20, 0x04, 0x24, 0x66,
+    ];
+
+    let connection_path = CipPath::default()
+        .with_segment(ElectronicKeySegment::new(0x1, 0xc, 0x38, 2, 1, true))
+        .with_class(0x04)
+        .with_instance(0x66);
+    assert_eq!(7, connection_path.word_size());
+
+    let mut connection_path_bytes: Vec<u8> = Vec::new();
+    connection_path
+        .write(&mut std::io::Cursor::new(&mut connection_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, connection_path_bytes);
+
+    let deserialized_connection_path =
+        CipPath::read(&mut std::io::Cursor::new(expected_byte_array)).unwrap();
+    assert_eq!(connection_path, deserialized_connection_path);
+
+    // Key formats other than 4 aren't defined
+    let other_key_format: Vec<CipByte> =
+        vec![0x34, 0x05, 0x01, 0x00, 0x0c, 0x00, 0x38, 0x00, 0x82, 0x01];
+    assert!(CipPath::read(&mut std::io::Cursor::new(other_key_format)).is_err());
+}
+
+#[test]
+fn test_service_id_segment_is_8_bit() {
+    // 0x38 is an 8-bit service ID, 0x39 the 16-bit form that the spec doesn't define
+    let service_path = CipPath::read(&mut std::io::Cursor::new(vec![0x38, 0x4c])).unwrap();
+    assert_eq!(
+        LogicalSegmentType::ServiceId,
+        logical_segment(&service_path, 0).logical_segment_type()
+    );
+    assert_eq!(0x4c, logical_segment(&service_path, 0).value());
+
+    assert!(CipPath::read(&mut std::io::Cursor::new(vec![0x39, 0x00, 0x4c, 0x00])).is_err());
+
+    let wide_service_path = CipPath::default().with_segment(LogicalPathSegment::new_u16(
+        LogicalSegmentType::ServiceId,
+        0x4c,
+    ));
+    let mut wide_service_bytes: Vec<u8> = Vec::new();
+    assert!(wide_service_path
+        .write(&mut std::io::Cursor::new(&mut wide_service_bytes))
+        .is_err());
+}
+
+#[test]
+fn test_symbolic_tag_path_round_trip() {
+    /*
+    Request Path: Program:Main.MyUDT.Member[3]
+    Path Segment: 0x91 (ANSI Extended Symbol Segment)
+        100. .... = Path Segment Type: Data Segment (4)
+        ...1 0001 = Data Segment Type: ANSI Extended Symbol Segment (0x11)
+        Data Size: 12
+        ANSI Symbol: Program:Main
+    Path Segment: 0x91 (ANSI Extended Symbol Segment)
+        Data Size: 5
+        ANSI Symbol: MyUDT
+        Pad Byte
+    Path Segment: 0x91 (ANSI Extended Symbol Segment)
+        Data Size: 6
+        ANSI Symbol: Member
+    Path Segment: 0x28 (8-Bit Member Segment)
+        001. .... = Path Segment Type: Logical Segment (1)
+        ...0 10.. = Logical Segment Type: Member ID (2)
+        .... ..00 = Logical Segment Format: 8-bit Logical Segment (0)
+        Member: 3
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   91 0c 50 72 6f 67 72 61 6d 3a 4d 61 69 6e 91 05
+    0010   4d 79 55 44 54 00 91 06 4d 65 6d 62 65 72 28 03
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x91, 0x0c, 0x50, 0x72, 0x6f, 0x67, 0x72, 0x61, 0x6d, 0x3a, 0x4d, 0x61, 0x69, 0x6e, 0x91,
+        0x05, 0x4d, 0x79, 0x55, 0x44, 0x54, 0x00, 0x91, 0x06, 0x4d, 0x65, 0x6d, 0x62, 0x65, 0x72,
+        0x28, 0x03,
+    ];
+
+    let tag_path = CipPath::from_tag_name("Program:Main.MyUDT.Member[3]").unwrap();
+    assert_eq!(
+        PathSegment::Symbolic(SymbolicSegment::new("Program:Main").unwrap()),
+        tag_path.segments[0]
+    );
+    assert_eq!(16, tag_path.word_size());
+
+    let mut tag_path_bytes: Vec<u8> = Vec::new();
+    tag_path
+        .write(&mut std::io::Cursor::new(&mut tag_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, tag_path_bytes);
+
+    let deserialized_tag_path =
+        CipPath::read(&mut std::io::Cursor::new(expected_byte_array)).unwrap();
+    assert_eq!(tag_path, deserialized_tag_path);
+    assert_eq!(
+        Some("Program:Main.MyUDT.Member[3]".to_string()),
+        deserialized_tag_path.tag_name()
+    );
+}
+
+#[test]
+fn test_symbolic_tag_path_array_indices() {
+    // Every dimension of an array index is a member segment, in the smallest format that fits
+    let tag_path = CipPath::from_tag_name("Matrix[1,300].Values[70000]").unwrap();
+
+    let expected_tag_path = CipPath::default()
+        .with_symbol("Matrix")
+        .unwrap()
+        .with_member(1)
+        .with_member(300)
+        .with_symbol("Values")
+        .unwrap()
+        .with_member(70000);
+    assert_eq!(expected_tag_path, tag_path);
+    assert_eq!(
+        Some("Matrix[1,300].Values[70000]".to_string()),
+        tag_path.tag_name()
+    );
+
+    // Only symbolic and member segments make up a tag name
+    assert_eq!(None, CipPath::new_full(0x4, 0x70, 0x3).tag_name());
+
+    assert!(CipPath::from_tag_name("").is_err());
+    assert!(CipPath::from_tag_name("Program:Main..Member").is_err());
+    assert!(CipPath::from_tag_name("3Tag").is_err());
+    assert!(CipPath::from_tag_name("Tag[3").is_err());
+    assert!(CipPath::from_tag_name("Tag[a]").is_err());
+
+    // Symbols are limited to ASCII names that fit in the one byte symbol size
+    assert!(SymbolicSegment::new("Température").is_err());
+    assert!(SymbolicSegment::new(&"T".repeat(256)).is_err());
+    assert!(CipPath::default().with_symbol("My Tag").is_err());
+    assert!(SymbolicSegment::new(&"T".repeat(255)).is_ok());
+}
@@ -0,0 +1,236 @@
+use binrw::BinWrite;
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::path::{
+    CipPath, ElectronicKeySegment, NetworkSegment, PathParseError, PathSegment,
+};
+use eipscanne_rs::cip::route::Route;
+use eipscanne_rs::cip::types::CipByte;
+
+#[test]
+fn test_parse_slash_path() {
+    /*
+    Request Path: Assembly, Instance: 0x96, Attribute: 0x03
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Assembly (0x04)
+    Path Segment: 0x24 (8-Bit Instance Segment)
+        Instance: 0x96
+    Path Segment: 0x30 (8-Bit Attribute Segment)
+        Attribute: 3
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   20 04 24 96 30 03
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![0x20, 0x04, 0x24, 0x96, 0x30, 0x03];
+
+    let cip_path: CipPath = "@4/150/3".parse().unwrap();
+    assert_eq!(CipPath::new_full(0x04, 0x96, 0x03), cip_path);
+
+    let mut cip_path_bytes: Vec<u8> = Vec::new();
+    cip_path
+        .write(&mut std::io::Cursor::new(&mut cip_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, cip_path_bytes);
+
+    // The keyword notation names the same path
+    let keyword_cip_path: CipPath = "class 0x04 instance 0x96 attr 3".parse().unwrap();
+    assert_eq!(cip_path, keyword_cip_path);
+    assert_eq!("@4/150/3", keyword_cip_path.to_string());
+}
+
+#[test]
+fn test_parse_path_picks_segment_format() {
+    /*
+    Request Path: Assembly, Instance: 0x00012345, Member: 0x0102
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Assembly (0x04)
+    Path Segment: 0x26 (32-Bit Instance Segment)
+        Instance: 0x00012345
+    Path Segment: 0x29 (16-Bit Member Segment)
+        Member: 0x0102
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   20 04 26 00 45 23 01 00 29 00 02 01
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x20, 0x04, 0x26, 0x00, 0x45, 0x23, 0x01, 0x00, 0x29, 0x00, 0x02, 0x01,
+    ];
+
+    let cip_path: CipPath = "class 4 instance 0x12345 member 258".parse().unwrap();
+
+    let mut cip_path_bytes: Vec<u8> = Vec::new();
+    cip_path
+        .write(&mut std::io::Cursor::new(&mut cip_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, cip_path_bytes);
+
+    // A member can't be written in the `@` form
+    assert_eq!(
+        "class 0x04 instance 0x12345 member 0x102",
+        cip_path.to_string()
+    );
+    assert_eq!(cip_path, cip_path.to_string().parse().unwrap());
+}
+
+#[test]
+fn test_parse_routed_paths() {
+    let backplane_route: Route = "1,0".parse().unwrap();
+
+    let routed_path: CipPath = "1,0 @1/1".parse().unwrap();
+    assert_eq!(
+        CipPath::default()
+            .with_class(0x1)
+            .with_instance(0x1)
+            .with_route(&backplane_route),
+        routed_path
+    );
+    assert_eq!("1,0 @1/1", routed_path.to_string());
+
+    let routed_tag_path: CipPath = "1,3,2,192.168.1.5,1,0 Program:Main.Values[2]"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        CipPath::from_tag_name("Program:Main.Values[2]")
+            .unwrap()
+            .with_route(&"1,3,2,192.168.1.5,1,0".parse().unwrap()),
+        routed_tag_path
+    );
+    assert_eq!(
+        "1,3,2,192.168.1.5,1,0 Program:Main.Values[2]",
+        routed_tag_path.to_string()
+    );
+
+    // A route on its own is a path too
+    let route_path: CipPath = "1,0".parse().unwrap();
+    assert_eq!(CipPath::from(backplane_route), route_path);
+    assert_eq!("1,0", route_path.to_string());
+}
+
+#[test]
+fn test_parse_one_word_keyword_tag() {
+    // A segment keyword on its own is a tag name, not a segment missing its value
+    let tag_path: CipPath = "class".parse().unwrap();
+    assert_eq!(CipPath::from_tag_name("class").unwrap(), tag_path);
+    assert_eq!("class", tag_path.to_string());
+    assert_eq!(tag_path, tag_path.to_string().parse().unwrap());
+
+    let routed_tag_path: CipPath = "1,0 attr".parse().unwrap();
+    assert_eq!(Some("attr".to_string()), routed_tag_path.tag_name());
+}
+
+#[test]
+fn test_parse_keyword_only_segments() {
+    /*
+    Connection Path: Connection Manager, Instance: 0x01, Production Inhibit Time, Symbol: Counter
+    Path Segment: 0x20 (8-Bit Class Segment)
+        Class: Connection Manager (0x06)
+    Path Segment: 0x24 (8-Bit Instance Segment)
+        Instance: 0x01
+    Path Segment: 0x43 (Network Segment: Production Inhibit Time)
+        Production Inhibit Time: 10ms
+    Path Segment: 0x50 (Network Segment: Safety)
+        Data: 0102
+    Path Segment: 0x91 (ANSI Extended Symbol Segment)
+        Data Size: 7
+        ANSI Symbol: Counter
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   20 06 24 01 43 0a 50 01 01 02 91 07 43 6f 75 6e
+    0010   74 65 72 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x20, 0x06, 0x24, 0x01, 0x43, 0x0a, 0x50, 0x01, 0x01, 0x02, 0x91, 0x07, 0x43, 0x6f, 0x75,
+        0x6e, 0x74, 0x65, 0x72, 0x00,
+    ];
+
+    let cip_path = CipPath::default()
+        .with_class(0x06)
+        .with_instance(0x01)
+        .with_segment(PathSegment::Network(NetworkSegment::ProductionInhibitTime(
+            10,
+        )))
+        .with_segment(PathSegment::Network(NetworkSegment::Safety(vec![
+            0x01, 0x02,
+        ])))
+        .with_symbol("Counter")
+        .unwrap();
+
+    // Segments without an `@` or tag form are written in the keyword form
+    let path_text = cip_path.to_string();
+    assert_eq!(
+        "class 0x06 instance 0x01 inhibit 10 safety 0x0102 symbol Counter",
+        path_text
+    );
+
+    let parsed_cip_path: CipPath = path_text.parse().unwrap();
+    assert_eq!(cip_path, parsed_cip_path);
+
+    let mut cip_path_bytes: Vec<u8> = Vec::new();
+    parsed_cip_path
+        .write(&mut std::io::Cursor::new(&mut cip_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, cip_path_bytes);
+}
+
+#[test]
+fn test_parse_electronic_key() {
+    let keyed_path = CipPath::default()
+        .with_segment(ElectronicKeySegment::new(0x1, 0xc, 0x38, 2, 1, false))
+        .with_class(0x04)
+        .with_instance(0x66);
+
+    let path_text = keyed_path.to_string();
+    assert_eq!(
+        "key 0x0001:0x000c:0x0038:2.1 class 0x04 instance 0x66",
+        path_text
+    );
+    assert_eq!(keyed_path, path_text.parse::<CipPath>().unwrap());
+
+    // Any compatible revision is accepted with the compatibility bit
+    let compatible_key_path: CipPath = "compatible_key 1:12:56:2.1".parse().unwrap();
+    assert_eq!(
+        CipPath::default().with_segment(ElectronicKeySegment::new(0x1, 0xc, 0x38, 2, 1, true)),
+        compatible_key_path
+    );
+    assert_eq!(
+        "compatible_key 0x0001:0x000c:0x0038:2.1",
+        compatible_key_path.to_string()
+    );
+}
+
+#[test]
+fn test_parse_invalid_paths() {
+    assert_eq!(
+        Err(PathParseError::new("@4/x", "\"x\" is not a number")),
+        "@4/x".parse::<CipPath>()
+    );
+
+    assert!("".parse::<CipPath>().is_err());
+    assert!("@".parse::<CipPath>().is_err());
+    assert!("@4/150/3/1".parse::<CipPath>().is_err());
+    assert!("class 4 instance".parse::<CipPath>().is_err());
+    assert!("class 4 slot 1".parse::<CipPath>().is_err());
+    assert!("1,256 @4/150/3".parse::<CipPath>().is_err());
+    assert!("1,0 Program:Main Tag".parse::<CipPath>().is_err());
+    assert!("inhibit 256".parse::<CipPath>().is_err());
+    assert!("safety 0x123".parse::<CipPath>().is_err());
+    assert!("service 0x100".parse::<CipPath>().is_err());
+    assert!("logical5 1".parse::<CipPath>().is_err());
+    assert!("key 1:12:56".parse::<CipPath>().is_err());
+    assert!("key 1:12:56:2".parse::<CipPath>().is_err());
+    assert!("key 1:12:0x10000:2.1".parse::<CipPath>().is_err());
+    assert!("key 1:12:56:128.1".parse::<CipPath>().is_err());
+}
@@ -0,0 +1,117 @@
+use binrw::BinWrite;
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::path::{CipPath, PortSegment};
+use eipscanne_rs::cip::route::Route;
+use eipscanne_rs::cip::types::CipByte;
+
+#[test]
+fn test_parse_backplane_route() {
+    /*
+    Route Path: Port: Backplane, Address: 0
+    Path Segment: 0x01 (Port Segment)
+        ...0 .... = Extended Link Address: False
+        .... 0001 = Port: 1
+        Link Address: 0
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   01 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![0x01, 0x00];
+
+    let route: Route = "1,0".parse().unwrap();
+    assert_eq!(Route::new().with_hop(1, 0), route);
+
+    let mut route_path_bytes: Vec<u8> = Vec::new();
+    CipPath::from(route)
+        .write(&mut std::io::Cursor::new(&mut route_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, route_path_bytes);
+}
+
+#[test]
+fn test_parse_bridged_route() {
+    /*
+    Route Path: Port: Backplane, Address: 3 -> Port: 2, Address: 192.168.1.5 -> Port: Backplane, Address: 0
+    Path Segment: 0x01 (Port Segment)
+        Port: 1
+        Link Address: 3
+    Path Segment: 0x12 (Port Segment)
+        ...1 .... = Extended Link Address: True
+        .... 0010 = Port: 2
+        Link Address Size: 11
+        Link Address: 192.168.1.5
+        Pad: 00
+    Path Segment: 0x01 (Port Segment)
+        Port: 1
+        Link Address: 0
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   01 03 12 0b 31 39 32 2e 31 36 38 2e 31 2e 35 00
+    0010   01 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x01, 0x03, 0x12, 0x0b, 0x31, 0x39, 0x32, 0x2e, 0x31, 0x36, 0x38, 0x2e, 0x31, 0x2e, 0x35,
+        0x00, 0x01, 0x00,
+    ];
+
+    let route: Route = "1,3,2,192.168.1.5,1,0".parse().unwrap();
+    assert_eq!(
+        vec![
+            PortSegment::new(1, 3),
+            PortSegment::new_extended(2, b"192.168.1.5".to_vec()).unwrap(),
+            PortSegment::new(1, 0),
+        ],
+        route.port_segments
+    );
+    assert_eq!("1,3,2,192.168.1.5,1,0", route.to_string());
+
+    // The route goes in front of the path to the target object
+    let routed_path = CipPath::new(0x1, 0x1).with_route(&route);
+
+    let mut routed_path_bytes: Vec<u8> = Vec::new();
+    routed_path
+        .write(&mut std::io::Cursor::new(&mut routed_path_bytes))
+        .unwrap();
+
+    assert_eq_hex!(expected_byte_array, routed_path_bytes[..18].to_vec());
+    assert_eq!(13, routed_path.word_size());
+}
+
+#[test]
+fn test_parse_invalid_routes() {
+    assert!("".parse::<Route>().is_err());
+    assert!("1".parse::<Route>().is_err());
+    assert!("1,0,2".parse::<Route>().is_err());
+    assert!("0,1".parse::<Route>().is_err());
+    assert!("1,256".parse::<Route>().is_err());
+    assert!("backplane,0".parse::<Route>().is_err());
+    assert!("2,".parse::<Route>().is_err());
+}
+
+#[test]
+fn test_reject_oversized_link_address() {
+    let long_link_address = "a".repeat(256);
+
+    assert!(PortSegment::new_extended(2, long_link_address.as_bytes().to_vec()).is_err());
+    assert!(format!("2,{}", long_link_address).parse::<Route>().is_err());
+
+    // A segment built by hand can't be written with a truncated size
+    let oversized_route_path = CipPath::default().with_segment(PortSegment {
+        port: 2,
+        link_address: long_link_address.into_bytes(),
+    });
+
+    let mut port_segment_bytes: Vec<u8> = Vec::new();
+    assert!(oversized_route_path
+        .write(&mut std::io::Cursor::new(&mut port_segment_bytes))
+        .is_err());
+}
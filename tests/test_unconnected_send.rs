@@ -0,0 +1,161 @@
+use binrw::{BinRead, BinWrite};
+
+use hex_test_macros::prelude::*;
+
+use eipscanne_rs::cip::message::response::ResponseStatusCode;
+use eipscanne_rs::cip::message::shared::ServiceCode;
+use eipscanne_rs::cip::path::CipPath;
+use eipscanne_rs::cip::route::Route;
+use eipscanne_rs::cip::types::CipByte;
+use eipscanne_rs::error::EipError;
+use eipscanne_rs::object_assembly::{RequestObjectAssembly, ResponseObjectAssembly};
+
+#[test]
+fn test_write_routed_set_attribute_single_request() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000003, Send RR Data
+        Encapsulation Header
+            Command: Send RR Data (0x006f)
+            Length: 44
+            Session Handle: 0x00000003
+            Status: Success (0x00000000)
+            Sender Context: 0000000000000000
+            Options: 0x00000000
+        Command Specific Data
+            Interface Handle: CIP (0x00000000)
+            Timeout: 0
+            Item Count: 2
+                Type ID: Null Address Item (0x0000)
+                    Length: 0
+                Type ID: Unconnected Data Item (0x00b2)
+                    Length: 28
+
+    Common Industrial Protocol
+        Service: Unconnected Send (Request)
+            0... .... = Request/Response: Request (0x0)
+            .101 0010 = Service: Unconnected Send (0x52)
+        Request Path Size: 4 words
+        Request Path: Connection Manager, Instance: 0x0001
+        CIP Connection Manager
+            Command Specific Data
+                Priority/Time_tick: 0x0a
+                    ...0 .... = Priority: 0
+                    .... 1010 = Tick time: 10 (1024 ms)
+                Time-out_ticks: 5
+                Actual Time Out: 5120ms
+                Message Request Size: 9
+                CIP Embedded Message Request
+                    Common Industrial Protocol
+                        Service: Set Attribute Single (Request)
+                        Request Path Size: 3 words
+                        Request Path: Assembly, Instance: 0x70, Attribute: 0x03
+                        Set Attribute Single (Request)
+                            Data: 01
+                Message Request Pad: 00
+                Route Path Size: 1 words
+                Reserved: 0x00
+                Route Path: Port: Backplane, Address: 0
+                    Path Segment: 0x01 (Port Segment)
+                        ...0 .... = Extended Link Address: False
+                        .... 0001 = Port: 1
+                        Link Address: 0
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 2c 00 03 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 1c 00 52 04 21 00 06 00 25 00
+    0030   01 00 0a 05 09 00 10 03 20 04 24 70 30 03 01 00
+    0040   01 00 01 00
+
+    */
+    let expected_byte_array: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x2c, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x1c, 0x00, 0x52, 0x04, 0x21, 0x00, 0x06,
+        0x00, 0x25, 0x00, 0x01, 0x00, 0x0a, 0x05, 0x09, 0x00, 0x10, 0x03, 0x20, 0x04, 0x24, 0x70,
+        0x30, 0x03, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00,
+    ];
+
+    let backplane_route: Route = "1,0".parse().unwrap();
+
+    let routed_request = RequestObjectAssembly::new_service_request(
+        0x3,
+        CipPath::new_full(0x4, 0x70, 0x3).with_route(&backplane_route),
+        ServiceCode::SetAttributeSingle,
+        Some(0x01u8),
+    );
+    assert_eq!(backplane_route, routed_request.route);
+
+    let mut byte_array_buffer: Vec<u8> = Vec::new();
+    let mut writer = std::io::Cursor::new(&mut byte_array_buffer);
+
+    routed_request.write(&mut writer).unwrap();
+
+    assert_eq_hex!(expected_byte_array, byte_array_buffer);
+}
+
+#[test]
+fn test_routing_error_reply() {
+    /*
+    EtherNet/IP (Industrial Protocol), Session: 0x00000003, Send RR Data
+        Encapsulation Header
+            Command: Send RR Data (0x006f)
+            Length: 23
+            Session Handle: 0x00000003
+            Status: Success (0x00000000)
+            Sender Context: 0000000000000000
+            Options: 0x00000000
+        Command Specific Data
+            Interface Handle: CIP (0x00000000)
+            Timeout: 0
+            Item Count: 2
+                Type ID: Null Address Item (0x0000)
+                    Length: 0
+                Type ID: Unconnected Data Item (0x00b2)
+                    Length: 7
+
+    Common Industrial Protocol
+        Service: Unconnected Send (Response)
+            1... .... = Request/Response: Response (0x1)
+            .101 0010 = Service: Unconnected Send (0x52)
+        Status: Connection failure: Port not available
+            General Status: Connection failure (0x01)
+            Additional Status Size: 1 words
+            Extended Status: 0x0311
+        Remaining Path Size: 1
+
+    -------------------------------------
+    Hex Dump:
+
+    0000   6f 00 17 00 03 00 00 00 00 00 00 00 00 00 00 00
+    0010   00 00 00 00 00 00 00 00 00 00 00 00 00 00 02 00
+    0020   00 00 00 00 b2 00 07 00 d2 00 01 01 11 03 01
+
+    */
+    let raw_bytes: Vec<CipByte> = vec![
+        0x6f, 0x00, 0x17, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x07, 0x00, 0xd2, 0x00, 0x01, 0x01, 0x11,
+        0x03, 0x01,
+    ];
+
+    let routed_response =
+        ResponseObjectAssembly::<u8>::read(&mut std::io::Cursor::new(raw_bytes)).unwrap();
+
+    let routed_result = routed_response.into_result(CipPath::new_full(0x4, 0x70, 0x3));
+
+    let Err(EipError::Routing {
+        general_status,
+        additional_status,
+        remaining_path_size,
+    }) = routed_result
+    else {
+        panic!("Expected a routing error, got {:?}", routed_result);
+    };
+
+    assert_eq!(ResponseStatusCode::ConnectionFailure, general_status);
+    assert_eq!(vec![0x0311], additional_status);
+    assert_eq!(Some(0x1), remaining_path_size);
+}